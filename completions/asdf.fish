@@ -59,13 +59,25 @@ complete -f -c asdf -n '__fish_asdf_needs_command' -a plugin-add -d "Add git rep
 complete -f -c asdf -n '__fish_asdf_using_command plugin-add; and __fish_asdf_arg_number 2' -a '(__fish_asdf_plugin_list_all | grep -v \'*\' | awk \'{ print $1 }\')'
 complete -f -c asdf -n '__fish_asdf_using_command plugin-add; and __fish_asdf_arg_number 3' -a '(__fish_asdf_plugin_list_all | grep (__fish_asdf_arg_at 3) | awk \'{ print $2 }\')'
 complete -f -c asdf -n '__fish_asdf_using_command plugin-add; and __fish_asdf_arg_number 4'
+complete -f -c asdf -n '__fish_asdf_using_command plugin-add' -l test -d "Check the plugin works and remove it if not"
+complete -f -c asdf -n '__fish_asdf_using_command plugin-add' -l ref -r -d "Add the plugin at a git ref and pin it there"
 
 # plugin-list completion
 complete -f -c asdf -n '__fish_asdf_needs_command' -a plugin-list -d "List installed plugins"
+complete -f -c asdf -n '__fish_asdf_using_command plugin-list' -l outdated -d "Show which plugins have an update available"
 
 # plugin-list-all completion
 complete -f -c asdf -n '__fish_asdf_needs_command' -a plugin-list-all -d "List all existing plugins"
 
+# plugin-new completion
+complete -f -c asdf -n '__fish_asdf_needs_command' -a plugin-new -d "Create a skeleton plugin repository"
+complete -f -c asdf -n '__fish_asdf_using_command plugin-new' -l repo -r -d "Git URL of the tool's releases"
+complete -f -c asdf -n '__fish_asdf_using_command plugin-new' -l optional-callbacks -d "Also create the optional callback scripts"
+
+# plugin-info completion
+complete -f -c asdf -n '__fish_asdf_needs_command' -a plugin-info -d "Show plugin API compatibility"
+complete -f -c asdf -n '__fish_asdf_using_command plugin-info; and __fish_asdf_arg_number 2' -a '(__fish_asdf_plugin_list)'
+
 # plugin-remove completion
 complete -f -c asdf -n '__fish_asdf_needs_command' -a plugin-remove -d "Remove plugin and package versions"
 complete -f -c asdf -n '__fish_asdf_using_command plugin-remove; and __fish_asdf_arg_number 2' -a '(__fish_asdf_plugin_list)'
@@ -74,6 +86,12 @@ complete -f -c asdf -n '__fish_asdf_using_command plugin-remove; and __fish_asdf
 complete -f -c asdf -n '__fish_asdf_needs_command' -a plugin-update -d "Update plugin"
 complete -f -c asdf -n '__fish_asdf_using_command plugin-update; and __fish_asdf_arg_number 2' -a '(__fish_asdf_plugin_list)'
 complete -f -c asdf -n '__fish_asdf_using_command plugin-update; and __fish_asdf_arg_number 2' -a --all
+complete -f -c asdf -n '__fish_asdf_using_command plugin-update' -l jobs -r -d "Number of plugins to update at once"
+complete -f -c asdf -n '__fish_asdf_using_command plugin-update' -l force -d "Discard local changes to plugins"
+complete -f -c asdf -n '__fish_asdf_using_command plugin-update' -l stash -d "Stash and reapply local changes to plugins"
+complete -f -c asdf -n '__fish_asdf_using_command plugin-update' -l unpin -d "Unpin plugins added with --ref"
+complete -f -c asdf -n '__fish_asdf_using_command plugin-update' -l dry-run -d "Print what the update would do"
+complete -f -c asdf -n '__fish_asdf_using_command plugin-update' -l fail-fast -d "Stop starting updates after one fails"
 
 # install completion
 complete -f -c asdf -n '__fish_asdf_needs_command' -a install -d "Install a specific version of a package"
@@ -84,6 +102,31 @@ complete -f -c asdf -n '__fish_asdf_using_command install; and __fish_asdf_arg_n
 complete -f -c asdf -n '__fish_asdf_needs_command' -a uninstall -d "Remove a specific version of a package"
 complete -f -c asdf -n '__fish_asdf_using_command uninstall; and __fish_asdf_arg_number 2' -a '(__fish_asdf_plugin_list)'
 complete -f -c asdf -n '__fish_asdf_using_command uninstall; and __fish_asdf_arg_number 3' -a '(__fish_asdf_list_versions (__fish_asdf_arg_at 3))'
+complete -f -c asdf -n '__fish_asdf_using_command install' -l pin -d "Write the resolved latest versions to .tool-versions"
+complete -c asdf -n '__fish_asdf_using_command install' -l tool-versions -r -d "Read versions from this file only"
+complete -c asdf -n '__fish_asdf_using_command install' -l file -r -d "Read versions from this file only"
+complete -c asdf -n '__fish_asdf_using_command current' -l tool-versions -r -d "Read versions from this file only"
+complete -f -c asdf -n '__fish_asdf_using_command current' -l full-paths -d "Print version file paths in full"
+complete -f -c asdf -n '__fish_asdf_using_command current' -l explain -d "List every source checked for the version"
+complete -c asdf -n '__fish_asdf_using_command exec' -l tool-versions -r -d "Read versions from this file only"
+complete -f -c asdf -n '__fish_asdf_using_command local' -l pin -d "Replace latest versions with the installed ones"
+complete -f -c asdf -n '__fish_asdf_using_command local' -l legacy -d "Also write the legacy version file of the plugin"
+complete -f -c asdf -n '__fish_asdf_using_command global' -l legacy -d "Also write the legacy version file of the plugin"
+complete -f -c asdf -n '__fish_asdf_using_command uninstall' -l yes -d "Do not ask for confirmation"
+complete -f -c asdf -n '__fish_asdf_using_command uninstall' -l all -d "Remove every version"
+complete -f -c asdf -n '__fish_asdf_using_command uninstall' -l all-but-current -d "Remove every version not set for the current directory or home"
+
+# prune completion
+complete -c asdf -n '__fish_asdf_needs_command' -a prune -d "Remove installed versions no version file references"
+complete -f -c asdf -n '__fish_asdf_using_command prune' -l yes -d "Do not ask for confirmation"
+complete -f -c asdf -n '__fish_asdf_using_command prune' -l dry-run -d "Print what would be removed"
+complete -f -c asdf -n '__fish_asdf_using_command prune' -l fail-fast -d "Stop at the first failure"
+
+# verify completion
+complete -f -c asdf -n '__fish_asdf_needs_command' -a verify -d "Check an installed version against its install receipt"
+complete -f -c asdf -n '__fish_asdf_using_command verify; and __fish_asdf_arg_number 2' -a '(__fish_asdf_plugin_list)'
+complete -f -c asdf -n '__fish_asdf_using_command verify; and __fish_asdf_arg_number 3' -a '(__fish_asdf_list_versions (__fish_asdf_arg_at 3))'
+complete -f -c asdf -n '__fish_asdf_using_command verify' -l repair -d "Reinstall the version if it differs"
 
 # current completion
 complete -f -c asdf -n '__fish_asdf_needs_command' -a current -d "Display version set or being used for package"
@@ -97,11 +140,23 @@ complete -f -c asdf -n '__fish_asdf_using_command where; and __fish_asdf_arg_num
 # which completion
 complete -f -c asdf -n '__fish_asdf_needs_command' -a which -d "Display executable path for a command"
 complete -f -c asdf -n '__fish_asdf_using_command which; and __fish_asdf_arg_number 2' -a '(__fish_asdf_list_shims)'
+complete -f -c asdf -n '__fish_asdf_using_command which' -l json -d "Print the plugin, version and path as JSON"
+complete -f -c asdf -n '__fish_asdf_using_command which' -l explain -d "Show how the executable was picked"
 
 # latest completion
 complete -f -c asdf -n '__fish_asdf_needs_command' -a latest -d "Show latest stable version of a package"
 complete -f -c asdf -n '__fish_asdf_using_command latest; and __fish_asdf_arg_number 2' -a '(__fish_asdf_plugin_list)'
 complete -f -c asdf -n '__fish_asdf_using_command latest; and __fish_asdf_arg_number 2' -a --all
+complete -f -c asdf -n '__fish_asdf_using_command latest' -l quiet -d "Only set the exit status"
+complete -f -c asdf -n '__fish_asdf_using_command latest' -l installed-only -d "Only consider installed versions"
+complete -f -c asdf -n '__fish_asdf_using_command latest' -l refresh -d "Run the plugin's bin/list-all instead of reading the cache"
+complete -f -c asdf -n '__fish_asdf_using_command latest' -l porcelain -d "Stable tab separated output for --all"
+complete -f -c asdf -n '__fish_asdf_using_command latest' -l short -d "Only plugin and version for --all"
+complete -f -c asdf -n '__fish_asdf_using_command latest' -l full-paths -d "Do not shorten columns to fit the terminal"
+
+# outdated completion
+complete -f -c asdf -n '__fish_asdf_needs_command' -a outdated -d "Show current versions older than the latest stable version"
+complete -f -c asdf -n '__fish_asdf_using_command outdated' -l json -d "Print the result as JSON"
 
 # list completion
 complete -f -c asdf -n '__fish_asdf_needs_command' -a list -d "List installed versions of a package"
@@ -110,11 +165,18 @@ complete -f -c asdf -n '__fish_asdf_using_command list; and __fish_asdf_arg_numb
 # list-all completion
 complete -f -c asdf -n '__fish_asdf_needs_command' -a list-all -d "List all versions of a package"
 complete -f -c asdf -n '__fish_asdf_using_command list-all; and __fish_asdf_arg_number 2' -a '(__fish_asdf_plugin_list)'
+complete -f -c asdf -n '__fish_asdf_using_command list-all' -l refresh -d "Run the plugin's bin/list-all instead of reading the cache"
 
 # reshim completion
 complete -f -c asdf -n '__fish_asdf_needs_command' -a reshim -d "Recreate shims for version of a package"
 complete -f -c asdf -n '__fish_asdf_using_command reshim; and __fish_asdf_arg_number 2' -a '(__fish_asdf_plugin_list)'
 complete -f -c asdf -n '__fish_asdf_using_command reshim; and __fish_asdf_arg_number 3' -a '(__fish_asdf_list_versions (__fish_asdf_arg_at 3))'
+complete -f -c asdf -n '__fish_asdf_using_command reshim' -l dry-run -d "Print the shims that would change"
+complete -f -c asdf -n '__fish_asdf_using_command install' -l dry-run -d "Print what would be installed"
+complete -f -c asdf -n '__fish_asdf_using_command uninstall' -l dry-run -d "Print what would be removed"
+complete -f -c asdf -n '__fish_asdf_using_command reshim' -l fail-fast -d "Stop at the first failure"
+complete -f -c asdf -n '__fish_asdf_using_command install' -l fail-fast -d "Stop at the first failure"
+complete -f -c asdf -n '__fish_asdf_using_command uninstall' -l fail-fast -d "Stop at the first failure"
 
 # local completion
 complete -f -c asdf -n '__fish_asdf_needs_command' -a local -d "Set local version for a plugin"
@@ -129,9 +191,50 @@ complete -f -c asdf -n '__fish_asdf_using_command global; and test (count (comma
 # shell completion
 complete -f -c asdf -n '__fish_asdf_needs_command' -a shell -d "Set version for a plugin in current shell session"
 complete -f -c asdf -n '__fish_asdf_using_command shell; and __fish_asdf_arg_number 2' -a '(__fish_asdf_plugin_list)'
+complete -f -c asdf -n '__fish_asdf_using_command shell; and __fish_asdf_arg_number 2' -a --unset -d "Restore the variables set in this shell"
 complete -f -c asdf -n '__fish_asdf_using_command shell; and test (count (commandline -opc)) -gt 2' -a '(__fish_asdf_list_versions (__fish_asdf_arg_at 3)) system'
 
+# shimexec completion
+complete -f -c asdf -n '__fish_asdf_needs_command' -a shimexec -d "Run a command as its shim would"
+complete -f -c asdf -n '__fish_asdf_using_command shimexec; and __fish_asdf_arg_number 2' -a '(__fish_asdf_list_shims)'
+complete -f -c asdf -n '__fish_asdf_using_command shimexec' -l dry-run -d "Show how the shim resolves without running it"
+
+# completion completion
+complete -f -c asdf -n '__fish_asdf_needs_command' -a completion -d "Print the completion script for a shell"
+complete -f -c asdf -n '__fish_asdf_using_command completion; and __fish_asdf_arg_number 2' -a 'bash zsh fish'
+
+# shellenv completion
+complete -f -c asdf -n '__fish_asdf_needs_command' -a shellenv -d "Print the shell code that sets up asdf in a shell"
+complete -f -c asdf -n '__fish_asdf_using_command shellenv; and __fish_asdf_arg_number 2' -a 'bash zsh sh ksh fish nu pwsh'
+
+# init completion
+complete -f -c asdf -n '__fish_asdf_needs_command' -a init -d "Print the shell code that sets up asdf and its completions"
+complete -f -c asdf -n '__fish_asdf_using_command init; and __fish_asdf_arg_number 2' -a 'bash zsh fish nu pwsh'
+complete -f -c asdf -n '__fish_asdf_using_command init' -l no-completions -d "Leave out the completions"
+complete -f -c asdf -n '__fish_asdf_using_command init' -l command-not-found -d "Suggest how to get a missing command"
+
+# gc completion
+complete -f -c asdf -n '__fish_asdf_needs_command' -a gc -d "Remove old temp dirs and downloads"
+complete -f -c asdf -n '__fish_asdf_using_command gc' -l dry-run -d "Show what would be removed"
+
+# check completion
+complete -f -c asdf -n '__fish_asdf_needs_command' -a check -d "Check a .tool-versions file for malformed lines"
+complete -F -c asdf -n '__fish_asdf_using_command check' -l fix -d "Fix whitespace and duplicate entries"
+complete -F -c asdf -n '__fish_asdf_using_command check' -l installed -d "Report plugins and versions that are not installed"
+
+# cache completion
+complete -f -c asdf -n '__fish_asdf_needs_command' -a cache -d "Cached versions of plugins"
+complete -f -c asdf -n '__fish_asdf_using_command cache; and __fish_asdf_arg_number 2' -a clear -d "Clear the cached versions of plugins"
+complete -f -c asdf -n '__fish_asdf_using_command cache; and __fish_asdf_arg_number 3' -a '(__fish_asdf_plugin_list)'
+
+# stats completion
+complete -f -c asdf -n '__fish_asdf_needs_command' -a stats -d "Usage statistics"
+complete -f -c asdf -n '__fish_asdf_using_command stats; and __fish_asdf_arg_number 2' -a commands -d "Summarize the commands run through shims"
+complete -f -c asdf -n '__fish_asdf_using_command stats' -l all -d "Summarize every project"
+
 # misc
 complete -f -c asdf -n '__fish_asdf_needs_command' -l "help" -d "Displays help"
 complete -f -c asdf -n '__fish_asdf_needs_command' -a "info" -d "Print OS, Shell and ASDF debug information"
+complete -f -c asdf -n '__fish_asdf_needs_command' -a "explain-config" -d "Print effective config values and their source"
+complete -f -c asdf -n '__fish_asdf_using_command explain-config; and __fish_asdf_arg_number 2' -a '(asdf __complete explain-config "")'
 complete -f -c asdf -n '__fish_asdf_needs_command' -l "version" -d "Displays asdf version"
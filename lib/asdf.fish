@@ -7,7 +7,13 @@ function asdf
     switch "$command"
         case "shell"
             # source commands that need to export variables
-            command asdf export-shell-version fish $argv | source # asdf_allow: source
+            env ASDF_SHELL_PID=$fish_pid asdf export-shell-version fish $argv | source # asdf_allow: source
+        case "env"
+            if test "$argv[1]" = "--restore"
+                env ASDF_SHELL_PID=$fish_pid asdf export-shell-version fish --restore | source # asdf_allow: source
+            else
+                command asdf env $argv
+            end
         case '*'
             # forward other commands to asdf script
             command asdf "$command" $argv
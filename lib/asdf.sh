@@ -9,7 +9,14 @@ asdf() {
   case "$command" in
   "shell")
     # commands that need to export variables
-    eval "$(asdf export-shell-version sh "$@")" # asdf_allow: eval
+    eval "$(ASDF_SHELL_PID=$$ command asdf export-shell-version sh "$@")" # asdf_allow: eval
+    ;;
+  "env")
+    if [ "${1:-}" = "--restore" ]; then
+      eval "$(ASDF_SHELL_PID=$$ command asdf export-shell-version sh --restore)" # asdf_allow: eval
+    else
+      command asdf env "$@" # asdf_allow: ' asdf '
+    fi
     ;;
   *)
     # forward other commands to asdf script
@@ -0,0 +1,34 @@
+# -*- sh -*-
+
+# Explains why a command was not found and how asdf can provide it. This
+# command is hidden and only meant to be called by the command not found
+# handlers `asdf init --command-not-found` prints.
+command_not_found_command() {
+  local command_name=$1
+
+  printf "%s: command not found\\n" "$command_name" >&2
+
+  local plugins_path
+  plugins_path=$(get_plugin_path)
+
+  local found=false plugin_path plugin_name version
+  for plugin_path in "$plugins_path"/*/; do
+    [ -d "$plugin_path" ] || continue
+    plugin_name=$(basename "$plugin_path")
+    for version in $(list_installed_versions "$plugin_name"); do
+      if plugin_executables "$plugin_name" "$version" | grep -q "/$command_name\$"; then
+        printf "%s %s provides it but has no shim for it, run \`asdf reshim %s %s\`\\n" \
+          "$plugin_name" "$version" "$plugin_name" "$version" >&2
+        found=true
+      fi
+    done
+  done
+
+  if [ "$found" = false ] && [ -f "$(asdf_data_dir)/repository/plugins/$command_name" ]; then
+    printf "It can be installed with \`asdf plugin add %s\`\\n" "$command_name" >&2
+  fi
+
+  exit 127
+}
+
+command_not_found_command "$@"
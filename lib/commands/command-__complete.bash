@@ -0,0 +1,194 @@
+# -*- sh -*-
+# shellcheck source=lib/functions/versions.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/versions.bash"
+
+# Prints the completion candidates for an asdf command line, one per line. The
+# arguments are the words after `asdf`, the last of which is the word being
+# completed. This command is hidden and only meant to be called by the shell
+# completion scripts.
+complete_command() {
+  local words=("$@")
+  [ "${#words[@]}" -eq 0 ] && words=("")
+
+  local current="${words[${#words[@]} - 1]}"
+  local position=${#words[@]}
+  local cmd="${words[0]}"
+
+  # Treat `asdf plugin add` like `asdf plugin-add`
+  while [ "$position" -gt 2 ] && [ -f "$(asdf_dir)/lib/commands/command-${cmd}-${words[1]}.bash" ]; do
+    cmd="${cmd}-${words[1]}"
+    words=("$cmd" "${words[@]:2}")
+    position=$((position - 1))
+  done
+
+  local plugin_name="${words[1]}"
+
+  if [ "$position" -eq 1 ]; then
+    complete_commands
+  else
+    case "$cmd" in
+    plugin | list | shim | stats | cache)
+      [ "$position" -eq 2 ] && complete_sub_commands "$cmd"
+      ;;
+    plugin-add)
+      [ "$position" -eq 2 ] && complete_repository_plugins && printf "%s\\n" --refresh --test --ref
+      ;;
+    plugin-list)
+      printf "%s\\n" --urls --refs --outdated
+      ;;
+    plugin-list-all)
+      printf "%s\\n" --refresh
+      ;;
+    plugin-update)
+      [ "$position" -eq 2 ] && list_installed_plugins
+      printf "%s\\n" --all --jobs --force --stash --unpin --dry-run --fail-fast
+      ;;
+    plugin-remove | plugin-info | plugin-test | help)
+      [ "$position" -eq 2 ] && list_installed_plugins
+      ;;
+    current | list | list-all)
+      [ "$position" -eq 2 ] && list_installed_plugins
+      printf "%s\\n" --json
+      [ "$cmd" = "current" ] && printf "%s\\n" --tool-versions --full-paths --explain
+      [ "$cmd" = "list-all" ] && printf "%s\\n" --refresh
+      ;;
+    cache-clear)
+      [ "$position" -eq 2 ] && list_installed_plugins
+      ;;
+    latest)
+      [ "$position" -eq 2 ] && list_installed_plugins && printf "%s\\n" --all
+      printf "%s\\n" --quiet --installed-only --refresh --json --porcelain --short --full-paths
+      ;;
+    outdated)
+      printf "%s\\n" --json
+      ;;
+    explain-config)
+      [ "$position" -eq 2 ] && asdf_config_keys
+      ;;
+    install)
+      if [ "$position" -eq 2 ]; then
+        list_installed_plugins
+      elif [ "$position" -eq 3 ]; then
+        printf "%s\\n" latest
+        list_all_command "$plugin_name" 2>/dev/null | tr ' ' '\n'
+      fi
+      printf "%s\\n" --jobs --keep-download --pin --dry-run --fail-fast --tool-versions --file
+      ;;
+    uninstall | where | reshim | verify)
+      if [ "$position" -eq 2 ]; then
+        list_installed_plugins
+      elif [ "$position" -eq 3 ]; then
+        list_installed_versions "$plugin_name"
+      fi
+      [ "$cmd" = "where" ] && printf "%s\\n" --json
+      [ "$cmd" = "verify" ] && printf "%s\\n" --repair
+      [ "$cmd" = "uninstall" ] && printf "%s\\n" --yes --dry-run --all --all-but-current --fail-fast
+      [ "$cmd" = "reshim" ] && printf "%s\\n" --dry-run --fail-fast
+      ;;
+    local | global | shell)
+      if [ "$position" -eq 2 ]; then
+        list_installed_plugins
+        [ "$cmd" != "shell" ] && printf "%s\\n" --pin --legacy
+        [ "$cmd" = "shell" ] && printf "%s\\n" --unset
+      else
+        list_installed_versions "$plugin_name"
+        printf "%s\\n" system latest
+      fi
+      ;;
+    which | exec | env | shim-versions | shimexec)
+      [ "$position" -eq 2 ] && complete_shims
+      [ "$cmd" = "which" ] && printf "%s\\n" --json --explain
+      [ "$cmd" = "exec" ] && [ "$position" -eq 2 ] && printf "%s\\n" --tool-versions
+      [ "$cmd" = "shimexec" ] && printf "%s\\n" --dry-run
+      [ "$cmd" = "env" ] && [ "$position" -eq 2 ] && printf "%s\\n" --restore --direnv
+      ;;
+    completion)
+      [ "$position" -eq 2 ] && printf "%s\\n" bash zsh fish
+      ;;
+    shellenv)
+      [ "$position" -eq 2 ] && printf "%s\\n" bash zsh sh ksh fish nu pwsh
+      ;;
+    init)
+      [ "$position" -eq 2 ] && printf "%s\\n" bash zsh fish nu pwsh
+      printf "%s\\n" --no-completions --command-not-found
+      ;;
+    info)
+      printf "%s\\n" --paths
+      ;;
+    gc)
+      printf "%s\\n" --dry-run
+      ;;
+    prune)
+      printf "%s\\n" --yes --dry-run --fail-fast
+      ;;
+    check)
+      printf "%s\\n" --fix --installed
+      ;;
+    stats-commands)
+      printf "%s\\n" --all
+      ;;
+    version)
+      printf "%s\\n" --verbose
+      ;;
+    update)
+      printf "%s\\n" --head
+      ;;
+    esac
+  fi | filter_completions "$current"
+}
+
+# Keeps the candidates that start with the word being completed. Flags are
+# only offered once the word starts with a dash.
+filter_completions() {
+  local current=$1
+
+  if [[ "$current" == -* ]]; then
+    awk -v prefix="$current" 'NF && index($0, prefix) == 1'
+  else
+    awk -v prefix="$current" 'NF && index($0, prefix) == 1 && !/^-/'
+  fi
+}
+
+complete_commands() {
+  local command_file command_name
+  for command_file in "$(asdf_dir)"/lib/commands/command-*.bash; do
+    command_name=$(basename "$command_file" .bash)
+    command_name=${command_name#command-}
+    case "$command_name" in
+    __* | export-shell-version) ;;
+    explain-config) printf "%s\\n" "$command_name" ;;
+    *) printf "%s\\n" "$command_name" "${command_name%%-*}" ;;
+    esac
+  done | awk '!seen[$0]++'
+  printf "%s\\n" shell
+}
+
+complete_sub_commands() {
+  local parent=$1
+  local command_file command_name
+  for command_file in "$(asdf_dir)"/lib/commands/command-"$parent"-*.bash; do
+    [ -f "$command_file" ] || continue
+    command_name=$(basename "$command_file" .bash)
+    command_name=${command_name#command-"$parent"-}
+    printf "%s\\n" "${command_name%%-*}"
+  done | awk '!seen[$0]++'
+}
+
+# Plugins in the short-name repository that are not installed yet. The
+# repository is never synced here, as completion must stay fast and offline.
+complete_repository_plugins() {
+  local plugin_index
+  for plugin_index in "$(asdf_data_dir)"/repository/plugins/*; do
+    [ -f "$plugin_index" ] || continue
+    [ -d "$(get_plugin_path "$(basename "$plugin_index")")" ] || basename "$plugin_index"
+  done
+}
+
+complete_shims() {
+  local shim
+  for shim in "$(asdf_data_dir)"/shims/*; do
+    [ -f "$shim" ] && basename "$shim"
+  done
+}
+
+complete_command "$@"
@@ -0,0 +1,21 @@
+# -*- sh -*-
+
+cache_clear_command() {
+  local plugin_name=$1
+
+  if [ "$#" -gt 1 ]; then
+    display_error "usage: asdf cache clear [<name>]"
+    exit 1
+  fi
+
+  if [ -n "$plugin_name" ]; then
+    check_if_plugin_exists "$plugin_name"
+    clear_list_all_cache "$plugin_name"
+    printf "Cleared the cached versions of %s\\n" "$plugin_name"
+  else
+    clear_list_all_cache
+    printf "Cleared the cached versions of all plugins\\n"
+  fi
+}
+
+cache_clear_command "$@"
@@ -0,0 +1,68 @@
+# -*- sh -*-
+# shellcheck source=lib/functions/tool_versions.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/tool_versions.bash"
+# shellcheck source=lib/functions/versions.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/versions.bash"
+# shellcheck source=lib/functions/uninstalls.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/uninstalls.bash"
+
+check_command() {
+  local fix=false
+  local installed=false
+  local positional=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    --fix)
+      fix=true
+      shift
+      ;;
+    --installed)
+      installed=true
+      shift
+      ;;
+    *)
+      positional+=("$1")
+      shift
+      ;;
+    esac
+  done
+
+  set -- "${positional[@]}"
+
+  if [ "$#" -gt 1 ]; then
+    display_error "usage: asdf check [<file>] [--fix] [--installed]"
+    exit 1
+  fi
+
+  local file=${1:-$(find_tool_versions)}
+  if [ -z "$file" ]; then
+    display_error "No $(version_file_name) file found in $PWD or its parents"
+    exit 1
+  elif [ ! -f "$file" ]; then
+    display_error "No such version file: $file"
+    exit 1
+  fi
+
+  if [ "$fix" = true ]; then
+    fix_tool_versions_file "$file" || exit 1
+  fi
+
+  local status=0
+  lint_tool_versions_file report "$file" || status=1
+
+  if [ "$installed" = true ]; then
+    check_tool_versions_installed "$file" || status=1
+    # Legacy version files are only read for the directory, not a given file
+    if [ "$#" -eq 0 ]; then
+      check_legacy_versions_installed || status=1
+    fi
+  fi
+
+  if [ "$status" -ne 0 ]; then
+    exit 1
+  fi
+  printf "No problems found in %s\\n" "$file"
+}
+
+check_command "$@"
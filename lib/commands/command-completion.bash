@@ -0,0 +1,26 @@
+# -*- sh -*-
+
+completion_command() {
+  local shell=$1
+  local completion_file
+
+  case "$shell" in
+  bash)
+    completion_file="asdf.bash"
+    ;;
+  zsh)
+    completion_file="_asdf"
+    ;;
+  fish)
+    completion_file="asdf.fish"
+    ;;
+  *)
+    display_error "usage: asdf completion <bash|zsh|fish>"
+    exit 1
+    ;;
+  esac
+
+  cat "$(asdf_dir)/completions/$completion_file"
+}
+
+completion_command "$@"
@@ -2,12 +2,18 @@
 # shellcheck source=lib/functions/plugins.bash
 . "$(dirname "$(dirname "$0")")/lib/functions/plugins.bash"
 
+# Exits with status 2 when the plugin is not installed and returns 1 when the
+# version is not installed or 126 when no version is set, so scripts can tell
+# these apart.
 # shellcheck disable=SC2059
 plugin_current_command() {
   local plugin_name=$1
   local terminal_format=$2
+  local json=$3
+  local max_width=$4
 
-  check_if_plugin_exists "$plugin_name"
+  check_current_plugin_exists "$plugin_name"
+  check_plugin_deprecations "$plugin_name"
 
   local search_path
   search_path=$(pwd)
@@ -28,17 +34,60 @@ plugin_current_command() {
   done
   check_for_deprecated_plugin "$plugin_name"
 
+  if [ "$json" = true ]; then
+    print_current_json "$plugin_name" "$full_version" "$version_file_path" "$version_not_installed"
+  fi
+
   if [ -n "$version_not_installed" ]; then
     description="Not installed. Run \"asdf install $plugin $version\""
-    printf "$terminal_format" "$plugin" "$version" "$description" 1>&2
+    [ "$json" = true ] || printf "$terminal_format" "$plugin" "$version" "$(styled error "$description" 2)" 1>&2
     return 1
   elif [ -z "$full_version" ]; then
     description="No version is set. Run \"asdf <global|shell|local> $plugin <version>\""
-    printf "$terminal_format" "$plugin" "______" "$description" 1>&2
+    [ "$json" = true ] || printf "$terminal_format" "$plugin" "______" "$(styled warning "$description" 2)" 1>&2
     return 126
   else
-    description="$version_file_path"
-    printf "$terminal_format" "$plugin" "$full_version" "$description"
+    # The version file path is shortened to what is left of the line
+    local path_width=""
+    if [ -n "$max_width" ]; then
+      local plugin_width=$((${#plugin} > 15 ? ${#plugin} : 15))
+      local version_width=$((${#full_version} > 15 ? ${#full_version} : 15))
+      path_width=$((max_width - plugin_width - version_width - 2))
+    fi
+    description=$(shorten_path "$version_file_path" "$path_width")
+    # The version is padded before it is styled, so escape codes don't count
+    # towards its width
+    local padded_version
+    printf -v padded_version "%-15s" "$full_version"
+    [ "$json" = true ] || printf "$terminal_format" "$plugin" "$(styled success "$padded_version")" "$description"
+  fi
+}
+
+# Like check_if_plugin_exists, but exits with status 2 so a missing plugin is
+# not mistaken for a version that is not installed.
+check_current_plugin_exists() {
+  if ! (check_if_plugin_exists "$1"); then
+    exit 2
+  fi
+}
+
+print_current_json() {
+  local plugin_name=$1
+  local full_version=$2
+  local version_file_path=$3
+  local version_not_installed=$4
+
+  printf '{"plugin":%s,' "$(json_string "$plugin_name")"
+  if [ -z "$full_version" ]; then
+    printf '"version":null,"source":null,"installed":false}'
+  else
+    printf '"version":%s,' "$(json_string "$full_version")"
+    printf '"source":%s,' "$(json_string "$version_file_path")"
+    if [ -n "$version_not_installed" ]; then
+      printf '"installed":false}'
+    else
+      printf '"installed":true}'
+    fi
   fi
 }
 
@@ -47,22 +96,132 @@ current_command() {
   local terminal_format="%-15s %-15s %-10s\\n"
   local exit_status=0
   local plugin
+  local json=false
+  local full_paths=false
+  local explain=false
+  local positional=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    --json)
+      json=true
+      shift
+      ;;
+    --full-paths)
+      full_paths=true
+      shift
+      ;;
+    --explain)
+      explain=true
+      shift
+      ;;
+    --tool-versions)
+      use_tool_versions_file "$2"
+      shift 2
+      ;;
+    *)
+      positional+=("$1")
+      shift
+      ;;
+    esac
+  done
+
+  set -- "${positional[@]}"
+
+  if [ "$json" = true ]; then
+    ASDF_WARNINGS_FORMAT=json
+  fi
+
+  if [ "$explain" = true ]; then
+    if [ $# -eq 0 ]; then
+      local separator=""
+      # shellcheck disable=SC2119
+      for plugin in $(plugin_list_command); do
+        printf "%s" "$separator"
+        explain_current_version "$plugin"
+        separator="\\n"
+      done
+    else
+      check_current_plugin_exists "$1"
+      explain_current_version "$1"
+    fi
+    exit 0
+  fi
+
+  local max_width=""
+  if [ "$json" != true ] && [ "$full_paths" != true ] && [ -t 1 ]; then
+    max_width=$(terminal_width)
+  fi
 
   # printf "$terminal_format" "PLUGIN" "VERSION" "SET BY CONFIG" # disable this until we release headings across the board
   if [ $# -eq 0 ]; then
+    local separator=""
+    [ "$json" = true ] && printf "["
     # shellcheck disable=SC2119
     for plugin in $(plugin_list_command); do
-      plugin_current_command "$plugin" "$terminal_format"
+      [ "$json" = true ] && printf "%s" "$separator"
+      plugin_current_command "$plugin" "$terminal_format" "$json" "$max_width"
+      separator=","
     done
+    [ "$json" = true ] && printf "]\\n"
   else
     plugin=$1
-    plugin_current_command "$plugin" "$terminal_format"
+    plugin_current_command "$plugin" "$terminal_format" "$json" "$max_width"
     exit_status="$?"
+    [ "$json" = true ] && printf "\\n"
   fi
 
   exit "$exit_status"
 }
 
+# Prints every place find_versions looks in for the version of a plugin, in
+# the order it looks, with what each one sets, followed by the place that
+# won. Places after the winner are not checked.
+explain_current_version() {
+  local plugin_name=$1
+
+  printf "%s\\n" "$plugin_name"
+
+  explain_checked() {
+    printf "  %-14s%s\\n" "$1" "$2"
+  }
+
+  explain_origin() {
+    local kind=$1
+    local origin=$2
+    local version=$3
+
+    case $kind in
+    env)
+      explain_checked "${version:-not set}" "$origin environment variable"
+      ;;
+    file)
+      if [ -f "$origin" ]; then
+        explain_checked "${version:-not set}" "$origin"
+      else
+        explain_checked "no file" "$origin"
+      fi
+      ;;
+    resolvers)
+      explain_checked "${version:-not set}" "version_resolvers = $(get_asdf_config_value "version_resolvers")"
+      ;;
+    esac
+
+    [ -n "$version" ] || return 1
+    printf "%s %s is set by %s\\n" "$plugin_name" "$version" "$origin"
+  }
+
+  if asdf_strict; then
+    local version_env_var
+    version_env_var="ASDF_$(printf "%s\\n" "$plugin_name" | tr '[:lower:]-' '[:upper:]_')_VERSION"
+    explain_checked "skipped" "$version_env_var environment variable (ASDF_STRICT)"
+  fi
+
+  if ! each_version_origin "$plugin_name" "$PWD" explain_origin; then
+    printf "No version is set for %s\\n" "$plugin_name"
+  fi
+}
+
 # Warn if the plugin isn't using the updated legacy file api.
 check_for_deprecated_plugin() {
   local plugin_name=$1
@@ -75,8 +234,7 @@ check_for_deprecated_plugin() {
   local new_script="${plugin_path}/bin/list-legacy-filenames"
 
   if [ "$legacy_config" = "yes" ] && [ -f "$deprecated_script" ] && [ ! -f "$new_script" ]; then
-    printf "Heads up! It looks like your %s plugin is out of date. You can update it with:\\n\\n" "$plugin_name"
-    printf "  asdf plugin-update %s\\n\\n" "$plugin_name"
+    asdf_warn "the $plugin_name plugin is out of date. You can update it with \`asdf plugin update $plugin_name\`"
   fi
 }
 
@@ -0,0 +1,282 @@
+# -*- sh -*-
+# shellcheck source=lib/functions/tool_versions.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/tool_versions.bash"
+
+doctor_command() {
+  case "$1" in
+  "")
+    doctor_checks
+    ;;
+  --trace-last)
+    doctor_trace_last "${2:-1}"
+    ;;
+  *)
+    display_error "usage: asdf doctor [--trace-last [<count>]]"
+    exit 1
+    ;;
+  esac
+}
+
+# Runs every check and prints its problems with a suggested fix. Exits with 1
+# when any check found a problem.
+doctor_checks() {
+  local total=0
+
+  doctor_check "PATH" doctor_check_path
+  doctor_check "shims" doctor_check_shims
+  doctor_check "orphaned shims" doctor_check_orphaned_shims
+  doctor_check "plugins" doctor_check_plugin_callbacks
+  doctor_check "legacy files" doctor_check_legacy_version_file
+  doctor_check "version files" doctor_check_version_files
+
+  if [ "$total" -eq 0 ]; then
+    printf "\\n%s\\n" "$(styled success "No problems found")"
+  else
+    printf "\\n%s\\n" "$(styled error "Found $(problem_count "$total")")"
+    exit 1
+  fi
+}
+
+problem_count() {
+  if [ "$1" -eq 1 ]; then
+    printf "1 problem\\n"
+  else
+    printf "%s problems\\n" "$1"
+  fi
+}
+
+# Prints the status of one check. Check functions print a "<problem>\t<fix>"
+# line for each problem they find.
+doctor_check() {
+  local name=$1
+  local check=$2
+
+  local problems
+  problems=$("$check")
+
+  if [ -z "$problems" ]; then
+    printf "%-16s%s\\n" "$name" "$(styled success "ok")"
+    return 0
+  fi
+
+  local count
+  count=$(wc -l <<<"$problems" | tr -d ' ')
+  total=$((total + count))
+  printf "%-16s%s\\n" "$name" "$(styled error "$(problem_count "$count")")"
+  awk -F '\t' '{ printf "  %s\n    fix: %s\n", $1, $2 }' <<<"$problems"
+}
+
+# The shims dir has to come before any other directory on PATH with an
+# executable of the same name, or that executable runs instead of the shim.
+doctor_check_path() {
+  local shims_path
+  shims_path="$(asdf_data_dir)/shims"
+
+  local path_dirs path_dir
+  IFS=':' read -r -a path_dirs <<<"$PATH"
+
+  local found=false
+  for path_dir in "${path_dirs[@]}"; do
+    if [ "${path_dir%/}" = "${shims_path%/}" ]; then
+      found=true
+      break
+    fi
+  done
+
+  if [ "$found" = "false" ]; then
+    printf "%s is not on PATH\\t%s\\n" "$shims_path" "load asdf.sh from your shell config, which adds it to the start of PATH"
+    return 0
+  fi
+
+  local shim_path shadowed
+  for path_dir in "${path_dirs[@]}"; do
+    if [ "${path_dir%/}" = "${shims_path%/}" ]; then
+      break
+    fi
+
+    shadowed=""
+    for shim_path in "$shims_path"/*; do
+      [ -e "$shim_path" ] || break
+      if is_executable "$path_dir/$(basename "$shim_path")"; then
+        shadowed="$shadowed $(basename "$shim_path")"
+      fi
+    done
+
+    if [ -n "$shadowed" ]; then
+      printf "%s comes before %s on PATH and shadows:%s\\t%s\\n" "$path_dir" "$shims_path" "$shadowed" "move $shims_path to the start of PATH, after any other PATH changes in your shell config"
+    fi
+  done
+}
+
+# Every executable of an installed version needs a shim that lists the version
+doctor_check_shims() {
+  local shims_path
+  shims_path="$(asdf_data_dir)/shims"
+
+  local plugin_name version executable_path executable_name missing
+  for plugin_name in $(list_installed_plugins); do
+    for version in $(list_installed_versions "$plugin_name"); do
+      missing=""
+      while IFS= read -r executable_path; do
+        [ -n "$executable_path" ] || continue
+        executable_name=$(basename "$executable_path")
+        if ! grep -qx "# asdf-plugin: $plugin_name $version" "$shims_path/$executable_name" 2>/dev/null; then
+          missing="$missing $executable_name"
+        fi
+      done <<<"$(plugin_executables "$plugin_name" "$version")"
+
+      if [ -n "$missing" ]; then
+        printf "%s %s has executables without a shim:%s\\t%s\\n" "$plugin_name" "$version" "$missing" "run \`asdf reshim $plugin_name $version\`"
+      fi
+    done
+  done
+}
+
+# A shim none of whose versions is installed any more fails whenever it runs
+doctor_check_orphaned_shims() {
+  local shims_path
+  shims_path="$(asdf_data_dir)/shims"
+
+  local installed="" plugin_name version
+  for plugin_name in $(list_installed_plugins); do
+    for version in $(list_installed_versions "$plugin_name"); do
+      installed="$installed"$'\n'"$plugin_name $version"
+    done
+  done
+  installed="$installed"$'\n'
+
+  local shim_path live
+  for shim_path in "$shims_path"/*; do
+    [ -e "$shim_path" ] || break
+
+    live=false
+    while read -r plugin_name version; do
+      if [[ "$installed" == *$'\n'"$plugin_name $version"$'\n'* ]]; then
+        live=true
+        break
+      fi
+    done <<<"$(awk '/^# asdf-plugin: / { print $3, $4 }' "$shim_path")"
+
+    if [ "$live" = "false" ]; then
+      printf "%s has no installed versions\\t%s\\n" "$shim_path" "remove it with \`rm $shim_path\`"
+    fi
+  done
+}
+
+# bin/list-all and bin/install are the callbacks every plugin has to provide
+doctor_check_plugin_callbacks() {
+  local plugin_name plugin_path script
+  for plugin_name in $(list_installed_plugins); do
+    plugin_path=$(get_plugin_path "$plugin_name")
+    for script in list-all install; do
+      if [ ! -f "$plugin_path/bin/$script" ]; then
+        printf "%s plugin has no bin/%s\\t%s\\n" "$plugin_name" "$script" "update it with \`asdf plugin update $plugin_name\`, or report it to the plugin author"
+      elif [ ! -x "$plugin_path/bin/$script" ]; then
+        printf "%s plugin's bin/%s is not executable\\t%s\\n" "$plugin_name" "$script" "run \`chmod +x $plugin_path/bin/$script\`"
+      fi
+    done
+  done
+}
+
+# legacy_version_file has to be yes for the legacy version files of plugins,
+# such as .nvmrc, to be read
+doctor_check_legacy_version_file() {
+  local config_path="${ASDF_CONFIG_FILE:-"$HOME/.asdfrc"}"
+  local legacy_config
+  legacy_config=$(get_asdf_config_value "legacy_version_file")
+
+  if [ "$legacy_config" != "yes" ] && [ "$legacy_config" != "no" ]; then
+    printf "legacy_version_file is %s, which is neither yes nor no\\t%s\\n" "$legacy_config" "set legacy_version_file = yes or no in $config_path"
+    return 0
+  fi
+
+  local plugin_name legacy_filenames filename search_path search_paths
+  local supported=false
+  IFS=$'\n' read -rd '' -a search_paths <<<"$(upward_search_paths "$PWD")"
+
+  for plugin_name in $(list_installed_plugins); do
+    legacy_filenames=$(plugin_legacy_filenames "$plugin_name")
+    if [ -z "$legacy_filenames" ]; then
+      continue
+    fi
+    supported=true
+
+    if [ "$legacy_config" = "yes" ]; then
+      continue
+    fi
+    for search_path in "${search_paths[@]}"; do
+      for filename in $legacy_filenames; do
+        if [ -f "$search_path/$filename" ]; then
+          printf "%s is ignored by the %s plugin as legacy_version_file is no\\t%s\\n" "$search_path/$filename" "$plugin_name" "set legacy_version_file = yes in $config_path, or move the version to $(version_file_name)"
+        fi
+      done
+    done
+  done
+
+  if [ "$legacy_config" = "yes" ] && [ "$supported" = "false" ]; then
+    printf "legacy_version_file is yes but no installed plugin reads legacy version files\\t%s\\n" "set legacy_version_file = no in $config_path"
+  fi
+}
+
+# Version files that can't be read are skipped silently when looking up
+# versions, as are malformed lines in the others
+doctor_check_version_files() {
+  local file_names file_name
+  read -r -a file_names <<<"$(version_file_names)"
+
+  local search_paths search_path version_file version_files=()
+  IFS=$'\n' read -rd '' -a search_paths <<<"$(upward_search_paths "$PWD")"
+  for file_name in "${file_names[@]}"; do
+    version_files+=("${search_paths[@]/%//$file_name}" "$HOME/$file_name")
+  done
+
+  for version_file in "${version_files[@]}"; do
+    if [ -d "$version_file" ]; then
+      printf "%s is a directory\\t%s\\n" "$version_file" "remove or rename it"
+    elif [ -e "$version_file" ] && [ ! -r "$version_file" ]; then
+      printf "%s is not readable\\t%s\\n" "$version_file" "run \`chmod u+r $version_file\`"
+    elif [ -f "$version_file" ]; then
+      lint_tool_versions_file doctor "$version_file"
+    fi
+  done | awk '!seen[$0]++'
+}
+
+# Prints the most recent failed plugin script invocations from the trace,
+# newest first.
+doctor_trace_last() {
+  local count=$1
+
+  if ! [[ "$count" =~ ^[1-9][0-9]*$ ]]; then
+    display_error "count must be a positive integer"
+    exit 1
+  fi
+
+  local trace_file
+  trace_file="$(asdf_data_dir)/trace"
+
+  # Lines kept when the trace is trimmed are appended after newer ones, so
+  # they are put back in order by their timestamp
+  local failures
+  if [ -f "$trace_file" ]; then
+    failures=$(awk -F '\t' '$4 != 0' "$trace_file" | sort -s -t "$(printf '\t')" -k 1,1 | tail -n "$count" | awk '{ lines[NR] = $0 } END { for (i = NR; i > 0; i--) print lines[i] }')
+  fi
+
+  if [ -z "$failures" ]; then
+    printf "No failed plugin scripts recorded\\n"
+    return 0
+  fi
+
+  # Fields may be empty, so they are split with awk rather than read, which
+  # would merge consecutive tabs
+  awk -F '\t' '{
+    if (NR > 1) print ""
+    printf "%s %s %s exited with %s after %ss\n", $1, $2, $3, $4, $5
+    printf "  %-12s%s\n", "directory", $6
+    printf "  %-12s%s\n", "arguments", ($7 == "" ? "(none)" : $7)
+    printf "  %s\n", "environment"
+    n = split($8, variables, "\037")
+    for (i = 1; i <= n; i++) printf "    %s\n", variables[i]
+  }' <<<"$failures"
+}
+
+doctor_command "$@"
@@ -10,6 +10,16 @@ shim_env_command() {
     exit 1
   fi
 
+  if [ "$shim_name" = "--direnv" ]; then
+    direnv_env
+    exit $?
+  fi
+
+  if [ "$shim_name" = "--restore" ]; then
+    printf "Shell integration is not enabled. Please ensure you source asdf in your shell setup." >&2
+    exit 1
+  fi
+
   if [ -z "$env_cmd" ]; then
     env_cmd="env"
   fi
@@ -21,4 +31,76 @@ shim_env_command() {
   with_shim_executable "$shim_name" shim_env || exit $?
 }
 
+# Prints the environment of the versions set for the current directory as a
+# direnv snippet: the install bin directories ahead of the rest of PATH, the
+# variables the plugin exec-env scripts export and a watch_file line for each
+# version file, so direnv reloads when it changes.
+direnv_env() {
+  local tmp_dir
+  tmp_dir=$(make_tmp_dir "direnv")
+  exported_variables >"$tmp_dir/env"
+
+  local original_path=$PATH
+  # Prints the directories the plugin adds to PATH on the first line, then
+  # the variables its exec-env exports
+  direnv_plugin_env() {
+    printf "%s\\n" "${PATH%"$original_path"}"
+    exported_variables | grep -vxF -f "$tmp_dir/env" | grep -v '^export \(PATH\|_\)='
+  }
+
+  local plugins_path
+  plugins_path=$(get_plugin_path)
+
+  local path_prefix="" variables="" version_files=""
+  local plugin_path plugin_name version_and_path versions version_file version installed_version plugin_env
+  for plugin_path in "$plugins_path"/*/; do
+    [ -d "$plugin_path" ] || continue
+    plugin_name=$(basename "$plugin_path")
+    version_and_path=$(find_versions "$plugin_name" "$PWD")
+    IFS='|' read -r versions version_file <<<"$version_and_path"
+    [ -n "$versions" ] || continue
+    [ ! -f "$version_file" ] || version_files+="$version_file"$'\n'
+
+    # The first installed version of the line is the one a shim would run
+    installed_version=""
+    for version in $versions; do
+      if is_version_constraint "$version"; then
+        version=$(find_installed_version_for_constraint "$plugin_name" "$version")
+      fi
+      if [ "$version" = "system" ] || { [ -n "$version" ] && [ -d "$(find_install_path "$plugin_name" "$version")" ]; }; then
+        installed_version=$version
+        break
+      fi
+    done
+
+    if [ -z "$installed_version" ]; then
+      asdf_warn "$plugin_name $versions is not installed"
+      continue
+    fi
+    [ "$installed_version" != "system" ] || continue
+
+    plugin_env=$(with_plugin_env "$plugin_name" "$installed_version" direnv_plugin_env)
+    path_prefix+=$(head -n 1 <<<"$plugin_env")
+    variables+=$(tail -n +2 <<<"$plugin_env")$'\n'
+  done
+  rm -rf "$tmp_dir"
+
+  printf "%s" "$version_files" | awk '!seen[$0]++' | while IFS= read -r version_file; do
+    printf "watch_file %q\\n" "$version_file"
+  done
+  printf "%s" "$variables" | awk 'NF && !seen[$0]++'
+  if [ -n "$path_prefix" ]; then
+    printf "export PATH=%q\\n" "$path_prefix$PATH"
+  fi
+}
+
+# Prints an `export NAME=value` line for every exported variable, with the
+# value quoted so it fits on one line.
+exported_variables() {
+  local name
+  for name in $(compgen -e); do
+    printf "export %s=%q\\n" "$name" "${!name}"
+  done
+}
+
 shim_env_command "$@"
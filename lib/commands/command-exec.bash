@@ -1,6 +1,11 @@
 # -*- sh -*-
 
 shim_exec_command() {
+  if [ "$1" = "--tool-versions" ]; then
+    use_tool_versions_file "$2"
+    shift 2
+  fi
+
   local shim_name
   shim_name=$(basename "$1")
   local shim_args=("${@:2}")
@@ -25,6 +30,26 @@ shim_exec_command() {
     if [ "$pre_status" -ne 0 ]; then
       return "$pre_status"
     fi
+    record_shim_metric "$shim_name" "$plugin_name" "$version"
+
+    # Read once, as every shim run goes through here
+    local auto_reshim_enabled
+    auto_reshim_enabled=$(get_plugin_config_value "auto_reshim" "$plugin_name")
+    if [ "$auto_reshim_enabled" = "yes" ]; then
+      auto_reshim "$plugin_name" "$version"
+    fi
+
+    # Commands that install executables run in a child process, so the
+    # version can be reshimmed once they exit
+    if [ "$auto_reshim_enabled" = "yes" ] && is_auto_reshim_command "$plugin_name" "$shim_name"; then
+      local exit_status=0
+      "$executable_path" "${shim_args[@]}" || exit_status=$?
+      auto_reshim "$plugin_name" "$version"
+      exit "$exit_status"
+    fi
+
+    # The EXIT trap printing the warnings does not run once exec replaces us
+    print_warnings
     exec "$executable_path" "${shim_args[@]}"
   }
 
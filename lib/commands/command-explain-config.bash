@@ -0,0 +1,156 @@
+# -*- sh -*-
+
+explain_config_command() {
+  local key=$1
+
+  if [ "$#" -gt 1 ] || [[ "$key" == -* ]]; then
+    display_error "usage: asdf explain-config [<key>]"
+    exit 1
+  fi
+
+  local keys
+  if [ -n "$key" ]; then
+    if ! is_known_config_key "$key" && ! asdf_config_keys | grep -qx "$key"; then
+      display_error "Unknown config key $key"
+      exit 1
+    fi
+    keys=$key
+  else
+    keys=$(asdf_config_keys)
+  fi
+
+  local rows
+  rows=$(
+    printf "KEY\\tVALUE\\tSOURCE\\n"
+    for key in $keys; do
+      config_value_origins "$key" | awk -F '\t' -v key="$key" '
+        NR == 1 { printf "%s\t%s\t%s\n", key, $3, $1 " " $2; next }
+        $1 != "default" { printf "\t%s\t%s\n", $3, $1 " " $2 " (overridden)" }
+        END { if (NR == 0) printf "%s\t%s\t%s\n", key, "(empty)", "default" }'
+    done
+  )
+  awk -F '\t' '
+    { lines[NR] = $0; if (length($1) > width1) width1 = length($1); if (length($2) > width2) width2 = length($2) }
+    END {
+      for (n = 1; n <= NR; n++) {
+        split(lines[n], fields, "\t")
+        printf "%-" (width1 + 2) "s%-" (width2 + 2) "s%s\n", fields[1], fields[2], fields[3]
+      }
+    }' <<<"$rows"
+
+  if [ "$#" -eq 0 ]; then
+    print_config_environment
+    print_ignored_config_keys
+  fi
+}
+
+# Prints a "<origin>\t<location>\t<value>" line for every place a key is set
+# to a value, from the one that is used to the ones it overrides. Origins are
+# env, project (the .asdfrc found from the current directory upwards), home
+# and default.
+config_value_origins() {
+  local key=$1
+  local config_path=${ASDF_CONFIG_FILE:-"$HOME/.asdfrc"}
+  local default_config_path=${ASDF_CONFIG_DEFAULT_FILE:-"$(asdf_dir)/defaults"}
+  local local_config_path
+  local_config_path="$(find_file_upwards ".asdfrc")"
+
+  # The home config file is also found by the upward search from within $HOME
+  if [ "$local_config_path" = "$config_path" ]; then
+    local_config_path=""
+  fi
+
+  local env_var value
+  env_var=$(config_key_env_var "$key")
+  if [ -n "$env_var" ]; then
+    if [ -n "${!env_var}" ]; then
+      printf "env\\t%s\\t%s\\n" "$env_var" "${!env_var}"
+    fi
+  elif value=$(get_asdf_config_value_from_file "$local_config_path" "$key"); then
+    printf "project\\t%s\\t%s\\n" "$local_config_path" "$(config_display_value "$key" "$value")"
+  fi
+
+  if value=$(get_asdf_config_value_from_file "$config_path" "$key"); then
+    printf "home\\t%s\\t%s\\n" "$config_path" "$(config_display_value "$key" "$value")"
+  fi
+
+  if value=$(get_asdf_config_value_from_file "$default_config_path" "$key"); then
+    printf "default\\t%s\\t%s\\n" "$default_config_path" "$(config_display_value "$key" "$value")"
+  fi
+}
+
+# Prints a config value the way explain-config shows it, without the
+# credentials in a proxy URL.
+config_display_value() {
+  if [ "$1" = "proxy" ]; then
+    redact_url "$2"
+  else
+    printf "%s\\n" "$2"
+  fi
+}
+
+# The upward search settings and the version file names are read from the
+# environment and the home config file only, as the project .asdfrc is itself
+# found by that search.
+config_key_env_var() {
+  case "$1" in
+  search_max_depth)
+    printf "ASDF_SEARCH_MAX_DEPTH\\n"
+    ;;
+  search_skip_paths)
+    printf "ASDF_SEARCH_SKIP_PATHS\\n"
+    ;;
+  tool_versions_filenames)
+    printf "ASDF_TOOL_VERSIONS_FILENAMES\\n"
+    ;;
+  esac
+}
+
+print_config_environment() {
+  local env_var
+  local set_vars=()
+  for env_var in ASDF_DIR ASDF_DATA_DIR ASDF_CONFIG_FILE ASDF_CONFIG_DEFAULT_FILE ASDF_DEFAULT_TOOL_VERSIONS_FILENAME ASDF_TOOL_VERSIONS_FILENAMES ASDF_TOOL_VERSIONS_FILE ASDF_CONCURRENCY ASDF_STRICT ASDF_PROXY ASDF_NO_PROXY ASDF_OFFLINE ASDF_COLOR ASDF_DEBUG; do
+    if [ "$env_var" = "ASDF_PROXY" ] && [ -n "$ASDF_PROXY" ]; then
+      set_vars+=("$env_var=$(redact_url "$ASDF_PROXY")")
+    elif [ -n "${!env_var}" ]; then
+      set_vars+=("$env_var=${!env_var}")
+    fi
+  done
+
+  if [ "${#set_vars[@]}" -gt 0 ]; then
+    printf "\\nEnvironment:\\n"
+    printf "  %s\\n" "${set_vars[@]}"
+  fi
+}
+
+# Prints the keys in the user config files that asdf does not read
+print_ignored_config_keys() {
+  local config_path=${ASDF_CONFIG_FILE:-"$HOME/.asdfrc"}
+  local local_config_path
+  local_config_path="$(find_file_upwards ".asdfrc")"
+  if [ "$local_config_path" = "$config_path" ]; then
+    local_config_path=""
+  fi
+
+  local ignored=() key
+  for key in $(asdf_config_keys_in_files "$config_path"); do
+    if ! is_known_config_key "$key"; then
+      ignored+=("$key in $config_path is not a known config key")
+    fi
+  done
+
+  for key in $(asdf_config_keys_in_files "$local_config_path"); do
+    if ! is_known_config_key "$key"; then
+      ignored+=("$key in $local_config_path is not a known config key")
+    elif [ -n "$(config_key_env_var "$key")" ]; then
+      ignored+=("$key in $local_config_path is only read from $(config_key_env_var "$key") or $config_path")
+    fi
+  done
+
+  if [ "${#ignored[@]}" -gt 0 ]; then
+    printf "\\nIgnored:\\n"
+    printf "  %s\\n" "${ignored[@]}"
+  fi
+}
+
+explain_config_command "$@"
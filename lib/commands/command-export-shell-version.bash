@@ -7,8 +7,19 @@ shell_command() {
   local asdf_shell="$1"
   shift
 
+  if [ "$#" -eq 1 ] && [ "$1" = "--unset" ]; then
+    restore_session_variables "$asdf_shell"
+    exit 0
+  fi
+
+  if [ "$#" -eq 1 ] && [ "$1" = "--restore" ]; then
+    restore_session_variables "$asdf_shell"
+    restore_path "$asdf_shell"
+    exit 0
+  fi
+
   if [ "$#" -lt "2" ]; then
-    printf "Usage: asdf shell <name> {<version>|--unset}\\n" >&2
+    printf "Usage: asdf shell {<name> {<version>|--unset} | --unset}\\n" >&2
     printf "false\\n"
     exit 1
   fi
@@ -46,6 +57,11 @@ shell_command() {
     exit 1
   fi
 
+  if ! record_session_variable "$version_env_var"; then
+    printf "false\\n"
+    exit 1
+  fi
+
   case "$asdf_shell" in
   fish)
     printf "set -gx %s \"%s\"\\n" "$version_env_var" "$version"
@@ -62,4 +78,138 @@ shell_command() {
   esac
 }
 
+# The variables set with `asdf shell` are recorded in a session file named
+# after the process ID of the shell, together with the values they had before.
+# The shell wrappers pass their process ID in ASDF_SHELL_PID.
+session_file_path() {
+  printf "%s/sessions/%s\\n" "$(asdf_data_dir)" "${ASDF_SHELL_PID:-$PPID}"
+}
+
+# Records the value a variable has before asdf first sets it in this session,
+# as "<name>=<value>", or just "<name>" when it is not set. The file has a line
+# per variable, so a value with a newline is refused.
+record_session_variable() {
+  local env_var=$1
+  local session_file
+  session_file=$(session_file_path)
+
+  if [ -f "$session_file" ] && cut -d '=' -f 1 "$session_file" | grep -qxF "$env_var"; then
+    return 0
+  fi
+
+  if [[ "${!env_var-}" == *$'\n'* ]]; then
+    display_error "$env_var contains a newline, so asdf shell could not restore it"
+    return 1
+  fi
+
+  mkdir -p "$(dirname "$session_file")"
+  if [ -n "${!env_var+set}" ]; then
+    printf "%s=%s\\n" "$env_var" "${!env_var}" >>"$session_file"
+  else
+    printf "%s\\n" "$env_var" >>"$session_file"
+  fi
+}
+
+# Prints the commands that give the variables recorded in the session file
+# their earlier values back, and removes the file.
+restore_session_variables() {
+  local asdf_shell=$1
+  local session_file
+  session_file=$(session_file_path)
+
+  [ -f "$session_file" ] || return 0
+
+  if [ "$asdf_shell" = "elvish" ]; then
+    printf "Restoring the session is not supported in elvish\\n" >&2
+    exit 1
+  fi
+
+  local line env_var
+  while IFS= read -r line; do
+    env_var=${line%%=*}
+    if [ "$line" = "$env_var" ]; then
+      print_unset_variable "$asdf_shell" "$env_var"
+    else
+      print_export_variable "$asdf_shell" "$env_var" "${line#*=}"
+    fi
+  done <"$session_file"
+
+  rm -f "$session_file"
+}
+
+# Prints the command that removes the asdf bin and shims directories from PATH,
+# followed by how to add them back.
+restore_path() {
+  local asdf_shell=$1
+
+  if [ "$asdf_shell" = "elvish" ]; then
+    printf "Restoring the session is not supported in elvish\\n" >&2
+    exit 1
+  fi
+
+  local path_entries=() path_entry
+  IFS=':' read -r -a path_entries <<<"$PATH"
+  local kept_entries=()
+  for path_entry in "${path_entries[@]}"; do
+    if [ "$path_entry" != "$(asdf_dir)/bin" ] && [ "$path_entry" != "$(asdf_data_dir)/shims" ]; then
+      kept_entries+=("$path_entry")
+    fi
+  done
+
+  if [ "$asdf_shell" = "fish" ]; then
+    printf "set -gx PATH"
+    for path_entry in "${kept_entries[@]}"; do
+      printf " %s" "$(fish_quote "$path_entry")"
+    done
+    printf "\\n"
+    printf "asdf is turned off in this shell, run \`source %s/asdf.fish\` to turn it back on\\n" "$(asdf_dir)" >&2
+  else
+    local kept_path
+    kept_path=$(
+      IFS=':'
+      printf "%s" "${kept_entries[*]}"
+    )
+    print_export_variable "$asdf_shell" "PATH" "$kept_path"
+    printf "asdf is turned off in this shell, run \`. %s/asdf.sh\` to turn it back on\\n" "$(asdf_dir)" >&2
+  fi
+}
+
+# Prints the command that sets a variable, with the value quoted so the shell
+# reads it as is.
+print_export_variable() {
+  local asdf_shell=$1
+  local env_var=$2
+  local value=$3
+
+  case "$asdf_shell" in
+  fish)
+    printf "set -gx %s %s\\n" "$env_var" "$(fish_quote "$value")"
+    ;;
+  *)
+    printf "export %s=%q\\n" "$env_var" "$value"
+    ;;
+  esac
+}
+
+# Quotes a value for fish, in which only backslashes and single quotes are
+# special inside single quotes.
+fish_quote() {
+  local value=${1//\\/\\\\}
+  printf "'%s'" "${value//\'/\\\'}"
+}
+
+print_unset_variable() {
+  local asdf_shell=$1
+  local env_var=$2
+
+  case "$asdf_shell" in
+  fish)
+    printf "set -e %s\\n" "$env_var"
+    ;;
+  *)
+    printf "unset %s\\n" "$env_var"
+    ;;
+  esac
+}
+
 shell_command "$@"
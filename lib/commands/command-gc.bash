@@ -0,0 +1,20 @@
+# -*- sh -*-
+# shellcheck source=lib/functions/installs.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/installs.bash"
+
+gc_command() {
+  case "$1" in
+  "")
+    collect_garbage verbose
+    ;;
+  -n | --dry-run)
+    collect_garbage dry-run
+    ;;
+  *)
+    display_error "usage: asdf gc [--dry-run]"
+    exit 1
+    ;;
+  esac
+}
+
+gc_command "$@"
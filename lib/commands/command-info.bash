@@ -1,13 +1,100 @@
 # -*- sh -*-
 # shellcheck source=lib/functions/plugins.bash
 . "$(dirname "$(dirname "$0")")/lib/functions/plugins.bash"
+# shellcheck source=lib/functions/installs.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/installs.bash"
 
 info_command() {
+  warn_unknown_config_keys
+
+  if [ "$1" = "--paths" ]; then
+    info_paths
+    return
+  fi
+
   printf "%s:\\n%s\\n\\n" "OS" "$(uname -a)"
   printf "%s:\\n%s\\n\\n" "SHELL" "$($SHELL --version)"
   printf "%s:\\n%s\\n\\n" "ASDF VERSION" "$(asdf_version)"
-  printf "%s:\\n%s\\n\\n" "ASDF ENVIRONMENT VARIABLES" "$(env | grep -E "ASDF_DIR|ASDF_DATA_DIR|ASDF_CONFIG_FILE|ASDF_DEFAULT_TOOL_VERSIONS_FILENAME")"
+  printf "%s:\\n%s\\n\\n" "ASDF ENVIRONMENT VARIABLES" "$(env | grep -E "ASDF_DIR|ASDF_DATA_DIR|ASDF_CONFIG_FILE|ASDF_DEFAULT_TOOL_VERSIONS_FILENAME|ASDF_TOOL_VERSIONS_FILE|ASDF_CONCURRENCY|ASDF_STRICT|ASDF_DEBUG=")"
   printf "%s:\\n%s\\n\\n" "ASDF INSTALLED PLUGINS" "$(plugin_list_command --urls --refs)"
 }
 
+# Prints one tab separated line per resolved path or config value in the form
+# `<name> <value> <source>`, where source is one of default, env or file.
+info_paths() {
+  local data_dir
+  data_dir="$(asdf_data_dir)"
+
+  local data_dir_origin="default"
+  [ -n "$ASDF_DATA_DIR" ] && data_dir_origin="env"
+
+  local config_file_origin="default"
+  [ -n "$ASDF_CONFIG_FILE" ] && config_file_origin="env"
+
+  local default_config_file_origin="default"
+  [ -n "$ASDF_CONFIG_DEFAULT_FILE" ] && default_config_file_origin="env"
+
+  local tool_versions_filename_origin="default"
+  if [ -n "$ASDF_DEFAULT_TOOL_VERSIONS_FILENAME" ] || [ -n "$ASDF_TOOL_VERSIONS_FILENAMES" ]; then
+    tool_versions_filename_origin="env"
+  elif [ -n "$(get_upward_search_setting "ASDF_TOOL_VERSIONS_FILENAMES" "tool_versions_filenames")" ]; then
+    tool_versions_filename_origin="file"
+  fi
+
+  print_info_line "asdf_dir" "$(asdf_dir)" "default"
+  print_info_line "data_dir" "$data_dir" "$data_dir_origin"
+  print_info_line "config_file" "${ASDF_CONFIG_FILE:-"$HOME/.asdfrc"}" "$config_file_origin"
+  print_info_line "default_config_file" "${ASDF_CONFIG_DEFAULT_FILE:-"$(asdf_dir)/defaults"}" "$default_config_file_origin"
+  print_info_line "local_config_file" "$(find_file_upwards ".asdfrc")" "file"
+  print_info_line "tool_versions_filename" "$(version_file_names)" "$tool_versions_filename_origin"
+  print_info_line "plugins" "$data_dir/plugins" "$data_dir_origin"
+  print_info_line "installs" "$data_dir/installs" "$data_dir_origin"
+  print_info_line "downloads" "$data_dir/downloads" "$data_dir_origin"
+  print_info_line "shims" "$data_dir/shims" "$data_dir_origin"
+  print_info_line "tmp" "$data_dir/tmp" "$data_dir_origin"
+  print_info_line "registry" "$data_dir/repository" "$data_dir_origin"
+  print_info_line "registry_url" "$(asdf_repository_url)" "default"
+  local concurrency_origin="default"
+  if [ -n "$ASDF_CONCURRENCY" ]; then
+    concurrency_origin="env"
+  elif [ "$(get_asdf_config_value_origin "concurrency")" = "file" ]; then
+    concurrency_origin="file"
+  fi
+  print_info_line "concurrency" "$(get_concurrency)" "$concurrency_origin"
+
+  local key
+  for key in $(asdf_config_keys); do
+    print_info_line "config.$key" "$(get_asdf_config_value "$key")" "$(get_asdf_config_value_origin "$key")"
+  done
+}
+
+print_info_line() {
+  printf "%s\\t%s\\t%s\\n" "$1" "$2" "$3"
+}
+
+# Warn about keys in the user config files that asdf does not know about, which
+# are usually typos.
+warn_unknown_config_keys() {
+  local key
+  for key in $(asdf_config_keys_in_files "${ASDF_CONFIG_FILE:-"$HOME/.asdfrc"}" "$(find_file_upwards ".asdfrc")"); do
+    if ! is_known_config_key "$key"; then
+      asdf_warn "unknown config key $key"
+    fi
+  done
+}
+
+get_asdf_config_value_origin() {
+  local key=$1
+  local config_path=${ASDF_CONFIG_FILE:-"$HOME/.asdfrc"}
+  local local_config_path
+  local_config_path="$(find_file_upwards ".asdfrc")"
+
+  if get_asdf_config_value_from_file "$local_config_path" "$key" >/dev/null ||
+    get_asdf_config_value_from_file "$config_path" "$key" >/dev/null; then
+    printf "file\\n"
+  else
+    printf "default\\n"
+  fi
+}
+
 info_command "$@"
@@ -0,0 +1,82 @@
+# -*- sh -*-
+# shellcheck source=lib/functions/shellenv.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/shellenv.bash"
+
+# Output from this command must be executable shell code
+init_command() {
+  local asdf_shell=""
+  local completions=true
+  local command_not_found=false
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    --no-completions)
+      completions=false
+      ;;
+    --command-not-found)
+      command_not_found=true
+      ;;
+    *)
+      asdf_shell=$1
+      ;;
+    esac
+    shift
+  done
+
+  case "$asdf_shell" in
+  nushell)
+    asdf_shell=nu
+    ;;
+  powershell)
+    asdf_shell=pwsh
+    ;;
+  esac
+
+  case "$asdf_shell" in
+  bash | zsh | fish | nu | pwsh) ;;
+  *)
+    display_error "usage: asdf init <bash|zsh|fish|nu|pwsh> [--no-completions] [--command-not-found]"
+    exit 1
+    ;;
+  esac
+
+  if [ "$command_not_found" = true ] && { [ "$asdf_shell" = "nu" ] || [ "$asdf_shell" = "pwsh" ]; }; then
+    display_error "--command-not-found is not supported for $asdf_shell"
+    exit 1
+  fi
+
+  print_shellenv "$(shellenv_shell_name "$asdf_shell")"
+
+  # There are no completion scripts for nu and pwsh. The others are loaded
+  # through the ASDF_DIR set above, so the directory is not printed again.
+  if [ "$completions" = true ]; then
+    case "$asdf_shell" in
+    bash)
+      printf ". \"\$ASDF_DIR/completions/asdf.bash\"\\n"
+      ;;
+    zsh)
+      # compinit, run later in .zshrc, picks up the completion from fpath
+      printf "fpath=(\"\$ASDF_DIR/completions\" \$fpath)\\n"
+      ;;
+    fish)
+      printf "source \"\$ASDF_DIR/completions/asdf.fish\"\\n"
+      ;;
+    esac
+  fi
+
+  if [ "$command_not_found" = true ]; then
+    case "$asdf_shell" in
+    bash)
+      printf "command_not_found_handle() {\\n  asdf __command-not-found \"\$1\"\\n}\\n"
+      ;;
+    zsh)
+      printf "command_not_found_handler() {\\n  asdf __command-not-found \"\$1\"\\n}\\n"
+      ;;
+    fish)
+      printf "function fish_command_not_found\\n  asdf __command-not-found \$argv[1]\\nend\\n"
+      ;;
+    esac
+  fi
+}
+
+init_command "$@"
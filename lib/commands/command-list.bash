@@ -1,9 +1,32 @@
 # -*- sh -*-
 
 list_command() {
+  local json=false
+  local positional=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    --json)
+      json=true
+      shift
+      ;;
+    *)
+      positional+=("$1")
+      shift
+      ;;
+    esac
+  done
+
+  set -- "${positional[@]}"
+
   local plugin_name=$1
   local query=$2
 
+  if [ "$json" = true ]; then
+    list_command_json "$plugin_name" "$query"
+    return
+  fi
+
   if [ -z "$plugin_name" ]; then
     local plugins_path
     plugins_path=$(get_plugin_path)
@@ -17,6 +40,13 @@ list_command() {
     else
       printf "%s\\n" 'No plugins installed'
     fi
+  elif is_plugin_pattern "$plugin_name"; then
+    local plugin_names
+    plugin_names=$(expand_plugin_pattern "$plugin_name") || exit 1
+    while IFS= read -r plugin_name; do
+      printf "%s\\n" "$plugin_name"
+      display_installed_versions "$plugin_name" "$query"
+    done <<<"$plugin_names"
   else
     check_if_plugin_exists "$plugin_name"
     display_installed_versions "$plugin_name" "$query"
@@ -28,7 +58,6 @@ display_installed_versions() {
   local query=$2
   local versions
   local current_version
-  local flag
 
   versions=$(list_installed_versions "$plugin_name")
 
@@ -45,15 +74,65 @@ display_installed_versions() {
     current_version=$(cut -d '|' -f 1 <<<"$(find_versions "$plugin_name" "$(pwd)")")
 
     for version in $versions; do
-      flag="  "
       if [[ "$version" == "$current_version" ]]; then
-        flag=" *"
+        printf "%s\\n" "$(styled current " *$version")"
+      else
+        printf "  %s\\n" "$version"
       fi
-      printf "%s%s\\n" "$flag" "$version"
     done
   else
     display_error '  No versions installed'
   fi
 }
 
+# Prints installed versions as a JSON array of objects with the plugin, the
+# version and whether it is the current version.
+list_command_json() {
+  local plugin_name=$1
+  local query=$2
+  local plugins=()
+
+  if [ -z "$plugin_name" ]; then
+    local plugins_path
+    plugins_path=$(get_plugin_path)
+
+    if find "$plugins_path" -mindepth 1 -type d &>/dev/null; then
+      for plugin_path in "$plugins_path"/*/; do
+        plugins+=("$(basename "$plugin_path")")
+      done
+    fi
+  elif is_plugin_pattern "$plugin_name"; then
+    local plugin_names
+    plugin_names=$(expand_plugin_pattern "$plugin_name") || exit 1
+    IFS=$'\n' read -rd '' -a plugins <<<"$plugin_names"
+  else
+    check_if_plugin_exists "$plugin_name"
+    plugins=("$plugin_name")
+  fi
+
+  local separator=""
+  local versions current_version version current
+  printf "["
+  for plugin_name in "${plugins[@]}"; do
+    versions=$(list_installed_versions "$plugin_name")
+
+    if [[ $query ]]; then
+      versions=$(printf "%s\n" "$versions" | grep -E "^\s*$query")
+    fi
+
+    current_version=$(cut -d '|' -f 1 <<<"$(find_versions "$plugin_name" "$(pwd)")")
+
+    for version in $versions; do
+      current=false
+      if [[ "$version" == "$current_version" ]]; then
+        current=true
+      fi
+      printf '%s{"plugin":%s,"version":%s,"current":%s}' "$separator" \
+        "$(json_string "$plugin_name")" "$(json_string "$version")" "$current"
+      separator=","
+    done
+  done
+  printf "]\\n"
+}
+
 list_command "$@"
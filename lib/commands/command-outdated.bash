@@ -0,0 +1,83 @@
+# -*- sh -*-
+# shellcheck source=lib/functions/versions.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/versions.bash"
+
+outdated_command() {
+  local json=false
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    --json)
+      json=true
+      shift
+      ;;
+    *)
+      display_error "usage: asdf outdated [--json]"
+      exit 1
+      ;;
+    esac
+  done
+
+  # The current versions are resolved first, so the latest version is only
+  # looked up for the plugins that have one
+  local plugin_path plugin_name current
+  local plugin_names=() current_versions=()
+  for plugin_path in "$(get_plugin_path)"/*/; do
+    [ -d "$plugin_path" ] || continue
+    plugin_name=$(basename "$plugin_path")
+
+    # Only the first version of the list is the one commands run with
+    current=$(find_versions "$plugin_name" "$PWD" | cut -d '|' -f 1 | cut -d ' ' -f 1)
+    case "$current" in
+    "" | system | ref:* | path:*)
+      continue
+      ;;
+    esac
+    plugin_names+=("$plugin_name")
+    current_versions+=("$current")
+  done
+
+  local rows=""
+  if [ "${#plugin_names[@]}" -gt 0 ]; then
+    rows=$(latest_all_versions "" "${plugin_names[@]}")
+  fi
+
+  local outdated=""
+  local latest _query _resolved_by status index=0
+  while IFS=$'\t' read -r plugin_name latest _query _resolved_by status; do
+    [ -n "$plugin_name" ] || continue
+    current=${current_versions[$index]}
+    index=$((index + 1))
+    [ "$latest" != "unknown" ] || continue
+
+    # A current version newer than the latest stable one, such as a
+    # prerelease, is not outdated
+    if [ "$current" != "$latest" ] &&
+      [ "$(printf "%s\\n%s\\n" "$current" "$latest" | sort_versions | tail -n 1)" = "$latest" ]; then
+      outdated+=$(printf "%s\\t%s\\t%s\\t%s" "$plugin_name" "$current" "$latest" "$status")$'\n'
+    fi
+  done <<<"$rows"
+
+  if [ "$json" = "true" ]; then
+    local separator=""
+    printf "["
+    while IFS=$'\t' read -r plugin_name current latest status; do
+      [ -n "$plugin_name" ] || continue
+      printf '%s{"plugin":%s,"current":%s,"latest":%s,"installed":%s}' "$separator" \
+        "$(json_string "$plugin_name")" "$(json_string "$current")" "$(json_string "$latest")" \
+        "$([ "$status" = "installed" ] && printf true || printf false)"
+      separator=","
+    done <<<"$outdated"
+    printf "]\\n"
+    return 0
+  fi
+
+  if [ -z "$outdated" ]; then
+    printf "All tools are up to date\\n"
+    return 0
+  fi
+
+  print_table "$(printf "PLUGIN\\tCURRENT\\tLATEST\\tSTATUS\\n%s" "$outdated")"
+}
+
+outdated_command "$@"
@@ -0,0 +1,58 @@
+# -*- sh -*-
+
+plugin_info_command() {
+  local plugin_name=$1
+  check_if_plugin_exists "$plugin_name"
+
+  local plugin_path
+  plugin_path=$(get_plugin_path "$plugin_name")
+
+  local download_api="bin/install"
+  if [ -f "${plugin_path}/bin/download" ]; then
+    download_api="bin/download + bin/install"
+  fi
+
+  local legacy_file_api="none"
+  if [ -f "${plugin_path}/bin/list-legacy-filenames" ]; then
+    legacy_file_api="bin/list-legacy-filenames"
+  elif [ -f "${plugin_path}/bin/get-version-from-legacy-file" ]; then
+    legacy_file_api="bin/get-version-from-legacy-file"
+  elif [ -n "$(plugin_legacy_filenames "$plugin_name")" ]; then
+    legacy_file_api="built-in ($(plugin_legacy_filenames "$plugin_name"))"
+  fi
+
+  local resolver_api="none"
+  if [ -f "${plugin_path}/bin/resolve-version" ]; then
+    resolver_api="bin/resolve-version"
+  fi
+
+  local latest_api="bin/list-all"
+  if [ -f "${plugin_path}/bin/latest-stable" ]; then
+    latest_api="bin/latest-stable"
+  fi
+
+  print_plugin_info_line "name" "$plugin_name"
+  print_plugin_info_line "path" "$plugin_path"
+  print_plugin_info_line "url" "$(redact_url "$(git --git-dir "$plugin_path/.git" remote get-url origin 2>/dev/null)")"
+  print_plugin_info_line "ref" "$(git --git-dir "$plugin_path/.git" rev-parse --short HEAD 2>/dev/null)"
+  print_plugin_info_line "pinned" "$(git --git-dir "$plugin_path/.git" config --get asdf.ref 2>/dev/null || printf "no")"
+  print_plugin_info_line "install" "$download_api"
+  print_plugin_info_line "legacy files" "$legacy_file_api"
+  print_plugin_info_line "latest" "$latest_api"
+  print_plugin_info_line "resolver" "$resolver_api"
+
+  local deprecations
+  deprecations=$(plugin_deprecations "$plugin_name")
+  if [ -z "$deprecations" ]; then
+    print_plugin_info_line "status" "ok"
+  else
+    print_plugin_info_line "status" "deprecated"
+    printf "%s\\n" "$deprecations" | sed 's/^/  - /'
+  fi
+}
+
+print_plugin_info_line() {
+  printf "%-14s%s\\n" "$1" "$2"
+}
+
+plugin_info_command "$@"
@@ -1,7 +1,12 @@
 # -*- sh -*-
 
 plugin_list_all_command() {
-  initialize_or_update_repository
+  local refresh
+  if [ "$1" = "--refresh" ]; then
+    refresh="refresh"
+  fi
+
+  initialize_or_update_repository "$refresh"
 
   local plugins_index_path
   plugins_index_path="$(asdf_data_dir)/repository/plugins"
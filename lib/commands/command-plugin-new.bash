@@ -0,0 +1,72 @@
+# -*- sh -*-
+
+plugin_new_command() {
+  local optional_callbacks=false
+  local tool_repo=""
+  local positional=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    --optional-callbacks)
+      optional_callbacks=true
+      shift
+      ;;
+    --repo)
+      if [ -z "${2:-}" ]; then
+        display_error "--repo needs the URL of the tool's repository"
+        exit 1
+      fi
+      tool_repo=$2
+      shift 2
+      ;;
+    *)
+      positional+=("$1")
+      shift
+      ;;
+    esac
+  done
+
+  set -- "${positional[@]}"
+
+  if [[ $# -lt 1 || $# -gt 2 ]]; then
+    display_error "usage: asdf plugin new <name> [<directory>] [--repo <url>] [--optional-callbacks]"
+    exit 1
+  fi
+
+  local plugin_name=$1
+  local plugin_dir=${2:-"asdf-$plugin_name"}
+
+  local regex="^[[:alpha:][:digit:]_-]+$"
+  if ! printf "%s" "$plugin_name" | grep -q -E "$regex"; then
+    display_error "$plugin_name is invalid. Name must match regex $regex"
+    exit 1
+  fi
+
+  if [ -e "$plugin_dir" ] && [ -n "$(find "$plugin_dir" -mindepth 1 -maxdepth 1 2>/dev/null)" ]; then
+    display_error "$plugin_dir already exists and is not empty"
+    exit 1
+  fi
+
+  local templates_dir
+  templates_dir="$(asdf_dir)/lib/templates"
+
+  mkdir -p "$plugin_dir"
+  cp -R "$templates_dir/plugin/." "$plugin_dir"
+  if [ "$optional_callbacks" = true ]; then
+    cp -R "$templates_dir/plugin-optional/." "$plugin_dir"
+  fi
+
+  local file contents
+  while IFS= read -r file; do
+    contents=$(cat "$file")
+    contents=$(substitute "$contents" "__TOOL_NAME__" "$plugin_name")
+    contents=$(substitute "$contents" "__TOOL_REPO__" "${tool_repo:-"https://github.com/<owner>/$plugin_name"}")
+    printf "%s\\n" "$contents" >"$file"
+  done <<<"$(find "$plugin_dir" -type f)"
+
+  printf "Created the %s plugin in %s\\n" "$plugin_name" "$plugin_dir"
+  printf "Fill in the TODOs in %s, commit it and test it with:\\n" "$plugin_dir/lib/utils.bash"
+  printf "  asdf plugin test %s <git-url> \"%s --version\"\\n" "$plugin_name" "$plugin_name"
+}
+
+plugin_new_command "$@"
@@ -13,15 +13,18 @@ plugin_remove_command() {
   if [ -f "${plugin_path}/bin/pre-plugin-remove" ]; then
     (
       export ASDF_PLUGIN_PATH=$plugin_path
-      "${plugin_path}/bin/pre-plugin-remove"
+      run_plugin_script "${plugin_path}/bin/pre-plugin-remove"
     )
   fi
 
   rm -rf "$plugin_path"
   rm -rf "$(asdf_data_dir)/installs/${plugin_name}"
   rm -rf "$(asdf_data_dir)/downloads/${plugin_name}"
+  rm -rf "$(asdf_data_dir)/receipts/${plugin_name}"
+  clear_list_all_cache "$plugin_name"
 
   grep -l "asdf-plugin: ${plugin_name}" "$(asdf_data_dir)"/shims/* 2>/dev/null | xargs rm -f
+  update_shim_index
 
   asdf_run_hook "post_asdf_plugin_remove" "$plugin_name"
   asdf_run_hook "post_asdf_plugin_remove_${plugin_name}"
@@ -0,0 +1,154 @@
+# -*- sh -*-
+
+# shellcheck source=lib/commands/reshim.bash
+. "$(dirname "$ASDF_CMD_FILE")/reshim.bash"
+# shellcheck source=lib/functions/versions.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/versions.bash"
+# shellcheck source=lib/functions/uninstalls.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/uninstalls.bash"
+
+prune_command() {
+  local assume_yes=false
+  local roots=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    -y | --yes)
+      assume_yes=true
+      shift
+      ;;
+    --dry-run)
+      ASDF_DRY_RUN=yes
+      shift
+      ;;
+    --fail-fast)
+      ASDF_FAIL_FAST=yes
+      shift
+      ;;
+    -*)
+      display_error "usage: asdf prune [<project-root>...] [--dry-run] [--yes] [--fail-fast]"
+      exit 1
+      ;;
+    *)
+      if [ ! -d "$1" ]; then
+        display_error "No such directory: $1"
+        exit 1
+      fi
+      roots+=("$1")
+      shift
+      ;;
+    esac
+  done
+
+  local version_files
+  version_files=$(
+    {
+      prune_version_files "$(version_file_names)" "${roots[@]}"
+      [ ! -f "${ASDF_DEFAULT_TOOL_VERSIONS_FILENAME:-}" ] || printf "%s\\n" "$ASDF_DEFAULT_TOOL_VERSIONS_FILENAME"
+    } | awk '!seen[$0]++'
+  )
+
+  local legacy_version_file
+  legacy_version_file=$(get_asdf_config_value "legacy_version_file")
+
+  local plugins_path
+  plugins_path=$(get_plugin_path)
+
+  local unreferenced=() plugin_path plugin_name referenced version
+  local legacy_filenames legacy_files all_legacy_files=""
+  for plugin_path in "$plugins_path"/*/; do
+    [ -d "$plugin_path" ] || continue
+    plugin_name=$(basename "$plugin_path")
+    referenced=$(referenced_versions "$plugin_name" "$version_files")
+
+    if [ "$legacy_version_file" = "yes" ]; then
+      legacy_filenames=$(plugin_legacy_filenames "$plugin_name")
+      if [ -n "$legacy_filenames" ]; then
+        legacy_files=$(prune_version_files "$legacy_filenames" "${roots[@]}")
+        referenced+=$'\n'$(referenced_versions "$plugin_name" "$legacy_files" legacy)
+        all_legacy_files+=$'\n'$legacy_files
+      fi
+    fi
+
+    for version in $(list_installed_versions "$plugin_name"); do
+      if ! grep -qxF "$version" <<<"$referenced"; then
+        unreferenced+=("$plugin_name $version")
+      fi
+    done
+  done
+
+  local files_count
+  files_count=$(awk 'NF && !seen[$0]++' <<<"$version_files"$'\n'"$all_legacy_files" | grep -c .)
+
+  if [ "${#unreferenced[@]}" -eq 0 ]; then
+    printf "Every installed version is referenced by the %s version files found\\n" "$files_count"
+    return 0
+  fi
+
+  printf "Installed versions not referenced by any of the %s version files found:\\n" "$files_count"
+  printf "  %s\\n" "${unreferenced[@]}"
+
+  if [ "$assume_yes" = "false" ] && ! asdf_dry_run && ! confirm_action "Uninstall these ${#unreferenced[@]} versions?"; then
+    display_error "Prune cancelled"
+    exit 1
+  fi
+
+  batch_start
+  for plugin_name in $(printf "%s\\n" "${unreferenced[@]}" | cut -d ' ' -f 1 | uniq); do
+    # shellcheck disable=SC2046
+    uninstall_tool_versions "$plugin_name" $(printf "%s\\n" "${unreferenced[@]}" | awk -v plugin="$plugin_name" '$1 == plugin { print $2 }')
+    batch_should_stop && break
+  done
+  batch_report "Prune" || exit 1
+}
+
+# Prints the files with one of the given space separated names that keep
+# versions installed, one per line: those in the home directory, those from
+# the current directory upwards and those anywhere under the given project
+# roots.
+prune_version_files() {
+  local file_names=() file_name name_args=()
+  read -r -a file_names <<<"$1"
+  shift
+  for file_name in "${file_names[@]}"; do
+    [ "${#name_args[@]}" -eq 0 ] || name_args+=(-o)
+    name_args+=(-name "$file_name")
+  done
+
+  {
+    local dir
+    while IFS= read -r dir; do
+      for file_name in "${file_names[@]}"; do
+        [ ! -f "$dir/$file_name" ] || printf "%s\\n" "$dir/$file_name"
+      done
+    done <<<"$HOME"$'\n'"$(upward_search_paths "$PWD")"
+
+    local root
+    for root in "$@"; do
+      find "$(cd "$root" && pwd)" \( -name node_modules -o -name .git \) -prune -o -type f \( "${name_args[@]}" \) -print
+    done
+  } | awk '!seen[$0]++'
+}
+
+# Prints the installed versions of a plugin that the version files reference,
+# one per line. With "legacy", the files are read as the plugin's legacy
+# version files.
+referenced_versions() {
+  local plugin_name=$1
+  local version_files=$2
+  local file_kind=${3:-}
+
+  local file versions
+  while IFS= read -r file; do
+    [ -n "$file" ] || continue
+    if [ "$file_kind" = "legacy" ]; then
+      versions=$(parse_legacy_version_file "$file" "$plugin_name")
+    else
+      versions=$(parse_asdf_version_file "$file" "$plugin_name")
+    fi
+    # shellcheck disable=SC2086
+    [ -z "$versions" ] || installed_versions_for "$plugin_name" $versions
+  done <<<"$version_files" | awk '!seen[$0]++'
+}
+
+prune_command "$@"
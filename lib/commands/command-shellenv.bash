@@ -0,0 +1,16 @@
+# -*- sh -*-
+# shellcheck source=lib/functions/shellenv.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/shellenv.bash"
+
+# Output from this command must be executable shell code
+shellenv_command() {
+  local asdf_shell
+  if ! asdf_shell=$(shellenv_shell_name "${1:-$(basename "${SHELL:-sh}")}"); then
+    display_error "usage: asdf shellenv [bash|zsh|sh|ksh|fish|nu|pwsh]"
+    exit 1
+  fi
+
+  print_shellenv "$asdf_shell"
+}
+
+shellenv_command "$@"
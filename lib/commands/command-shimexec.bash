@@ -0,0 +1,82 @@
+# -*- sh -*-
+
+shimexec_command() {
+  local dry_run=false
+  local shim_name
+  local shim_args=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    -n | --dry-run)
+      dry_run=true
+      shift
+      ;;
+    --)
+      shift
+      shim_args+=("$@")
+      break
+      ;;
+    *)
+      if [ -z "$shim_name" ]; then
+        shim_name=$(basename "$1")
+      else
+        shim_args+=("$1")
+      fi
+      shift
+      ;;
+    esac
+  done
+
+  if [ -z "$shim_name" ]; then
+    display_error "usage: asdf shimexec [--dry-run] <shim> [-- <args>...]"
+    exit 1
+  fi
+
+  if [ "$dry_run" = false ]; then
+    # Do exactly what the shim itself does
+    exec "$(asdf_dir)/bin/asdf" exec "$shim_name" "${shim_args[@]}" # asdf_allow: ' asdf '
+  fi
+
+  local tmp_dir
+  tmp_dir=$(make_tmp_dir "shimexec")
+  env >"$tmp_dir/env"
+
+  print_shim_exec() {
+    local plugin_name=$1
+    local full_version=$2
+    local executable_path=$3
+
+    local version_and_origin
+    version_and_origin=$(find_versions "$plugin_name" "$(pwd)")
+
+    printf "%-12s%s\\n" "plugin" "$plugin_name"
+    printf "%-12s%s\\n" "version" "$full_version"
+    printf "%-12s%s\\n" "set by" "$(cut -d '|' -f 2 <<<"$version_and_origin")"
+    printf "%-12s%s\\n" "executable" "$executable_path"
+
+    local hook_name="pre_${plugin_name}_${shim_name}"
+    if [ -n "$(get_asdf_config_value "$hook_name")" ]; then
+      printf "%-12s%s\\n" "pre hook" "$hook_name"
+    fi
+
+    if [ ! -x "$executable_path" ]; then
+      printf "No %s executable found for %s %s\\n" "$shim_name" "$plugin_name" "$full_version" >&2
+      return 2
+    fi
+
+    local command_line
+    command_line=$(printf "%q " "$executable_path" "${shim_args[@]}")
+    printf "%-12s%s\\n" "command" "${command_line% }"
+
+    # Only show the variables that the plugin environment adds or changes
+    printf "environment:\\n"
+    env | grep -vxF -f "$tmp_dir/env" | sed 's/^/  /'
+  }
+
+  local exit_code=0
+  with_shim_executable "$shim_name" print_shim_exec || exit_code=$?
+  rm -rf "$tmp_dir"
+  exit "$exit_code"
+}
+
+shimexec_command "$@"
@@ -0,0 +1,102 @@
+# -*- sh -*-
+
+stats_commands_command() {
+  local all_projects=false
+
+  case "$1" in
+  "") ;;
+  --all)
+    all_projects=true
+    ;;
+  *)
+    display_error "usage: asdf stats commands [--all]"
+    exit 1
+    ;;
+  esac
+
+  local metrics_path
+  metrics_path="$(asdf_data_dir)/metrics"
+
+  if [ ! -s "$metrics_path" ]; then
+    if [ "$(get_asdf_config_value "shim_metrics")" != "yes" ]; then
+      display_error "No commands recorded, set shim_metrics = yes in $HOME/.asdfrc to record the commands run through shims"
+    else
+      display_error "No commands recorded yet"
+    fi
+    exit 1
+  fi
+
+  local project_path=""
+  if [ "$all_projects" = "false" ]; then
+    project_path=$(current_project_path)
+  fi
+
+  # Runs per project, plugin, version and command, most used first
+  local summary
+  summary=$(awk -F '\t' -v project="$project_path" -v now="$(date +%s)" '
+    project != "" && $2 != project { next }
+    {
+      key = $2 "\t" $3 "\t" $4 "\t" $5
+      runs[key]++
+      if ($1 > last[key]) last[key] = $1
+    }
+    END {
+      for (key in runs) {
+        days = int((now - last[key]) / 86400)
+        print key "\t" runs[key] "\t" (days == 0 ? "today" : days "d ago")
+      }
+    }
+  ' "$metrics_path" | LC_ALL=C sort -t "$(printf '\t')" -k 1,1 -k 5,5nr -k 2,2 -k 4,4 | awk -F '\t' '
+    $1 != project {
+      project = $1
+      if (NR > 1) print ""
+      print project
+      printf "  %-16s%-14s%-20s%6s  %s\n", "PLUGIN", "VERSION", "COMMAND", "RUNS", "LAST RUN"
+    }
+    { printf "  %-16s%-14s%-20s%6s  %s\n", $2, $3, $4, $5, $6 }
+  ')
+
+  if [ -n "$summary" ]; then
+    printf "%s\\n" "$summary"
+  else
+    printf "No commands recorded for %s\\n" "$project_path"
+  fi
+
+  if [ "$all_projects" = "false" ]; then
+    print_unused_tool_versions "$metrics_path" "$project_path"
+  fi
+}
+
+# Prints the versions pinned in the project's version file that no command
+# was recorded for, which are candidates for removal.
+print_unused_tool_versions() {
+  local metrics_path=$1
+  local project_path=$2
+  local tool_versions_path
+  if ! tool_versions_path=$(version_file_in_dir "$project_path"); then
+    return 0
+  fi
+
+  local unused
+  unused=$(strip_tool_version_comments "$tool_versions_path" | awk -F '\t' -v project="$project_path" '
+    NR == FNR {
+      if ($2 == project) used[$3 " " $4] = 1
+      next
+    }
+    NF {
+      split($0, fields, " ")
+      for (i = 2; i in fields; i++) {
+        if (fields[i] != "system" && !((fields[1] " " fields[i]) in used)) print fields[1] " " fields[i]
+      }
+    }
+  ' "$metrics_path" -)
+
+  if [ -z "$unused" ]; then
+    return 0
+  fi
+
+  printf "\\nPinned in %s but never run:\\n" "$tool_versions_path"
+  printf "%s\\n" "$unused" | awk '{ printf "  %-16s%s\n", $1, $2 }'
+}
+
+stats_commands_command "$@"
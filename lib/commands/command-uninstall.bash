@@ -2,55 +2,191 @@
 
 # shellcheck source=lib/commands/reshim.bash
 . "$(dirname "$ASDF_CMD_FILE")/reshim.bash"
+# shellcheck source=lib/functions/versions.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/versions.bash"
+# shellcheck source=lib/functions/uninstalls.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/uninstalls.bash"
 
 uninstall_command() {
+  local assume_yes=false
+  local all=false
+  local all_but_current=false
+  local positional=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    -y | --yes)
+      assume_yes=true
+      shift
+      ;;
+    --dry-run)
+      ASDF_DRY_RUN=yes
+      shift
+      ;;
+    --all)
+      all=true
+      shift
+      ;;
+    --all-but-current)
+      all_but_current=true
+      shift
+      ;;
+    --fail-fast)
+      ASDF_FAIL_FAST=yes
+      shift
+      ;;
+    *)
+      positional+=("$1")
+      shift
+      ;;
+    esac
+  done
+
+  set -- "${positional[@]}"
+
   local plugin_name=$1
   local full_version=$2
-  local plugin_path
-  plugin_path=$(get_plugin_path "$plugin_name")
 
-  check_if_plugin_exists "$plugin_name"
+  if [ "$all" = "true" ]; then
+    if [ -z "$plugin_name" ] || [ "$#" -gt 1 ] || is_plugin_pattern "$plugin_name"; then
+      display_error "usage: asdf uninstall <name> --all [--yes] [--fail-fast]"
+      exit 1
+    fi
+    uninstall_all_versions "$plugin_name" "$assume_yes"
+    return
+  fi
 
-  IFS=':' read -r -a version_info <<<"$full_version"
-  if [ "${version_info[0]}" = "ref" ]; then
-    local install_type="${version_info[0]}"
-    local version="${version_info[1]}"
-  else
-    local install_type="version"
-    local version="${version_info[0]}"
+  if [ "$all_but_current" = "true" ]; then
+    if [ -z "$plugin_name" ] || [ "$#" -gt 1 ] || is_plugin_pattern "$plugin_name"; then
+      display_error "usage: asdf uninstall <name> --all-but-current [--yes] [--fail-fast]"
+      exit 1
+    fi
+    uninstall_all_but_current "$plugin_name" "$assume_yes"
+    return
   fi
 
-  local install_path
-  install_path=$(get_install_path "$plugin_name" "$install_type" "$version")
+  if ! is_plugin_pattern "$plugin_name"; then
+    uninstall_tool_version "$plugin_name" "$full_version"
+    return
+  fi
 
-  if [ ! -d "$install_path" ]; then
-    display_error "No such version"
+  local pattern=$plugin_name
+  if [ -z "$full_version" ]; then
+    display_error "usage: asdf uninstall <pattern> <version> [--yes] [--fail-fast]"
     exit 1
   fi
 
-  asdf_run_hook "pre_asdf_uninstall_${plugin_name}" "$full_version"
-  remove_shims_for_version "$plugin_name" "$full_version"
+  local plugin_names
+  plugin_names=$(expand_plugin_pattern "$pattern") || exit 1
+
+  # Only the plugins that have the version installed are uninstalled
+  local matched=()
+  while IFS= read -r plugin_name; do
+    if [ -d "$(find_install_path "$plugin_name" "$full_version")" ]; then
+      matched+=("$plugin_name")
+    fi
+  done <<<"$plugin_names"
+
+  if [ "${#matched[@]}" -eq 0 ]; then
+    display_error "No plugins matching $pattern have version $full_version installed"
+    exit 1
+  fi
 
-  if [ -f "${plugin_path}/bin/uninstall" ]; then
-    (
-      export ASDF_INSTALL_TYPE=$install_type
-      export ASDF_INSTALL_VERSION=$version
-      export ASDF_INSTALL_PATH=$install_path
-      "${plugin_path}/bin/uninstall"
-    )
-  else
-    rm -rf "$install_path"
+  if [ "$assume_yes" = "false" ] && ! asdf_dry_run && ! confirm_action "Uninstall $full_version of ${matched[*]}?"; then
+    display_error "Uninstall cancelled"
+    exit 1
   fi
 
-  asdf_run_hook "post_asdf_uninstall_${plugin_name}" "$full_version"
+  batch_start
+  for plugin_name in "${matched[@]}"; do
+    batch_run "$plugin_name $full_version" uninstall_tool_version "$plugin_name" "$full_version"
+    batch_should_stop && break
+  done
+  batch_report "Uninstall" || exit 1
 }
 
-remove_shims_for_version() {
+# Uninstalls every installed version of a plugin, including ref: and other
+# custom installs.
+uninstall_all_versions() {
   local plugin_name=$1
-  local full_version=$2
-  for shim_path in $(plugin_shims "$plugin_name" "$full_version"); do
-    remove_shim_for_version "$plugin_name" "$full_version" "$shim_path"
+  local assume_yes=$2
+
+  check_if_plugin_exists "$plugin_name"
+
+  local versions=() version
+  for version in $(list_installed_versions "$plugin_name"); do
+    versions+=("$version")
+  done
+
+  if [ "${#versions[@]}" -eq 0 ]; then
+    printf "No versions of %s are installed\\n" "$plugin_name"
+    return 0
+  fi
+
+  if [ "$assume_yes" = "false" ] && ! asdf_dry_run && ! confirm_action "Uninstall every version of $plugin_name: ${versions[*]}?"; then
+    display_error "Uninstall cancelled"
+    exit 1
+  fi
+
+  batch_start
+  uninstall_tool_versions "$plugin_name" "${versions[@]}"
+  batch_report "Uninstall" || exit 1
+  asdf_dry_run || rmdir "$(asdf_data_dir)/installs/$plugin_name" 2>/dev/null || true
+}
+
+# Uninstalls every version of a plugin that is neither set for the current
+# directory nor in the home version file.
+uninstall_all_but_current() {
+  local plugin_name=$1
+  local assume_yes=$2
+
+  check_if_plugin_exists "$plugin_name"
+
+  local current_versions
+  current_versions=$(current_tool_versions "$plugin_name")
+
+  if [ -z "$current_versions" ]; then
+    display_error "No installed version of $plugin_name is set in this directory or $HOME/$(version_file_name), not uninstalling every version"
+    exit 1
+  fi
+
+  local removed=() version
+  for version in $(list_installed_versions "$plugin_name"); do
+    if ! grep -qxF "$version" <<<"$current_versions"; then
+      removed+=("$version")
+    fi
   done
+
+  local kept
+  kept=$(tr '\n' ' ' <<<"$current_versions")
+  kept=${kept% }
+
+  if [ "${#removed[@]}" -eq 0 ]; then
+    printf "Only the current versions of %s are installed: %s\\n" "$plugin_name" "$kept"
+    return 0
+  fi
+
+  if [ "$assume_yes" = "false" ] && ! asdf_dry_run && ! confirm_action "Uninstall $plugin_name ${removed[*]} and keep $kept?"; then
+    display_error "Uninstall cancelled"
+    exit 1
+  fi
+
+  batch_start
+  uninstall_tool_versions "$plugin_name" "${removed[@]}"
+  batch_report "Uninstall" || exit 1
+}
+
+# Prints the installed versions of a plugin that are set for the current
+# directory or in the home version file, one per line.
+current_tool_versions() {
+  local plugin_name=$1
+
+  local version_and_path versions
+  version_and_path=$(find_versions "$plugin_name" "$PWD")
+  versions="${version_and_path%%|*} $(parse_asdf_version_file "$(version_file_in_dir "$HOME")" "$plugin_name")"
+
+  # shellcheck disable=SC2086
+  installed_versions_for "$plugin_name" $versions
 }
 
 uninstall_command "$@"
@@ -18,15 +18,17 @@ update_command() {
 do_update() {
   local update_to_head=$1
 
+  require_online "update asdf" || exit 1
+
   if [ "$update_to_head" = "--head" ]; then
     # Update to latest on the master branch
-    git fetch origin master
+    with_proxy git fetch origin master
     git checkout master
     git reset --hard origin/master
     printf "Updated asdf to latest on the master branch\\n"
   else
     # Update to latest release
-    git fetch origin --tags || exit 1
+    with_proxy git fetch origin --tags || exit 1
 
     if [ "$(get_asdf_config_value "use_release_candidates")" = "yes" ]; then
       # Use the latest tag whether or not it is an RC
@@ -42,10 +44,4 @@ do_update() {
   fi
 }
 
-# stolen from https://github.com/rbenv/ruby-build/pull/631/files#diff-fdcfb8a18714b33b07529b7d02b54f1dR942
-sort_versions() {
-  sed 'h; s/[+-]/./g; s/.p\([[:digit:]]\)/.z\1/; s/$/.z/; G; s/\n/ /' |
-    LC_ALL=C sort -t. -k 1,1 -k 2,2n -k 3,3n -k 4,4n -k 5,5n | awk '{print $2}'
-}
-
 update_command "$@"
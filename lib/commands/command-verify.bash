@@ -0,0 +1,95 @@
+# -*- sh -*-
+# shellcheck source=lib/functions/versions.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/versions.bash"
+# shellcheck source=lib/commands/reshim.bash
+. "$(dirname "$ASDF_CMD_FILE")/reshim.bash"
+# shellcheck source=lib/functions/installs.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/installs.bash"
+
+verify_command() {
+  local repair=false
+  local positional=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    --repair)
+      repair=true
+      shift
+      ;;
+    *)
+      positional+=("$1")
+      shift
+      ;;
+    esac
+  done
+  set -- "${positional[@]}"
+
+  local plugin_name=$1
+  local full_version=$2
+
+  if [ -z "$plugin_name" ] || [ -z "$full_version" ] || [ "$#" -gt 2 ]; then
+    display_error "usage: asdf verify <name> <version> [--repair]"
+    exit 1
+  fi
+
+  check_if_plugin_exists "$plugin_name"
+
+  IFS=':' read -r -a version_info <<<"$full_version"
+  if [ "${version_info[0]}" = "ref" ]; then
+    local install_type="${version_info[0]}"
+    local version="${version_info[1]}"
+  else
+    local install_type="version"
+    local version="${version_info[0]}"
+  fi
+
+  local install_path
+  install_path=$(get_install_path "$plugin_name" "$install_type" "$version")
+  local receipt_path
+  receipt_path=$(get_receipt_path "$plugin_name" "$install_type" "$version")
+
+  if [ ! -d "$install_path" ]; then
+    display_error "Version $full_version of $plugin_name is not installed"
+    exit 1
+  fi
+
+  local differences
+  if [ ! -f "$receipt_path" ]; then
+    printf "%s %s has no install receipt, it was installed before asdf recorded them\\n" "$plugin_name" "$full_version"
+  else
+    differences=$(compare_install_manifest "$receipt_path" "$install_path")
+    if [ -z "$differences" ]; then
+      printf "%s %s is intact\\n" "$plugin_name" "$full_version"
+      exit 0
+    fi
+
+    printf "%s\\n" "$differences"
+    printf "%s %s differs from its install receipt in %s files\\n" "$plugin_name" "$full_version" "$(wc -l <<<"$differences" | tr -d ' ')"
+  fi
+
+  if [ "$repair" = "false" ]; then
+    printf "Run \`asdf verify %s %s --repair\` to reinstall it\\n" "$plugin_name" "$full_version"
+    exit 1
+  fi
+
+  printf "Reinstalling %s %s\\n" "$plugin_name" "$full_version"
+  rm -rf "$install_path"
+  install_tool_version "$plugin_name" "$full_version"
+}
+
+# Prints a line for every file of the receipt that is missing or modified in
+# the install. Files added since the install, such as globally installed
+# packages, are not reported.
+compare_install_manifest() {
+  local receipt_path=$1
+  local install_path=$2
+
+  install_manifest "$install_path" | awk '
+    { path = substr($0, index($0, "  ") + 4) }
+    NR == FNR { installed[path] = $1; next }
+    !(path in installed) { printf "missing   %s\n", path; next }
+    installed[path] != $1 { printf "modified  %s\n", path }
+  ' - "$receipt_path"
+}
+
+verify_command "$@"
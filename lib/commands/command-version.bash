@@ -1,2 +1,11 @@
 # -*- sh -*-
-asdf_version
+
+asdf_version_command() {
+  if [ "$1" = "--verbose" ]; then
+    asdf_build_info
+  else
+    asdf_version
+  fi
+}
+
+asdf_version_command "$@"
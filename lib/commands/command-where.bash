@@ -1,6 +1,24 @@
 # -*- sh -*-
 
 where_command() {
+  local json=false
+  local positional=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    --json)
+      json=true
+      shift
+      ;;
+    *)
+      positional+=("$1")
+      shift
+      ;;
+    esac
+  done
+
+  set -- "${positional[@]}"
+
   local plugin_name=$1
   local full_version=$2
   check_if_plugin_exists "$plugin_name"
@@ -30,11 +48,23 @@ where_command() {
     exit 1
   fi
 
+  # A constraint is where the newest installed version satisfying it is
+  local installed_version
+  if is_version_constraint "$version"; then
+    installed_version=$(find_installed_version_for_constraint "$plugin_name" "$version")
+    version=${installed_version:-$version}
+  fi
+
   local install_path
   install_path=$(get_install_path "$plugin_name" "$install_type" "$version")
 
   if [ -d "$install_path" ]; then
-    printf "%s\\n" "$install_path"
+    if [ "$json" = true ]; then
+      printf '{"plugin":%s,"version":%s,"path":%s}\n' "$(json_string "$plugin_name")" \
+        "$(json_string "$version")" "$(json_string "$install_path")"
+    else
+      printf "%s\\n" "$install_path"
+    fi
     exit 0
   else
     if [ "$version" = "system" ]; then
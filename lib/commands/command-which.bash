@@ -1,14 +1,44 @@
 # -*- sh -*-
 
 which_command() {
+  local json=false
+  local explain=false
+  local command_name
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    --json)
+      json=true
+      ;;
+    --explain)
+      explain=true
+      ;;
+    *)
+      [ -n "$command_name" ] || command_name=$1
+      ;;
+    esac
+    shift
+  done
+
   local shim_name
-  shim_name=$(basename "$1")
+  shim_name=$(basename "$command_name")
 
   if [ -z "$shim_name" ]; then
     printf "usage: asdf which <command>\\n"
     exit 1
   fi
 
+  if [ "$json" = true ]; then
+    ASDF_WARNINGS_FORMAT=json
+  fi
+
+  warn_if_shim_shadowed "$shim_name"
+
+  if [ "$explain" = true ]; then
+    explain_shim_resolution "$shim_name"
+    exit $?
+  fi
+
   print_exec() {
     local plugin_name="$1"
     local version="$2"
@@ -19,11 +49,121 @@ which_command() {
       exit 1
     fi
 
-    printf "%s\\n" "$executable_path"
+    if [ "$json" = true ]; then
+      printf '{"plugin":%s,"version":%s,"path":%s}\n' "$(json_string "$plugin_name")" \
+        "$(json_string "$version")" "$(json_string "$executable_path")"
+    else
+      printf "%s\\n" "$executable_path"
+    fi
     exit 0
   }
 
   with_shim_executable "$shim_name" print_exec || exit 1
 }
 
+# Prints each step of picking the executable a shim runs: the plugins and
+# versions the shim metadata lists, the version set for each plugin and where
+# it was set, the rule that selected the version and the executable it found.
+explain_shim_resolution() {
+  local shim_name=$1
+  local shim_path
+  shim_path="$(asdf_data_dir)/shims/$shim_name"
+
+  if [ ! -f "$shim_path" ]; then
+    printf "unknown command: %s. Perhaps you have to reshim?\\n" "$shim_name" >&2
+    return 1
+  fi
+
+  printf "%-12s%s\\n" "shim" "$shim_path"
+
+  local plugin_name
+  while IFS= read -r plugin_name; do
+    printf "%-12s%s %s\\n" "provided by" "$plugin_name" \
+      "$(shim_plugin_versions "$shim_name" | awk -v plugin="$plugin_name" '$1 == plugin { versions = versions sep $2; sep = " " } END { print versions }')"
+  done <<<"$(shim_plugins "$shim_name")"
+
+  local candidates
+  candidates=$(shim_candidate_plugins "$shim_name")
+  if [ "$candidates" != "$(shim_plugins "$shim_name")" ]; then
+    printf "%-12s%s\\n" "candidates" "$(tr '\n' ' ' <<<"$candidates")(ASDF_<TOOL>_VERSION is set)"
+  fi
+
+  local version_and_origin version origin
+  while IFS= read -r plugin_name; do
+    version_and_origin=$(find_versions "$plugin_name" "$PWD")
+    IFS='|' read -r version origin <<<"$version_and_origin"
+    if [ -n "$version" ]; then
+      printf "%-12s%s %s set by %s\\n" "version" "$plugin_name" "$version" "${origin:-a version resolver}"
+    else
+      printf "%-12s%s no version set\\n" "version" "$plugin_name"
+    fi
+  done <<<"$candidates"
+
+  # The rules are tried in the same order as with_shim_executable does
+  local selected reason
+  selected=$(select_version "$shim_name")
+  reason="first set version that provides $shim_name"
+  if [ -z "$selected" ]; then
+    selected=$(select_from_preset_version "$shim_name")
+    reason="set version listed in the shim metadata"
+  fi
+  if [ -z "$selected" ]; then
+    selected=$(select_missing_version "$shim_name" 2>/dev/null)
+    reason="missing_version_behavior = $(get_plugin_config_value "missing_version_behavior" "$(shim_plugins "$shim_name" | head -n 1)")"
+  fi
+  if [ -z "$selected" ]; then
+    selected=$(select_system_fallback "$shim_name" 2>/dev/null)
+    reason="set version is not installed and fallback_to_system = yes"
+  fi
+
+  if [ -z "$selected" ]; then
+    printf "%-12s%s\\n" "selected" "none, no set version provides $shim_name"
+  else
+    printf "%-12s%s (%s)\\n" "selected" "$selected" "$reason"
+  fi
+
+  explain_exec() {
+    local plugin_name=$1
+    local version=$2
+    local executable_path=$3
+
+    if [ -n "$ASDF_INSTALL_PATH" ]; then
+      printf "%-12s%s\\n" "install" "$ASDF_INSTALL_PATH"
+    fi
+    local exec_path_script
+    exec_path_script="$(get_plugin_path "$plugin_name")/bin/exec-path"
+    if [ -x "$exec_path_script" ]; then
+      printf "%-12s%s\\n" "exec-path" "$exec_path_script"
+    fi
+
+    if [ ! -x "$executable_path" ]; then
+      printf "No %s executable found for %s %s\\n" "$shim_name" "$plugin_name" "$version" >&2
+      exit 1
+    fi
+
+    printf "%-12s%s\\n" "bin path" "$(dirname "$executable_path")"
+    printf "%-12s%s\\n" "executable" "$executable_path"
+    exit 0
+  }
+
+  with_shim_executable "$shim_name" explain_exec || return 1
+}
+
+# Warn when another executable earlier in PATH would run instead of the shim
+warn_if_shim_shadowed() {
+  local shim_name=$1
+  local shim_path
+  shim_path="$(asdf_data_dir)/shims/$shim_name"
+
+  if [ ! -f "$shim_path" ]; then
+    return 0
+  fi
+
+  local resolved_path
+  resolved_path=$(command -v "$shim_name" 2>/dev/null)
+  if [[ "$resolved_path" == /* ]] && [ "$resolved_path" != "$shim_path" ]; then
+    asdf_warn "$resolved_path is earlier in PATH and shadows the asdf shim for $shim_name"
+  fi
+}
+
 which_command "$@"
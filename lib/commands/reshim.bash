@@ -15,6 +15,15 @@ remove_shim_for_version() {
     return 0
   fi
 
+  if asdf_dry_run; then
+    if [ "$(grep -c "# asdf-plugin:" "$shim_path")" -eq 1 ] || [ "$count_installed" -eq 0 ]; then
+      dry_run_note "remove $shim_path"
+    else
+      dry_run_note "remove $plugin_name $version from $shim_path"
+    fi
+    return 0
+  fi
+
   sed -i.bak -e "/# asdf-plugin: $plugin_name $version"'$/d' "$shim_path"
   rm "$shim_path".bak
 
@@ -25,6 +34,27 @@ remove_shim_for_version() {
 }
 
 reshim_command() {
+  local positional=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    --dry-run)
+      ASDF_DRY_RUN=yes
+      shift
+      ;;
+    --fail-fast)
+      ASDF_FAIL_FAST=yes
+      shift
+      ;;
+    *)
+      positional+=("$1")
+      shift
+      ;;
+    esac
+  done
+
+  set -- "${positional[@]}"
+
   local plugin_name=$1
   local full_version=$2
 
@@ -32,12 +62,15 @@ reshim_command() {
     local plugins_path
     plugins_path=$(get_plugin_path)
 
+    batch_start
     if find "$plugins_path" -mindepth 1 -type d &>/dev/null; then
       for plugin_path in "$plugins_path"/*/; do
         plugin_name=$(basename "$plugin_path")
-        reshim_command "$plugin_name"
+        batch_run "$plugin_name" reshim_command "$plugin_name"
+        batch_should_stop && break
       done
     fi
+    batch_report "Reshim" || exit 1
     return 0
   fi
 
@@ -64,11 +97,14 @@ reshim_command() {
     done
   fi
 
+  update_shim_index
 }
 
 ensure_shims_dir() {
   # Create shims dir if doesn't exist
-  if [ ! -d "$(asdf_data_dir)/shims" ]; then
+  if [ ! -d "$(asdf_data_dir)/shims" ] && asdf_dry_run; then
+    dry_run_note "create $(asdf_data_dir)/shims"
+  elif [ ! -d "$(asdf_data_dir)/shims" ]; then
     mkdir "$(asdf_data_dir)/shims"
   fi
 }
@@ -88,16 +124,30 @@ write_shim_script() {
   local shim_path
   shim_path="$(asdf_data_dir)/shims/$executable_name"
 
+  if asdf_dry_run; then
+    if [ ! -f "$shim_path" ]; then
+      dry_run_note "write $shim_path"
+    elif ! grep -x "# asdf-plugin: ${plugin_name} ${version}" "$shim_path" >/dev/null; then
+      dry_run_note "add ${plugin_name} ${version} to $shim_path"
+    fi
+    return 0
+  fi
+
   if [ -f "$shim_path" ]; then
     if ! grep -x "# asdf-plugin: ${plugin_name} ${version}" "$shim_path" >/dev/null; then
       sed -i.bak -e "s/exec /# asdf-plugin: ${plugin_name} ${version}\\"$'\n''exec /' "$shim_path"
       rm -f "$shim_path".bak
     fi
+    # Shims written by older versions did not quote the asdf path
+    if grep -q '^exec [^"]' "$shim_path"; then
+      sed -i.bak -e 's|^exec \(.*\)/bin/asdf exec |exec "\1/bin/asdf" exec |' "$shim_path" # asdf_allow: ' asdf '
+      rm -f "$shim_path".bak
+    fi
   else
     cat <<EOF >"$shim_path"
 #!/usr/bin/env bash
 # asdf-plugin: ${plugin_name} ${version}
-exec $(asdf_dir)/bin/asdf exec "${executable_name}" "\$@" # asdf_allow: ' asdf '
+exec "$(asdf_dir)/bin/asdf" exec "${executable_name}" "\$@" # asdf_allow: ' asdf '
 EOF
   fi
 
@@ -145,19 +195,19 @@ remove_obsolete_shims() {
   local formatted_shims
   local formatted_exec_names
 
-  local temp_dir
-  temp_dir=${TMPDIR:-/tmp}
+  local tmp_dir
+  tmp_dir=$(make_tmp_dir "reshim-${plugin_name}")
 
   # comm only takes to files, so we write this data to temp files so we can
   # pass it to comm.
-  formatted_shims="$(mktemp "$temp_dir/asdf-command-reshim-formatted-shims.XXXXXX")"
+  formatted_shims="$tmp_dir/formatted-shims"
   printf "%s\\n" "$shims" >"$formatted_shims"
 
-  formatted_exec_names="$(mktemp "$temp_dir/asdf-command-reshim-formatted-exec-names.XXXXXX")"
+  formatted_exec_names="$tmp_dir/formatted-exec-names"
   printf "%s\\n" "$exec_names" >"$formatted_exec_names"
 
   obsolete_shims=$(comm -23 "$formatted_shims" "$formatted_exec_names")
-  rm -f "$formatted_exec_names" "$formatted_shims"
+  rm -rf "$tmp_dir"
 
   for shim_name in $obsolete_shims; do
     remove_shim_for_version "$plugin_name" "$full_version" "$shim_name"
@@ -1,3 +1,5 @@
 # -*- sh -*-
 # shellcheck source=lib/functions/versions.bash
 . "$(dirname "$(dirname "$0")")/lib/functions/versions.bash"
+# shellcheck source=lib/functions/tool_versions.bash
+. "$(dirname "$(dirname "$0")")/lib/functions/tool_versions.bash"
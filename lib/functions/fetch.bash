@@ -0,0 +1,68 @@
+# Downloads for plugin scripts, which run bin/private/asdf-fetch through the
+# ASDF_FETCH variable, so that every plugin gets the same proxy, retry and
+# checksum handling. curl is used when it is installed, wget otherwise. Both
+# read the proxy settings applied by with_proxy.
+
+# Downloads a URL to a file, retrying a failed download fetch_retries times
+# with a growing delay, and checks the sha256 checksum of the file when one is
+# given. The file is only written once the download is complete and verified.
+fetch_url() {
+  local url=$1
+  local file=$2
+  local sha256=$3
+
+  require_online "download $url" || return 1
+
+  if ! command -v curl >/dev/null 2>&1 && ! command -v wget >/dev/null 2>&1; then
+    display_error "curl or wget is needed to download $url"
+    return 1
+  fi
+
+  local retries
+  retries=$(get_asdf_config_value "fetch_retries")
+  [[ "$retries" =~ ^[0-9]+$ ]] || retries=0
+
+  local partial_path="$file.part"
+  local attempt=0
+  until with_proxy fetch_url_once "$url" "$partial_path"; do
+    rm -f "$partial_path"
+    if [ "$attempt" -ge "$retries" ]; then
+      display_error "Could not download $url"
+      return 1
+    fi
+    attempt=$((attempt + 1))
+    display_error "Could not download $url, retrying in $((2 ** attempt)) seconds ($attempt of $retries)"
+    sleep $((2 ** attempt))
+  done
+
+  if [ -n "$sha256" ]; then
+    local actual_sha256
+    actual_sha256=$(file_sha256 "$partial_path")
+    if [ "$actual_sha256" != "$(tr '[:upper:]' '[:lower:]' <<<"$sha256")" ]; then
+      rm -f "$partial_path"
+      display_error "Checksum mismatch for $url: expected $sha256, got $actual_sha256"
+      return 1
+    fi
+  fi
+
+  mv -f "$partial_path" "$file"
+}
+
+fetch_url_once() {
+  local url=$1
+  local file=$2
+
+  if command -v curl >/dev/null 2>&1; then
+    curl -fsSL -o "$file" "$url"
+  else
+    wget -q -O "$file" "$url"
+  fi
+}
+
+file_sha256() {
+  if command -v sha256sum >/dev/null 2>&1; then
+    sha256sum "$1" | cut -d ' ' -f 1
+  else
+    shasum -a 256 "$1" | cut -d ' ' -f 1
+  fi
+}
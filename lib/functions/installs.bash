@@ -1,30 +1,280 @@
 handle_failure() {
   local install_path="$1"
-  rm -rf "$install_path"
+  local tmp_dir="$2"
+  rm -rf "$install_path" "$tmp_dir"
   exit 1
 }
 
 handle_cancel() {
   local install_path="$1"
-  printf "\\nreceived sigint, cleaning up"
-  handle_failure "$install_path"
+  local tmp_dir="$2"
+  local partial_download_path="$3"
+  printf "\\nreceived sigint, cleaning up\\n"
+  rm -rf "$partial_download_path"
+  handle_failure "$install_path" "$tmp_dir"
 }
 
 install_command() {
+  local jobs
+  local ASDF_INSTALL_PIN
+  local positional=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    -j | --jobs)
+      if [ "$#" -lt 2 ]; then
+        display_error "--jobs must be a positive integer"
+        exit 1
+      fi
+      jobs=$2
+      shift 2
+      ;;
+    --jobs=*)
+      jobs=${1#--jobs=}
+      shift
+      ;;
+    --pin)
+      ASDF_INSTALL_PIN=yes
+      shift
+      ;;
+    --dry-run)
+      ASDF_DRY_RUN=yes
+      shift
+      ;;
+    --fail-fast)
+      ASDF_FAIL_FAST=yes
+      shift
+      ;;
+    --tool-versions | --file)
+      use_tool_versions_file "$2"
+      shift 2
+      ;;
+    *)
+      positional+=("$1")
+      shift
+      ;;
+    esac
+  done
+
+  set -- "${positional[@]}"
+
+  if [ -n "$jobs" ] && ! [[ "$jobs" =~ ^[1-9][0-9]*$ ]]; then
+    display_error "--jobs must be a positive integer"
+    exit 1
+  fi
+
   local plugin_name=$1
   local full_version=$2
   local extra_args="${*:3}"
 
-  if [ "$plugin_name" = "" ] && [ "$full_version" = "" ]; then
-    install_local_tool_versions "$extra_args"
+  if [ -n "$ASDF_INSTALL_PIN" ] && [ -n "$full_version" ]; then
+    display_error "--pin only applies to versions read from $(version_file_name) files"
+    exit 1
+  fi
+
+  if [ -n "$ASDF_TOOL_VERSIONS_FILE" ] && [ -n "$full_version" ]; then
+    display_error "--tool-versions only applies to versions read from a version file"
+    exit 1
+  fi
+
+  if is_plugin_pattern "$plugin_name"; then
+    install_plugin_pattern "$plugin_name" "$full_version" "$extra_args"
+  elif [ "$plugin_name" = "" ] && [ "$full_version" = "" ]; then
+    # A dry run prints its plan one install at a time
+    asdf_dry_run && jobs=1
+    install_local_tool_versions "${jobs:-$(get_concurrency)}"
   elif [[ $# -eq 1 ]]; then
     install_one_local_tool "$plugin_name"
   else
     install_tool_version "$plugin_name" "$full_version" "$extra_args"
   fi
+
+  if asdf_dry_run; then
+    collect_garbage dry-run
+  else
+    collect_garbage quiet
+  fi
+}
+
+# Installs the given version, or the versions set in the config files when no
+# version is given, of every installed plugin matching the pattern. Plugins
+# without a version set are skipped, and a failure of one plugin does not stop
+# the others from installing unless --fail-fast is given.
+install_plugin_pattern() {
+  local pattern=$1
+  local full_version=$2
+  local extra_args=$3
+
+  local plugin_names
+  plugin_names=$(expand_plugin_pattern "$pattern") || exit 1
+
+  batch_start
+
+  local plugin_name
+  local some_tools_installed
+  while IFS= read -r plugin_name; do
+    if [ -z "$full_version" ]; then
+      [ -n "$(find_versions "$plugin_name" "$PWD")" ] || continue
+      some_tools_installed=yes
+      batch_run "$plugin_name" install_one_local_tool "$plugin_name"
+    else
+      batch_run "$plugin_name $full_version" install_tool_version "$plugin_name" "$full_version" "$extra_args"
+    fi
+    batch_should_stop && break
+  done <<<"$plugin_names"
+
+  if [ -z "$full_version" ] && [ -z "$some_tools_installed" ]; then
+    printf "No versions specified for plugins matching %s in config files or environment\\n" "$pattern"
+    exit 1
+  fi
+
+  batch_report "Install" || exit 1
+}
+
+# Runs the given command while holding the lock shared by parallel installs.
+# Runs it directly when installs are not running in parallel. The command runs
+# in a subshell, so the lock is released even when it exits. A lock left by an
+# install that was killed is taken over, and waiting gives up after ten
+# minutes.
+with_install_lock() {
+  if [ -z "$ASDF_INSTALL_LOCK" ]; then
+    "$@"
+    return
+  fi
+
+  local lock_dir="$ASDF_INSTALL_LOCK/held"
+  local holder waited=0
+  until mkdir "$lock_dir" 2>/dev/null; do
+    holder=$(cat "$lock_dir/pid" 2>/dev/null)
+    if [ -n "$holder" ] && ! kill -0 "$holder" 2>/dev/null; then
+      rm -rf "$lock_dir"
+      continue
+    fi
+
+    if [ "$waited" -ge 6000 ]; then
+      display_error "Timed out waiting for the install lock held by process ${holder:-unknown}"
+      return 1
+    fi
+    sleep 0.1
+    waited=$((waited + 1))
+  done
+  printf "%s\\n" "${BASHPID:-$$}" >"$lock_dir/pid"
+
+  local exit_code=0
+  ("$@") || exit_code=$?
+  rm -rf "$lock_dir"
+  return "$exit_code"
+}
+
+# Runs an install step, prefixing each line of its output with the given label
+# when installs run in parallel so that interleaved output can be told apart.
+run_install_step() {
+  local label=$1
+  shift
+
+  if [ -z "$ASDF_INSTALL_LOCK" ]; then
+    "$@"
+    return
+  fi
+
+  "$@" 2>&1 | prefix_lines "[$label] "
+  return "${PIPESTATUS[0]}"
 }
 
+prefix_lines() {
+  local prefix=$1
+  local line
+  while IFS= read -r line || [ -n "$line" ]; do
+    printf "%s%s\\n" "$prefix" "$line"
+  done
+}
+
+# Prints install progress to stderr. Progress is shown when stderr is a terminal
+# unless ASDF_PROGRESS is set to 1 (always) or 0 (never).
+report_progress() {
+  case "${ASDF_PROGRESS:-}" in
+  1) ;;
+  0) return 0 ;;
+  *) [ -t 2 ] || return 0 ;;
+  esac
+
+  printf "%s\\n" "$1" >&2
+}
+
+# Verifies the detached signatures a download script placed next to the files
+# they sign. GPG signatures (.sig, .asc) are checked against signature_keyring
+# and minisign signatures (.minisig) against minisign_public_key. Signatures
+# without a configured key are ignored unless require_signatures is yes.
+verify_download_signatures() {
+  local download_path=$1
+
+  local keyring
+  keyring=$(get_asdf_config_value "signature_keyring")
+  keyring=${keyring/#\~/$HOME}
+  local minisign_key
+  minisign_key=$(get_asdf_config_value "minisign_public_key")
+  minisign_key=${minisign_key/#\~/$HOME}
+  local require_signatures
+  require_signatures=$(get_asdf_config_value "require_signatures")
+
+  local verified_count=0
+  local signature
+  for signature in "$download_path"/*.sig "$download_path"/*.asc "$download_path"/*.minisig; do
+    [ -f "$signature" ] || continue
+
+    local signed_file=${signature%.*}
+    if [ ! -f "$signed_file" ]; then
+      display_error "No file found for signature $(basename "$signature")"
+      return 1
+    fi
+
+    local verify_output
+    case "$signature" in
+    *.minisig)
+      [ -n "$minisign_key" ] || continue
+      verify_output=$(minisign -V -q -p "$minisign_key" -m "$signed_file" -x "$signature" 2>&1)
+      ;;
+    *)
+      [ -n "$keyring" ] || continue
+      verify_output=$(gpg --batch --no-default-keyring --keyring "$keyring" --verify "$signature" "$signed_file" 2>&1)
+      ;;
+    esac
+
+    # shellcheck disable=SC2181
+    if [ $? -ne 0 ]; then
+      display_error "Signature verification failed for $(basename "$signed_file")"
+      [ -n "$verify_output" ] && display_error "$verify_output"
+      return 1
+    fi
+
+    verified_count=$((verified_count + 1))
+  done
+
+  if [ "$require_signatures" = "yes" ] && [ "$verified_count" -eq 0 ]; then
+    display_error "No verified signatures found in the download and require_signatures is yes"
+    return 1
+  fi
+}
+
+# Prints how many installs or plugin updates run at once, which plugins also
+# get in ASDF_CONCURRENCY: ASDF_CONCURRENCY, else the concurrency setting, where
+# auto means the number of cores.
 get_concurrency() {
+  local concurrency=${ASDF_CONCURRENCY:-$(get_asdf_config_value "concurrency")}
+
+  if [[ "$concurrency" =~ ^[1-9][0-9]*$ ]]; then
+    printf "%s\\n" "$concurrency"
+    return 0
+  fi
+
+  if [ -n "$concurrency" ] && [ "$concurrency" != "auto" ]; then
+    asdf_warn "concurrency is $concurrency, which is neither auto nor a positive number, using the number of cores"
+  fi
+
+  get_cpu_count
+}
+
+get_cpu_count() {
   if command -v nproc >/dev/null 2>&1; then
     nproc
   elif command -v sysctl >/dev/null 2>&1 && sysctl hw.ncpu >/dev/null 2>&1; then
@@ -52,9 +302,27 @@ install_one_local_tool() {
     local plugin_version
     some_tools_installed='yes'
     plugin_versions=$(cut -d '|' -f 1 <<<"$plugin_version_and_path")
+
+    batch_start
+    local results=() failures
     for plugin_version in $plugin_versions; do
-      install_tool_version "$plugin_name" "$plugin_version"
+      failures=${#ASDF_BATCH_FAILURES[@]}
+      batch_run "$plugin_name $plugin_version" install_local_tool_version "$plugin_name" "$plugin_version" "$(cut -d '|' -f 2 <<<"$plugin_version_and_path")"
+      if [ "${#ASDF_BATCH_FAILURES[@]}" -gt "$failures" ]; then
+        results+=("$plugin_version: failed")
+      else
+        results+=("$plugin_version: installed")
+      fi
+      batch_should_stop && break
     done
+
+    # A summary of each version, when there is more than one
+    if [ "${#results[@]}" -gt 1 ]; then
+      printf "\\n%s versions:\\n" "$plugin_name"
+      printf "  %s\\n" "${results[@]}"
+    fi
+
+    batch_report "Install" || exit 1
   else
     printf "No versions specified for %s in config files or environment\\n" "$plugin_name"
     exit 1
@@ -62,6 +330,7 @@ install_one_local_tool() {
 }
 
 install_local_tool_versions() {
+  local jobs=${1:-1}
   local plugins_path
   plugins_path=$(get_plugin_path)
 
@@ -106,6 +375,29 @@ install_local_tool_versions() {
     exit 1
   fi
 
+  # The versions ASDF_STRICT refuses are reported before any install starts,
+  # rather than as failed items of the batch
+  if asdf_strict; then
+    local plugin_version_and_path plugin_version
+    for plugin_name in $plugins_installed; do
+      plugin_version_and_path="$(find_versions "$plugin_name" "$search_path")"
+      for plugin_version in ${plugin_version_and_path%%|*}; do
+        check_strict_tool_version "$plugin_name" "$plugin_version" "${plugin_version_and_path#*|}" || exit 1
+      done
+    done
+  fi
+
+  batch_start
+
+  local pids=()
+  local pid_labels=()
+  local ASDF_INSTALL_LOCK
+  if [ "$jobs" -gt 1 ]; then
+    # Reshims and hooks from parallel installs are serialized with this lock
+    ASDF_INSTALL_LOCK="$(make_tmp_dir "install-lock")"
+    trap 'kill "${pids[@]}" 2>/dev/null; rm -rf "$ASDF_INSTALL_LOCK"; exit 1' INT
+  fi
+
   if [ -n "$plugins_installed" ]; then
     for plugin_name in $plugins_installed; do
       local plugin_version_and_path
@@ -116,12 +408,38 @@ install_local_tool_versions() {
         some_tools_installed='yes'
         plugin_versions=$(cut -d '|' -f 1 <<<"$plugin_version_and_path")
         for plugin_version in $plugin_versions; do
-          install_tool_version "$plugin_name" "$plugin_version"
+          if [ "$jobs" -gt 1 ]; then
+            # Wait for the oldest install when all job slots are taken
+            if [ "${#pids[@]}" -ge "$jobs" ]; then
+              wait "${pids[0]}" || batch_add_failure "${pid_labels[0]}"
+              pids=("${pids[@]:1}")
+              pid_labels=("${pid_labels[@]:1}")
+            fi
+            batch_should_stop && break 2
+            batch_add_item
+            install_local_tool_version "$plugin_name" "$plugin_version" "$(cut -d '|' -f 2 <<<"$plugin_version_and_path")" &
+            pids+=($!)
+            pid_labels+=("$plugin_name $plugin_version")
+          else
+            batch_run "$plugin_name $plugin_version" install_local_tool_version "$plugin_name" "$plugin_version" "$(cut -d '|' -f 2 <<<"$plugin_version_and_path")"
+            batch_should_stop && break 2
+          fi
         done
       fi
     done
   fi
 
+  local index
+  for index in "${!pids[@]}"; do
+    wait "${pids[$index]}" || batch_add_failure "${pid_labels[$index]}"
+  done
+
+  if [ -n "$ASDF_INSTALL_LOCK" ]; then
+    rm -rf "$ASDF_INSTALL_LOCK"
+  fi
+
+  batch_report "Install" || exit 1
+
   if [ -z "$some_tools_installed" ]; then
     printf "Either specify a tool & version in the command\\n"
     printf "OR add .tool-versions file in this directory\\n"
@@ -130,6 +448,40 @@ install_local_tool_versions() {
   fi
 }
 
+# Fails with an error for a latest, latest:<version> or constraint version
+# read from a version file when ASDF_STRICT is set.
+check_strict_tool_version() {
+  local plugin_name=$1
+  local full_version=$2
+  local version_file=$3
+
+  if asdf_strict && { [[ $full_version == latest* ]] || is_version_constraint "$full_version"; }; then
+    display_error "$plugin_name $full_version in $version_file is not an exact version, which ASDF_STRICT requires"
+    return 1
+  fi
+}
+
+# Installs a version read from a version file. With --pin a latest or
+# latest:<version> version is resolved first, and once installed the concrete
+# version is written to the version file in its place.
+install_local_tool_version() {
+  local plugin_name=$1
+  local full_version=$2
+  local version_file=$3
+
+  check_strict_tool_version "$plugin_name" "$full_version" "$version_file" || exit 1
+
+  if [ -z "$ASDF_INSTALL_PIN" ]; then
+    install_tool_version "$plugin_name" "$full_version"
+    return
+  fi
+
+  local resolved
+  resolved=$(resolve_latest_version "$plugin_name" "$full_version") || exit 1
+  install_tool_version "$plugin_name" "$resolved" &&
+    with_install_lock pin_tool_version "$version_file" "$plugin_name" "$full_version" "$resolved"
+}
+
 install_tool_version() {
   local plugin_name=$1
   local full_version=$2
@@ -139,6 +491,7 @@ install_tool_version() {
 
   plugin_path=$(get_plugin_path "$plugin_name")
   check_if_plugin_exists "$plugin_name"
+  check_plugin_deprecations "$plugin_name"
 
   for flag in $flags; do
     case "$flag" in
@@ -167,6 +520,10 @@ install_tool_version() {
       local version
       version=$(latest_command "$plugin_name" "${version_info[1]}")
       full_version=$version
+    elif is_version_constraint "$full_version"; then
+      local version
+      version=$(resolve_version_constraint "$plugin_name" "$full_version") || exit 1
+      full_version=$version
     else
       local version="${version_info[0]}"
     fi
@@ -178,62 +535,325 @@ install_tool_version() {
   download_path=$(get_download_path "$plugin_name" "$install_type" "$version")
   local concurrency
   concurrency=$(get_concurrency)
-  trap 'handle_cancel $install_path' INT
+  local tmp_dir
 
   if [ -d "$install_path" ]; then
     printf "%s %s is already installed\\n" "$plugin_name" "$full_version"
+  elif asdf_dry_run; then
+    print_install_plan
   else
+    tmp_dir=$(make_tmp_dir "install-${plugin_name}")
+    # A download kept from an earlier install is not ours to remove
+    local partial_download_path=""
+    if [ ! -d "$download_path" ]; then
+      partial_download_path=$download_path
+    fi
+    trap 'handle_cancel "$install_path" "$tmp_dir" "$partial_download_path"' INT TERM
 
+    local started_at=$SECONDS
+    local download_exit_code=0
     if [ -f "${plugin_path}/bin/download" ]; then
       # Not a legacy plugin
       # Run the download script
-      (
-        # shellcheck disable=SC2030
-        export ASDF_INSTALL_TYPE=$install_type
-        # shellcheck disable=SC2030
-        export ASDF_INSTALL_VERSION=$version
-        # shellcheck disable=SC2030
-        export ASDF_INSTALL_PATH=$install_path
-        # shellcheck disable=SC2030
-        export ASDF_DOWNLOAD_PATH=$download_path
-        mkdir "$download_path"
-        asdf_run_hook "pre_asdf_download_${plugin_name}" "$full_version"
-        "${plugin_path}"/bin/download
-      )
+      report_progress "Downloading $plugin_name $full_version"
+      run_install_step "$plugin_name $full_version" run_download_script "$plugin_name" "$install_type" "$version" "$full_version" "$install_path" "$download_path" "$tmp_dir"
+      download_exit_code=$?
+    fi
+
+    if [ $download_exit_code -eq 0 ]; then
+      # Unverified downloads must not be reused by a later install
+      verify_download_signatures "$download_path" || {
+        download_exit_code=$?
+        rm -rf "$download_path"
+      }
     fi
 
-    local download_exit_code=$?
+    local install_exit_code=0
     if [ $download_exit_code -eq 0 ]; then
-      (
-        # shellcheck disable=SC2031
-        export ASDF_INSTALL_TYPE=$install_type
-        # shellcheck disable=SC2031
-        export ASDF_INSTALL_VERSION=$version
-        # shellcheck disable=SC2031
-        export ASDF_INSTALL_PATH=$install_path
-        # shellcheck disable=SC2031
-        export ASDF_DOWNLOAD_PATH=$download_path
-        # shellcheck disable=SC2031
-        export ASDF_CONCURRENCY=$concurrency
-        mkdir "$install_path"
-        asdf_run_hook "pre_asdf_install_${plugin_name}" "$full_version"
-        "${plugin_path}"/bin/install
-      )
+      report_progress "Installing $plugin_name $full_version"
+      run_install_step "$plugin_name $full_version" run_install_script "$plugin_name" "$install_type" "$version" "$full_version" "$install_path" "$download_path" "$tmp_dir" "$concurrency"
+      install_exit_code=$?
     fi
 
-    local install_exit_code=$?
     if [ $install_exit_code -eq 0 ] && [ $download_exit_code -eq 0 ]; then
+      # The install is complete, an interrupted reshim can be rerun
+      trap - INT TERM
+
+      write_install_receipt "$install_path" "$(get_receipt_path "$plugin_name" "$install_type" "$version")"
+
+      local download_size_kb=""
+      if [ -d "$download_path" ]; then
+        download_size_kb=$(du -sk "$download_path" | cut -f 1)
+      fi
+
       # Remove download directory if --keep-download flag or always_keep_download config setting are not set
       always_keep_download=$(get_asdf_config_value "always_keep_download")
       if [ ! "$keep_download" = "true" ] && [ ! "$always_keep_download" = "yes" ] && [ -d "$download_path" ]; then
         rm -r "$download_path"
       fi
 
-      reshim_command "$plugin_name" "$full_version"
+      rm -rf "$tmp_dir"
+
+      with_install_lock reshim_command "$plugin_name" "$full_version"
 
-      asdf_run_hook "post_asdf_install_${plugin_name}" "$full_version"
+      local summary_dir
+      summary_dir=$(make_tmp_dir "install-summary-${plugin_name}")
+      write_install_summary "$((SECONDS - started_at))" "$download_size_kb" >"$summary_dir/summary.json"
+      ASDF_INSTALL_SUMMARY_FILE="$summary_dir/summary.json" \
+        with_install_lock asdf_run_hook "post_asdf_install_${plugin_name}" "$full_version"
+      rm -rf "$summary_dir"
+
+      report_progress "Installed $plugin_name $full_version in $((SECONDS - started_at))s"
     else
-      handle_failure "$install_path"
+      report_progress "Failed to install $plugin_name $full_version"
+      # A failed download is not reused, unless it was kept from an earlier
+      # install
+      if [ $download_exit_code -ne 0 ]; then
+        rm -rf "$partial_download_path"
+      fi
+      handle_failure "$install_path" "$tmp_dir"
     fi
   fi
 }
+
+# Runs the download script of a plugin in a subshell with the variables it
+# reads, after the pre_asdf_download hook.
+run_download_script() {
+  local plugin_name=$1
+  local install_type=$2
+  local version=$3
+  local full_version=$4
+  local install_path=$5
+  local download_path=$6
+  local tmp_dir=$7
+
+  (
+    export ASDF_INSTALL_TYPE=$install_type
+    export ASDF_INSTALL_VERSION=$version
+    export ASDF_INSTALL_PATH=$install_path
+    export ASDF_DOWNLOAD_PATH=$download_path
+    export TMPDIR=$tmp_dir
+    mkdir "$download_path"
+    with_install_lock asdf_run_hook "pre_asdf_download_${plugin_name}" "$full_version"
+    run_plugin_script "$(get_plugin_path "$plugin_name")/bin/download"
+  )
+}
+
+# Runs the install script of a plugin in a subshell with the variables it
+# reads, after the pre_asdf_install hook.
+run_install_script() {
+  local plugin_name=$1
+  local install_type=$2
+  local version=$3
+  local full_version=$4
+  local install_path=$5
+  local download_path=$6
+  local tmp_dir=$7
+  local concurrency=$8
+
+  (
+    export ASDF_INSTALL_TYPE=$install_type
+    export ASDF_INSTALL_VERSION=$version
+    export ASDF_INSTALL_PATH=$install_path
+    export ASDF_DOWNLOAD_PATH=$download_path
+    export ASDF_CONCURRENCY=$concurrency
+    export TMPDIR=$tmp_dir
+    mkdir "$install_path"
+    with_install_lock asdf_run_hook "pre_asdf_install_${plugin_name}" "$full_version"
+    run_plugin_script "$(get_plugin_path "$plugin_name")/bin/install"
+  )
+}
+
+# Prints the JSON summary of a completed install that post_asdf_install_*
+# hooks read from ASDF_INSTALL_SUMMARY_FILE, using the variables of
+# install_tool_version. The download size is null when nothing was downloaded
+# and the trace path is null when plugin_trace_size is 0.
+write_install_summary() {
+  local duration=$1
+  local download_size_kb=$2
+
+  local trace_path="null"
+  local trace_size
+  trace_size=$(get_asdf_config_value "plugin_trace_size")
+  if [[ "$trace_size" =~ ^[0-9]+$ ]] && [ "$trace_size" -gt 0 ]; then
+    trace_path=$(json_string "$(asdf_data_dir)/trace")
+  fi
+
+  printf '{"plugin":%s,"version":%s,"install_type":%s,"install_path":%s,"duration_seconds":%s,"download_size_kb":%s,"trace_path":%s}\n' \
+    "$(json_string "$plugin_name")" "$(json_string "$version")" "$(json_string "$install_type")" \
+    "$(json_string "$install_path")" "$duration" "${download_size_kb:-null}" "$trace_path"
+}
+
+# Prints the changes and plugin scripts an install would make and run, using
+# the variables of install_tool_version.
+print_install_plan() {
+  local script_env="ASDF_INSTALL_TYPE=$install_type ASDF_INSTALL_VERSION=$version ASDF_INSTALL_PATH=$install_path ASDF_DOWNLOAD_PATH=$download_path"
+
+  if [ -f "${plugin_path}/bin/download" ]; then
+    [ -d "$download_path" ] || dry_run_note "create $download_path"
+    asdf_run_hook "pre_asdf_download_${plugin_name}" "$full_version"
+    dry_run_note "run ${plugin_path}/bin/download with $script_env"
+  fi
+
+  dry_run_note "create $install_path"
+  asdf_run_hook "pre_asdf_install_${plugin_name}" "$full_version"
+  dry_run_note "run ${plugin_path}/bin/install with $script_env ASDF_CONCURRENCY=$concurrency"
+  dry_run_note "write $(get_receipt_path "$plugin_name" "$install_type" "$version")"
+
+  if [ ! "$keep_download" = "true" ] && [ ! "$(get_asdf_config_value "always_keep_download")" = "yes" ]; then
+    dry_run_note "remove $download_path"
+  fi
+
+  dry_run_note "reshim $plugin_name $full_version"
+  asdf_run_hook "post_asdf_install_${plugin_name}" "$full_version"
+}
+
+# Prints a sha256 line for every regular file in an install, with paths
+# relative to the install and sorted so manifests can be compared.
+install_manifest() {
+  local install_path=$1
+  local sha256_cmd=(sha256sum)
+  if ! command -v sha256sum >/dev/null 2>&1; then
+    sha256_cmd=(shasum -a 256)
+  fi
+
+  (
+    cd "$install_path" || exit 1
+    find . -type f -exec "${sha256_cmd[@]}" {} +
+  ) | LC_ALL=C sort -k 2
+}
+
+# Records the files of a completed install so `asdf verify` can check it later.
+write_install_receipt() {
+  local install_path=$1
+  local receipt_path=$2
+
+  mkdir -p "$(dirname "$receipt_path")"
+  if install_manifest "$install_path" >"$receipt_path.$$"; then
+    mv -f "$receipt_path.$$" "$receipt_path"
+  else
+    rm -f "$receipt_path.$$"
+    asdf_warn "could not record the files of $install_path, \`asdf verify\` will not be able to check it"
+  fi
+}
+
+# Removes temp dirs and downloads older than the configured ages, then the
+# oldest downloads until the download cache fits the configured size. The
+# mode is "quiet", "verbose" (print what was removed) or "dry-run".
+collect_garbage() {
+  local mode=${1:-verbose}
+  local data_dir
+  data_dir=$(asdf_data_dir)
+
+  local tmp_max_age download_max_age download_max_size metrics_max_age
+  metrics_max_age=$(get_asdf_config_value "shim_metrics_max_age_days")
+  tmp_max_age=$(get_asdf_config_value "tmp_max_age_days")
+  download_max_age=$(get_asdf_config_value "download_max_age_days")
+  download_max_size=$(get_asdf_config_value "download_cache_max_size_mb")
+
+  if [[ "$metrics_max_age" =~ ^[0-9]+$ ]] && [ -f "$data_dir/metrics" ]; then
+    collect_garbage_metrics "$mode" "$data_dir/metrics" $(($(date +%s) - metrics_max_age * 86400))
+  fi
+
+  local path
+  # Session files of shells that have exited
+  if [ -d "$data_dir/sessions" ]; then
+    for path in "$data_dir"/sessions/*; do
+      [ -f "$path" ] || continue
+      ps -p "$(basename "$path")" >/dev/null 2>&1 || collect_garbage_path "$mode" "$path"
+    done
+  fi
+
+  if [[ "$tmp_max_age" =~ ^[0-9]+$ ]] && [ -d "$data_dir/tmp" ]; then
+    while IFS= read -r path; do
+      [ -n "$path" ] && ! garbage_in_use "$path" && collect_garbage_path "$mode" "$path"
+    done <<<"$(find "$data_dir/tmp" -mindepth 1 -maxdepth 1 -mmin +$((tmp_max_age * 1440)))"
+  fi
+
+  if [ ! -d "$data_dir/downloads" ]; then
+    return 0
+  fi
+
+  if [[ "$download_max_age" =~ ^[0-9]+$ ]]; then
+    while IFS= read -r path; do
+      [ -n "$path" ] && ! garbage_in_use "$path" && collect_garbage_path "$mode" "$path"
+    done <<<"$(find "$data_dir/downloads" -mindepth 2 -maxdepth 2 -type d -mmin +$((download_max_age * 1440)))"
+  fi
+
+  if [[ "$download_max_size" =~ ^[0-9]+$ ]]; then
+    local download_paths=()
+    IFS=$'\n' read -rd '' -a download_paths <<<"$(
+      find "$data_dir/downloads" -mindepth 2 -maxdepth 2 -type d | while IFS= read -r path; do
+        garbage_in_use "$path" || printf "%s\\n" "$path"
+      done
+    )"
+
+    local total_size oldest_path index
+    total_size=$(du -sk "$data_dir/downloads" | cut -f 1)
+    while [ "$total_size" -gt $((download_max_size * 1024)) ] && [ "${#download_paths[@]}" -gt 0 ]; do
+      oldest_path=""
+      for index in "${!download_paths[@]}"; do
+        if [ -z "$oldest_path" ] || [ "${download_paths[$index]}" -ot "$oldest_path" ]; then
+          oldest_path=${download_paths[$index]}
+        fi
+      done
+      for index in "${!download_paths[@]}"; do
+        [ "${download_paths[$index]}" = "$oldest_path" ] && unset "download_paths[$index]"
+      done
+
+      total_size=$((total_size - $(du -sk "$oldest_path" | cut -f 1)))
+      collect_garbage_path "$mode" "$oldest_path"
+    done
+  fi
+}
+
+# Returns 0 when anything in a temp dir or download changed in the last hour,
+# as an install that is still running may be using it.
+garbage_in_use() {
+  [ -n "$(find "$1" -mmin -60 2>/dev/null | head -n 1)" ]
+}
+
+collect_garbage_metrics() {
+  local mode=$1
+  local metrics_path=$2
+  local cutoff=$3
+
+  local expired
+  expired=$(awk -F '\t' -v cutoff="$cutoff" '$1 < cutoff' "$metrics_path" | wc -l | tr -d ' ')
+  if [ "$expired" -eq 0 ]; then
+    return 0
+  fi
+
+  if [ "$mode" = "dry-run" ]; then
+    printf "Would remove %s shim metrics from %s\\n" "$expired" "$metrics_path"
+    return 0
+  fi
+
+  # Shims append to the log while it is trimmed, so it is moved aside first
+  # and the lines kept are appended back, after any recorded in the meantime
+  mv -f "$metrics_path" "$metrics_path.$$" &&
+    awk -F '\t' -v cutoff="$cutoff" '$1 >= cutoff' "$metrics_path.$$" >>"$metrics_path"
+  rm -f "$metrics_path.$$"
+
+  if [ "$mode" = "verbose" ]; then
+    printf "Removed %s shim metrics from %s\\n" "$expired" "$metrics_path"
+  fi
+}
+
+collect_garbage_path() {
+  local mode=$1
+  local path=$2
+
+  case "$mode" in
+  dry-run)
+    printf "Would remove %s\\n" "$path"
+    ;;
+  verbose)
+    rm -rf "$path"
+    printf "Removed %s\\n" "$path"
+    ;;
+  *)
+    rm -rf "$path"
+    ;;
+  esac
+}
@@ -4,6 +4,7 @@ plugin_list_command() {
 
   local show_repo
   local show_ref
+  local show_outdated
 
   while [ -n "$*" ]; do
     case "$1" in
@@ -15,26 +16,41 @@ plugin_list_command() {
       show_ref=true
       shift
       ;;
+    "--outdated")
+      show_outdated=true
+      shift
+      ;;
     *)
       shift
       ;;
     esac
   done
 
-  if find "$plugins_path" -mindepth 1 -type d &>/dev/null; then
+  if [ -n "$show_outdated" ] && find "$plugins_path" -mindepth 1 -type d &>/dev/null; then
+    require_online "check plugins for updates" || exit 1
+
+    printf "%-20s%-10s%s\n" "PLUGIN" "STATUS" "DETAIL"
+    for plugin_path in "$plugins_path"/*/; do
+      printf "%-20s%s\n" "$(basename "$plugin_path")" "$(plugin_update_status "${plugin_path%/}")"
+    done
+  elif find "$plugins_path" -mindepth 1 -type d &>/dev/null; then
     (
       for plugin_path in "$plugins_path"/*/; do
         plugin_name=$(basename "$plugin_path")
         printf "%s" "$plugin_name"
 
         if [ -n "$show_repo" ]; then
-          printf "\\t%s" "$(git --git-dir "$plugin_path/.git" remote get-url origin 2>/dev/null)"
+          printf "\\t%s" "$(redact_url "$(git --git-dir "$plugin_path/.git" remote get-url origin 2>/dev/null)")"
         fi
 
         if [ -n "$show_ref" ]; then
           local branch
           local gitref
           branch=$(git --git-dir "$plugin_path/.git" rev-parse --abbrev-ref HEAD 2>/dev/null)
+          # A plugin pinned to a tag or commit has no branch checked out
+          if [ "$branch" = "HEAD" ]; then
+            branch=$(get_plugin_pinned_ref "$plugin_path" || printf "HEAD")
+          fi
           gitref=$(git --git-dir "$plugin_path/.git" rev-parse --short HEAD 2>/dev/null)
           printf "\\t%s\\t%s" "$branch" "$gitref"
         fi
@@ -49,8 +65,49 @@ plugin_list_command() {
 }
 
 plugin_add_command() {
+  local refresh
+  local run_test
+  local pinned_ref
+  local positional=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    --refresh)
+      refresh="refresh"
+      shift
+      ;;
+    --ref)
+      if [ "$#" -lt 2 ]; then
+        display_error "--ref needs a branch, tag or commit"
+        exit 1
+      fi
+      pinned_ref=$2
+      shift 2
+      ;;
+    --ref=*)
+      pinned_ref=${1#--ref=}
+      shift
+      ;;
+    --test)
+      run_test=true
+      shift
+      ;;
+    *)
+      positional+=("$1")
+      shift
+      ;;
+    esac
+  done
+
+  set -- "${positional[@]}"
+
   if [[ $# -lt 1 || $# -gt 2 ]]; then
-    display_error "usage: asdf plugin add <name> [<git-url>]"
+    display_error "usage: asdf plugin add [--refresh] [--test] [--ref <git-ref>] <name> [<git-url>]"
+    exit 1
+  fi
+
+  if [ -n "${pinned_ref+set}" ] && [ -z "$pinned_ref" ]; then
+    display_error "--ref needs a branch, tag or commit"
     exit 1
   fi
 
@@ -65,7 +122,7 @@ plugin_add_command() {
   if [ -n "$2" ]; then
     local source_url=$2
   else
-    initialize_or_update_repository
+    initialize_or_update_repository "$refresh"
     local source_url
     source_url=$(get_plugin_source_url "$plugin_name")
   fi
@@ -84,18 +141,39 @@ plugin_add_command() {
     display_error "Plugin named $plugin_name already added"
     exit 2
   else
+    if [ ! -d "${source_url#file://}" ]; then
+      require_online "clone $source_url" || exit 1
+    fi
+
     asdf_run_hook "pre_asdf_plugin_add" "$plugin_name"
     asdf_run_hook "pre_asdf_plugin_add_${plugin_name}"
 
-    if ! git clone -q "$source_url" "$plugin_path"; then
+    if ! plugin_git clone -q "$source_url" "$plugin_path"; then
+      exit 1
+    fi
+
+    if [ -n "$pinned_ref" ]; then
+      if ! git -C "$plugin_path" -c advice.detachedHead=false checkout -q --force "$pinned_ref" 2>/dev/null; then
+        rm -rf "$plugin_path"
+        display_error "Plugin $plugin_name was not added: $source_url has no branch, tag or commit $pinned_ref"
+        exit 1
+      fi
+      set_plugin_pinned_ref "$plugin_path" "$pinned_ref"
+    fi
+
+    local problem
+    if [ -n "$run_test" ] && ! problem=$(test_added_plugin "$plugin_name" "$plugin_path"); then
+      rm -rf "$plugin_path"
+      display_error "Plugin $plugin_name failed its test and was not added: $problem"
       exit 1
     fi
 
     if [ -f "${plugin_path}/bin/post-plugin-add" ]; then
       (
-        export ASDF_PLUGIN_SOURCE_URL=$source_url
+        ASDF_PLUGIN_SOURCE_URL=$(redact_url "$source_url")
+        export ASDF_PLUGIN_SOURCE_URL
         export ASDF_PLUGIN_PATH=$plugin_path
-        "${plugin_path}/bin/post-plugin-add"
+        run_plugin_script "${plugin_path}/bin/post-plugin-add"
       )
     fi
 
@@ -104,65 +182,491 @@ plugin_add_command() {
   fi
 }
 
+# Prints whether a plugin is behind the ref `asdf plugin update` would update
+# it to, its pinned ref or else the default branch of its origin, as a status
+# padded to 10 columns and a detail. Only the remote is queried: the status
+# is "current", "outdated", "pinned" for a plugin pinned to a commit, which
+# never changes, or "unknown" when the origin can't be reached.
+plugin_update_status() {
+  local plugin_path=$1
+  local git_options=(--git-dir "$plugin_path/.git")
+
+  local local_ref remote_ref target
+  local_ref=$(git "${git_options[@]}" rev-parse HEAD 2>/dev/null)
+  target=$(get_plugin_pinned_ref "$plugin_path")
+
+  local refs
+  if [ -n "$target" ]; then
+    refs=$(plugin_git "${git_options[@]}" ls-remote origin "refs/heads/$target" "refs/tags/$target" "refs/tags/$target^{}" 2>/dev/null)
+    # An annotated tag is listed twice, the peeled line is the commit it tags
+    remote_ref=$(awk 'NR == 1 { first = $1 } $2 ~ /\^\{\}$/ { peeled = $1 } END { print peeled ? peeled : first }' <<<"$refs")
+    if [ -z "$remote_ref" ] && [[ "$target" =~ ^[0-9a-f]{7,40}$ ]]; then
+      printf "%-10s%s\\n" "pinned" "${local_ref:0:7} (commit $target)"
+      return 0
+    fi
+  elif refs=$(plugin_git "${git_options[@]}" ls-remote --symref origin HEAD 2>/dev/null); then
+    target=$(awk '$1 == "ref:" { sub(/refs\/heads\//, "", $2); print $2; exit }' <<<"$refs")
+    remote_ref=$(awk '$1 != "ref:" { print $1; exit }' <<<"$refs")
+  fi
+
+  if [ -z "$remote_ref" ]; then
+    printf "%-10s%s\\n" "unknown" "could not find ${target:-the default branch} on origin"
+  elif [ "$remote_ref" = "$local_ref" ]; then
+    printf "%-10s%s\\n" "current" "${local_ref:0:7} ($target)"
+  else
+    printf "%-10s%s\\n" "outdated" "${local_ref:0:7} -> ${remote_ref:0:7} ($target)"
+  fi
+}
+
+# Prints the branch, tag or commit a plugin was pinned to by
+# `asdf plugin add --ref`, if any. It is kept in the git config of the plugin.
+get_plugin_pinned_ref() {
+  local plugin_path=$1
+  git --git-dir "$plugin_path/.git" config --get asdf.ref 2>/dev/null
+}
+
+# Pins a plugin to a ref, or unpins it when the ref is empty.
+set_plugin_pinned_ref() {
+  local plugin_path=$1
+  local ref=$2
+
+  if [ -n "$ref" ]; then
+    git --git-dir "$plugin_path/.git" config asdf.ref "$ref"
+  else
+    git --git-dir "$plugin_path/.git" config --unset asdf.ref 2>/dev/null || true
+  fi
+}
+
+# Checks that a newly cloned plugin has executable bin/list-all and
+# bin/install callbacks and that bin/list-all prints at least one version
+# within 30 seconds. Prints the problem found, if any, and fails.
+test_added_plugin() {
+  local plugin_name=$1
+  local plugin_path=$2
+  local timeout_seconds=30
+
+  local script
+  for script in list-all install; do
+    if [ ! -f "$plugin_path/bin/$script" ]; then
+      printf "it has no bin/%s\\n" "$script"
+      return 1
+    elif [ ! -x "$plugin_path/bin/$script" ]; then
+      printf "bin/%s is not executable\\n" "$script"
+      return 1
+    fi
+  done
+
+  local tmp_dir
+  tmp_dir=$(make_tmp_dir "plugin-test-${plugin_name}")
+
+  TMPDIR=$tmp_dir run_plugin_script "$plugin_path/bin/list-all" >"$tmp_dir/stdout" 2>"$tmp_dir/stderr" &
+  local pid=$!
+  local waited=0
+  while kill -0 "$pid" 2>/dev/null && [ "$waited" -lt "$((timeout_seconds * 10))" ]; do
+    sleep 0.1
+    waited=$((waited + 1))
+  done
+
+  local problem=""
+  if kill -0 "$pid" 2>/dev/null; then
+    kill_process_tree "$pid"
+    wait "$pid" 2>/dev/null
+    problem="bin/list-all did not finish within $timeout_seconds seconds"
+  elif ! wait "$pid"; then
+    problem="bin/list-all failed: $(head -n 1 "$tmp_dir/stderr")"
+  elif [ -z "$(tr -d '[:space:]' <"$tmp_dir/stdout")" ]; then
+    problem="bin/list-all printed no versions"
+  fi
+
+  rm -rf "$tmp_dir"
+  if [ -n "$problem" ]; then
+    printf "%s\\n" "$problem"
+    return 1
+  fi
+}
+
+# Stops a process along with everything it started, such as a plugin script
+# and the commands that script is running.
+kill_process_tree() {
+  local pid=$1
+  local child
+  for child in $(ps -A -o pid= -o ppid= | awk -v parent="$pid" '$2 == parent { print $1 }'); do
+    kill_process_tree "$child"
+  done
+  kill "$pid" 2>/dev/null
+}
+
 plugin_update_command() {
+  local jobs
+  local local_changes
+  local unpin
+  local positional=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    -j | --jobs)
+      if [ "$#" -lt 2 ]; then
+        display_error "--jobs must be a positive integer"
+        exit 1
+      fi
+      jobs=$2
+      shift 2
+      ;;
+    --jobs=*)
+      jobs=${1#--jobs=}
+      shift
+      ;;
+    --force)
+      local_changes=force
+      shift
+      ;;
+    --stash)
+      local_changes=stash
+      shift
+      ;;
+    --unpin)
+      unpin=yes
+      shift
+      ;;
+    --dry-run)
+      ASDF_DRY_RUN=yes
+      shift
+      ;;
+    --fail-fast)
+      ASDF_FAIL_FAST=yes
+      shift
+      ;;
+    *)
+      positional+=("$1")
+      shift
+      ;;
+    esac
+  done
+
+  set -- "${positional[@]}"
+
   if [ "$#" -lt 1 ]; then
-    display_error "usage: asdf plugin-update {<name> [git-ref] | --all}"
+    display_error "usage: asdf plugin-update {<name> [git-ref] | --all [--jobs <n>] [--fail-fast]} [--force | --stash] [--unpin]"
+    exit 1
+  fi
+
+  if [ -n "$jobs" ] && ! [[ "$jobs" =~ ^[1-9][0-9]*$ ]]; then
+    display_error "--jobs must be a positive integer"
     exit 1
   fi
 
+  require_online "update plugins" || exit 1
+
   local plugin_name="$1"
   local gitref="${2}"
   local plugins=
 
+  # Read by update_plugin, which unpins the plugins it updates
+  local ASDF_PLUGIN_UNPIN=$unpin
+
   if [ "$plugin_name" = "--all" ]; then
     if [ -d "$(asdf_data_dir)"/plugins ]; then
-      plugins=$(find "$(asdf_data_dir)"/plugins -mindepth 1 -maxdepth 1 -type d)
-      while IFS= read -r dir; do
-        update_plugin "$(basename "$dir")" "$dir" "$gitref" &
+      plugins=$(find "$(asdf_data_dir)"/plugins -mindepth 1 -maxdepth 1 -type d | sort)
+      if [ -n "$plugins" ] && asdf_dry_run; then
+        print_plugin_update_plans "$gitref" "$local_changes" <<<"$plugins"
+      elif [ -n "$plugins" ]; then
+        update_all_plugins "${jobs:-$(get_concurrency)}" "$gitref" "$local_changes" <<<"$plugins"
+      fi
+    fi
+  elif is_plugin_pattern "$plugin_name"; then
+    plugins=$(expand_plugin_pattern "$plugin_name") || exit 1
+    plugins=$(
+      while IFS= read -r plugin_name; do
+        get_plugin_path "$plugin_name"
       done <<<"$plugins"
-      wait
+    )
+    if asdf_dry_run; then
+      print_plugin_update_plans "$gitref" "$local_changes" <<<"$plugins"
+    else
+      update_all_plugins "${jobs:-$(get_concurrency)}" "$gitref" "$local_changes" <<<"$plugins"
     fi
   else
     local plugin_path
     plugin_path="$(get_plugin_path "$plugin_name")"
     check_if_plugin_exists "$plugin_name"
-    update_plugin "$plugin_name" "$plugin_path" "$gitref"
+    if asdf_dry_run; then
+      print_plugin_update_plan "$plugin_name" "$plugin_path" "$gitref" "$local_changes"
+    else
+      update_plugin "$plugin_name" "$plugin_path" "$gitref" "$local_changes"
+    fi
+  fi
+}
+
+# Prints the plan of updating each plugin whose path is read from stdin.
+print_plugin_update_plans() {
+  local gitref=$1
+  local local_changes=$2
+  local dir
+  while IFS= read -r dir; do
+    print_plugin_update_plan "$(basename "$dir")" "$dir" "$gitref" "$local_changes"
+  done
+}
+
+# Prints the git changes, hooks and plugin scripts updating a plugin would
+# make and run. Only the remote is queried.
+print_plugin_update_plan() {
+  local plugin_name=$1
+  local plugin_path=$2
+  local gitref=$3
+  local local_changes=$4
+  local common_git_options=(--git-dir "$plugin_path/.git" --work-tree "$plugin_path")
+
+  local pinned_ref
+  pinned_ref=$(get_plugin_pinned_ref "$plugin_path")
+  if [ -z "$gitref" ] && [ -n "$pinned_ref" ] && [ -z "$ASDF_PLUGIN_UNPIN" ]; then
+    gitref=$pinned_ref
   fi
+  if [ -z "$gitref" ]; then
+    gitref=$(plugin_git "${common_git_options[@]}" ls-remote --symref origin HEAD | awk '{ sub(/refs\/heads\//, ""); print $2; exit }')
+    [ -n "$gitref" ] || return 1
+  fi
+
+  local prev_ref post_ref
+  prev_ref=$(git "${common_git_options[@]}" rev-parse --short HEAD) || return 1
+  post_ref=$(plugin_git "${common_git_options[@]}" ls-remote origin "$gitref" | awk '{ print substr($1, 1, 7); exit }')
+
+  asdf_run_hook "pre_asdf_plugin_update" "$plugin_name"
+  asdf_run_hook "pre_asdf_plugin_update_${plugin_name}"
+
+  if [ -n "$(git "${common_git_options[@]}" status --porcelain --untracked-files=no)" ]; then
+    case "$local_changes" in
+    force) dry_run_note "discard local changes to $plugin_path" ;;
+    stash) dry_run_note "stash local changes to $plugin_path and reapply them after updating" ;;
+    *)
+      printf "%s has local changes, use --force to discard them or --stash to reapply them after updating\\n" "$plugin_name"
+      return 1
+      ;;
+    esac
+  fi
+
+  dry_run_note "fetch $gitref from origin into $plugin_path and check it out (${prev_ref} -> ${post_ref:-$gitref})"
+
+  if [ -n "$pinned_ref" ] && [ -n "$ASDF_PLUGIN_UNPIN" ]; then
+    dry_run_note "unpin $plugin_name from $pinned_ref"
+  elif [ -n "$pinned_ref" ] && [ "$gitref" != "$pinned_ref" ]; then
+    dry_run_note "pin $plugin_name to $gitref instead of $pinned_ref"
+  fi
+
+  if [ -f "${plugin_path}/bin/post-plugin-update" ]; then
+    dry_run_note "run ${plugin_path}/bin/post-plugin-update with ASDF_PLUGIN_PATH=$plugin_path ASDF_PLUGIN_PREV_REF=$prev_ref ASDF_PLUGIN_POST_REF=${post_ref:-$gitref}"
+  fi
+
+  asdf_run_hook "post_asdf_plugin_update" "$plugin_name"
+  asdf_run_hook "post_asdf_plugin_update_${plugin_name}"
 }
 
+# Updates the plugins whose paths are read from stdin, running at most the
+# given number of updates at once, then prints a status table. Fails if any
+# update failed. With --fail-fast no more updates start after one fails.
+update_all_plugins() {
+  local jobs=$1
+  local gitref=$2
+  local local_changes=$3
+
+  local ASDF_PLUGIN_UPDATE_STATUS_DIR
+  ASDF_PLUGIN_UPDATE_STATUS_DIR=$(make_tmp_dir "plugin-update-status")
+
+  batch_start
+
+  local pids=()
+  local pid_names=()
+  local dir
+  while IFS= read -r dir; do
+    # Wait for the oldest update when all job slots are taken
+    if [ "${#pids[@]}" -ge "$jobs" ]; then
+      wait "${pids[0]}" || batch_add_failure "${pid_names[0]}"
+      pids=("${pids[@]:1}")
+      pid_names=("${pid_names[@]:1}")
+    fi
+    if batch_should_stop; then
+      printf "skipped\\tan earlier update failed\\n" >"$ASDF_PLUGIN_UPDATE_STATUS_DIR/$(basename "$dir")"
+      continue
+    fi
+    batch_add_item
+    update_plugin "$(basename "$dir")" "$dir" "$gitref" "$local_changes" &
+    pids+=($!)
+    pid_names+=("$(basename "$dir")")
+  done
+
+  local index
+  for index in "${!pids[@]}"; do
+    wait "${pids[$index]}" || batch_add_failure "${pid_names[$index]}"
+  done
+
+  local status_file
+  printf "\n"
+  printf "%-20s%-10s%s\n" "PLUGIN" "STATUS" "DETAIL"
+  for status_file in "$ASDF_PLUGIN_UPDATE_STATUS_DIR"/*; do
+    [ -f "$status_file" ] || continue
+    local plugin_status plugin_detail
+    IFS=$'\t' read -r plugin_status plugin_detail <"$status_file"
+    printf "%-20s%-10s%s\n" "$(basename "$status_file")" "$plugin_status" "$plugin_detail"
+  done
+
+  rm -rf "$ASDF_PLUGIN_UPDATE_STATUS_DIR"
+
+  batch_report "Update" || exit 1
+}
+
+# Updates a plugin to the given ref. Without one a pinned plugin stays on its
+# pinned ref and any other plugin is updated to the default branch of its
+# origin. A pinned plugin updated to another ref is pinned to that ref.
 update_plugin() {
   local plugin_name=$1
   local plugin_path=$2
-  plugin_remote_default_branch=$(git --git-dir "$plugin_path/.git" --work-tree "$plugin_path" ls-remote --symref origin HEAD | awk '{ sub(/refs\/heads\//, ""); print $2; exit }')
-  local gitref=${3:-${plugin_remote_default_branch}}
-  logfile=$(mktemp)
+  local gitref=$3
+  local local_changes=$4
+
+  local pinned_ref
+  pinned_ref=$(get_plugin_pinned_ref "$plugin_path")
+  if [ -n "$ASDF_PLUGIN_UNPIN" ]; then
+    pinned_ref=""
+  fi
+  if [ -z "$gitref" ]; then
+    gitref=$pinned_ref
+  fi
+  if [ -z "$gitref" ]; then
+    gitref=$(plugin_git --git-dir "$plugin_path/.git" --work-tree "$plugin_path" ls-remote --symref origin HEAD | awk '{ sub(/refs\/heads\//, ""); print $2; exit }')
+  fi
+  local tmp_dir
+  tmp_dir=$(make_tmp_dir "plugin-update-${plugin_name}")
+  logfile="$tmp_dir/log"
 
   local common_git_options=(--git-dir "$plugin_path/.git" --work-tree "$plugin_path")
   local prev_ref=
   local post_ref=
+  local update_failed=
   {
     asdf_run_hook "pre_asdf_plugin_update" "$plugin_name"
     asdf_run_hook "pre_asdf_plugin_update_${plugin_name}"
 
     printf "Updating %s to %s\\n" "$plugin_name" "$gitref"
 
-    git "${common_git_options[@]}" fetch --prune --update-head-ok origin "$gitref:$gitref"
     prev_ref=$(git "${common_git_options[@]}" rev-parse --short HEAD)
-    post_ref=$(git "${common_git_options[@]}" rev-parse --short "${gitref}")
-    git "${common_git_options[@]}" -c advice.detachedHead=false checkout --force "$gitref"
 
-    if [ -f "${plugin_path}/bin/post-plugin-update" ]; then
-      (
-        export ASDF_PLUGIN_PATH=$plugin_path
-        export ASDF_PLUGIN_PREV_REF=$prev_ref
-        export ASDF_PLUGIN_POST_REF=$post_ref
-        "${plugin_path}/bin/post-plugin-update"
-      )
+    # Check before fetching, as fetching into the checked out branch makes an
+    # unchanged working tree look modified
+    local stashed=
+    if [ -n "$(git "${common_git_options[@]}" status --porcelain --untracked-files=no)" ]; then
+      case "$local_changes" in
+      force)
+        printf "Discarding local changes to %s\\n" "$plugin_name"
+        ;;
+      stash)
+        printf "Stashing local changes to %s\\n" "$plugin_name"
+        if plugin_git_stash "$plugin_path" push --quiet --message "asdf plugin update"; then
+          stashed=yes
+        else
+          update_failed=yes
+        fi
+        ;;
+      *)
+        printf "%s has local changes, use --force to discard them or --stash to reapply them after updating\\n" "$plugin_name"
+        update_failed=yes
+        ;;
+      esac
     fi
 
-    asdf_run_hook "post_asdf_plugin_update" "$plugin_name"
-    asdf_run_hook "post_asdf_plugin_update_${plugin_name}"
+    if [ -z "$update_failed" ] &&
+      fetch_plugin_ref "$plugin_path" "$gitref" &&
+      post_ref=$(git "${common_git_options[@]}" rev-parse --short "${gitref}") &&
+      git "${common_git_options[@]}" -c advice.detachedHead=false checkout --force "$gitref"; then
+
+      if [ -n "$pinned_ref" ] && [ "$gitref" != "$pinned_ref" ]; then
+        printf "Pinning %s to %s\\n" "$plugin_name" "$gitref"
+        set_plugin_pinned_ref "$plugin_path" "$gitref"
+      elif [ -n "$ASDF_PLUGIN_UNPIN" ] && [ -n "$(get_plugin_pinned_ref "$plugin_path")" ]; then
+        printf "Unpinning %s\\n" "$plugin_name"
+        set_plugin_pinned_ref "$plugin_path" ""
+      fi
+
+      # A new plugin version may list versions differently
+      clear_list_all_cache "$plugin_name"
+
+      if [ -n "$stashed" ] && ! plugin_git_stash "$plugin_path" pop --quiet; then
+        # Leave the plugin at the updated ref, the changes stay in the stash
+        git "${common_git_options[@]}" reset --quiet --hard
+        printf "Could not reapply local changes to %s, they are kept in git stash\\n" "$plugin_name"
+        update_failed=yes
+      fi
+
+      if [ -f "${plugin_path}/bin/post-plugin-update" ]; then
+        (
+          export ASDF_PLUGIN_PATH=$plugin_path
+          export ASDF_PLUGIN_PREV_REF=$prev_ref
+          export ASDF_PLUGIN_POST_REF=$post_ref
+          export TMPDIR=$tmp_dir
+          run_plugin_script "${plugin_path}/bin/post-plugin-update"
+        )
+      fi
+
+      asdf_run_hook "post_asdf_plugin_update" "$plugin_name"
+      asdf_run_hook "post_asdf_plugin_update_${plugin_name}"
+    else
+      if [ -n "$stashed" ]; then
+        plugin_git_stash "$plugin_path" pop --quiet
+      fi
+      update_failed=yes
+    fi
   } >"$logfile" 2>&1
   cat "$logfile"
-  rm "$logfile"
+
+  if [ -n "$ASDF_PLUGIN_UPDATE_STATUS_DIR" ]; then
+    local status_file="$ASDF_PLUGIN_UPDATE_STATUS_DIR/$plugin_name"
+    if [ -n "$update_failed" ]; then
+      printf "failed\\t%s\\n" "$(grep -v "^Updating " "$logfile" | tail -1)" >"$status_file"
+    elif [ "$prev_ref" = "$post_ref" ]; then
+      printf "current\\t%s\\n" "$post_ref" >"$status_file"
+    else
+      printf "updated\\t%s -> %s\\n" "$prev_ref" "$post_ref" >"$status_file"
+    fi
+  fi
+
+  rm -rf "$tmp_dir"
+
+  if [ -n "$update_failed" ]; then
+    return 1
+  fi
+}
+
+# Fetches a branch or tag from the origin of a plugin. A tag is fetched as a
+# tag, so that it is checked out as one rather than as a branch of the same
+# name. A commit the plugin already has is not fetched, as a commit can't be
+# fetched by an abbreviated hash and never changes.
+fetch_plugin_ref() {
+  local plugin_path=$1
+  local gitref=$2
+  local common_git_options=(--git-dir "$plugin_path/.git" --work-tree "$plugin_path")
+
+  if git "${common_git_options[@]}" show-ref --quiet --verify "refs/remotes/origin/$gitref"; then
+    :
+  elif git "${common_git_options[@]}" show-ref --quiet --verify "refs/tags/$gitref" ||
+    [ -n "$(plugin_git "${common_git_options[@]}" ls-remote --tags origin "refs/tags/$gitref")" ]; then
+    plugin_git "${common_git_options[@]}" fetch --force origin "refs/tags/$gitref:refs/tags/$gitref"
+    return
+  elif [[ "$gitref" =~ ^[0-9a-f]{7,40}$ ]] &&
+    git "${common_git_options[@]}" rev-parse --quiet --verify "$gitref^{commit}" >/dev/null; then
+    return 0
+  fi
+
+  plugin_git "${common_git_options[@]}" fetch --prune --update-head-ok origin "$gitref:$gitref"
+}
+
+# Runs git stash in a plugin. Stashing creates commits, so an identity is
+# provided when git has none configured.
+plugin_git_stash() {
+  local plugin_path=$1
+  shift
+
+  local git_options=(-C "$plugin_path")
+  if ! git "${git_options[@]}" config user.email >/dev/null; then
+    git_options+=(-c "user.name=asdf" -c "user.email=asdf@localhost")
+  fi
+
+  git "${git_options[@]}" stash "$@"
 }
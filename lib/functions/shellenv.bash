@@ -0,0 +1,80 @@
+# Shell code that sets up asdf, printed by `asdf shellenv` and `asdf init`.
+
+# Prints the normalized name of a shell, "sh" for the POSIX shells, or
+# returns 1 for a shell asdf can't set up.
+shellenv_shell_name() {
+  case "$1" in
+  bash | zsh | sh | ksh | dash)
+    printf "sh\\n"
+    ;;
+  fish | nu | pwsh)
+    printf "%s\\n" "$1"
+    ;;
+  nushell)
+    printf "nu\\n"
+    ;;
+  powershell)
+    printf "pwsh\\n"
+    ;;
+  *)
+    return 1
+    ;;
+  esac
+}
+
+# Prints a value quoted for the given shell, so the shell reads it back
+# unchanged.
+shellenv_quote() {
+  local asdf_shell=$1 value=$2
+
+  case "$asdf_shell" in
+  fish)
+    value=${value//\\/\\\\}
+    printf "'%s'" "${value//\'/\\\'}"
+    ;;
+  nu)
+    value=${value//\\/\\\\}
+    printf '"%s"' "${value//\"/\\\"}"
+    ;;
+  pwsh)
+    printf "'%s'" "${value//\'/\'\'}"
+    ;;
+  *)
+    printf "'%s'" "${value//\'/\'\\\'\'}"
+    ;;
+  esac
+}
+
+# Prints the code that exports ASDF_DIR and ASDF_DATA_DIR, puts asdf on PATH
+# and, in the shells asdf has a wrapper for, loads the asdf function that
+# `asdf shell` needs. The asdf directories are added to the PATH the shell
+# has when it runs the code, and only once.
+print_shellenv() {
+  local asdf_shell=$1
+
+  case "$asdf_shell" in
+  fish)
+    printf "set -gx ASDF_DIR %s\\n" "$(shellenv_quote fish "$(asdf_dir)")"
+    printf "set -gx ASDF_DATA_DIR %s\\n" "$(shellenv_quote fish "$(asdf_data_dir)")"
+    printf "fish_add_path --global --move --path \"\$ASDF_DATA_DIR/shims\" \"\$ASDF_DIR/bin\"\\n"
+    printf "source \"\$ASDF_DIR/lib/asdf.fish\"\\n"
+    ;;
+  nu)
+    printf "\$env.ASDF_DIR = %s\\n" "$(shellenv_quote nu "$(asdf_dir)")"
+    printf "\$env.ASDF_DATA_DIR = %s\\n" "$(shellenv_quote nu "$(asdf_data_dir)")"
+    printf "\$env.PATH = (\$env.PATH | split row (char esep) | prepend [(\$env.ASDF_DATA_DIR | path join shims) (\$env.ASDF_DIR | path join bin)] | uniq)\\n"
+    ;;
+  pwsh)
+    printf "\$env:ASDF_DIR = %s\\n" "$(shellenv_quote pwsh "$(asdf_dir)")"
+    printf "\$env:ASDF_DATA_DIR = %s\\n" "$(shellenv_quote pwsh "$(asdf_data_dir)")"
+    printf "\$env:PATH = (@(\"\$env:ASDF_DATA_DIR/shims\", \"\$env:ASDF_DIR/bin\") + (\$env:PATH -split [IO.Path]::PathSeparator) | Select-Object -Unique) -join [IO.Path]::PathSeparator\\n"
+    ;;
+  *)
+    printf "export ASDF_DIR=%s\\n" "$(shellenv_quote sh "$(asdf_dir)")"
+    printf "export ASDF_DATA_DIR=%s\\n" "$(shellenv_quote sh "$(asdf_data_dir)")"
+    printf "case \":\$PATH:\" in *\":\$ASDF_DIR/bin:\"*) ;; *) export PATH=\"\$ASDF_DIR/bin:\$PATH\" ;; esac\\n"
+    printf "case \":\$PATH:\" in *\":\$ASDF_DATA_DIR/shims:\"*) ;; *) export PATH=\"\$ASDF_DATA_DIR/shims:\$PATH\" ;; esac\\n"
+    printf ". \"\$ASDF_DIR/lib/asdf.sh\"\\n"
+    ;;
+  esac
+}
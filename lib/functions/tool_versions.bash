@@ -0,0 +1,246 @@
+# Checks of the format of version files. `asdf check` reports the problems
+# and fixes them with --fix, `asdf local` and `asdf global` warn about them
+# before writing to a file and `asdf doctor` lists them with its other checks.
+
+# Goes through a version file in one of four modes:
+#   report  prints each problem with its line and column, the content of the
+#           line and, when there is one, the line as it was probably meant
+#   doctor  prints a "<problem>\t<fix>" line for each problem
+#   fix     rewrites the file with the problems that can be fixed fixed. Lines
+#           are stripped of carriage returns and extra whitespace and the
+#           versions of a tool set on several lines are merged into its first
+#           line. Comments and blank lines are kept.
+#   entries prints a "<line>\t<column>\t<name>\t<version>" line for each
+#           version of the lines asdf reads, and one with an empty version
+#           for the name
+# The report and doctor modes return 1 when they found a problem.
+lint_tool_versions_file() {
+  local mode=$1
+  local file=$2
+
+  # shellcheck disable=SC2016
+  local program='
+    function problem(at, message, suggestion, fixable) {
+      found = 1
+      if (mode == "report") {
+        printf "%s:%d:%d: %s\n    %s\n", file, NR, at, message, content
+        if (suggestion != "") printf "    did you mean `%s`?\n", suggestion
+      } else if (mode == "doctor") {
+        printf "%s:%d:%d: %s\t%s\n", file, NR, at, message, (fixable ? "run `asdf check --fix " file "`" : "edit line " NR " of " file)
+      }
+    }
+
+    {
+      content = $0
+      carriage_return = sub(/\r$/, "", content)
+      lines[NR] = content
+      kind[NR] = "raw"
+
+      body = content
+      comment = ""
+      if (match(body, /[ \t]*#/)) {
+        comment = substr(body, RSTART)
+        body = substr(body, 1, RSTART - 1)
+      }
+
+      if (body ~ /^[ \t]*$/) {
+        if (carriage_return) problem(length(content) + 1, "the line ends with a carriage return", "", 1)
+        next
+      }
+
+      # Split the line into tokens, keeping where each starts and
+      # the whitespace before it
+      indent = match(body, /[^ \t]/) - 1
+      rest = substr(body, indent + 1)
+      offset = indent + 1
+      count = 0
+      while (match(rest, /[^ \t]+/)) {
+        count++
+        tokens[count] = substr(rest, RSTART, RLENGTH)
+        starts[count] = offset + RSTART - 1
+        separators[count] = substr(rest, 1, RSTART - 1)
+        offset += RSTART + RLENGTH - 1
+        rest = substr(rest, RSTART + RLENGTH)
+      }
+
+      name = tokens[1]
+      joined = 0
+      versions = ""
+      split("", listed)
+      repeated_at = 0
+      if (match(name, /[=@]/) && RSTART > 1 && RSTART < length(name)) {
+        joined = starts[1] + RSTART - 1
+        version = substr(name, RSTART + 1)
+        name = substr(name, 1, RSTART - 1)
+        versions = " " version
+        listed[version] = 1
+      }
+      for (i = 2; i <= count; i++) {
+        if (tokens[i] in listed) {
+          if (!repeated_at) {
+            repeated_at = starts[i]
+            repeated = tokens[i]
+          }
+          continue
+        }
+        listed[tokens[i]] = 1
+        versions = versions " " tokens[i]
+      }
+      suggestion = name versions comment
+
+      if (indent > 0) problem(1, "the line is indented, so it is not read", suggestion, 1)
+      if (joined) problem(joined, "expected a space between " name " and its version", suggestion, 1)
+      if (name !~ /^[A-Za-z0-9_-]+$/) {
+        problem(starts[1], name " is not a valid tool name", "", 0)
+        next
+      }
+      if (versions == "") problem(starts[1] + length(name), "no version is set for " name, "", 0)
+      if (!joined && count > 1 && separators[2] ~ /^\t/) problem(starts[2] - length(separators[2]), "a tab follows " name ", so the line is not read", suggestion, 1)
+      if (repeated_at) problem(repeated_at, repeated " is listed twice for " name, suggestion, 1)
+      if (carriage_return) problem(length(content) + 1, "the line ends with a carriage return, which is read as part of the last version", suggestion, 1)
+
+      if (mode == "entries" && indent == 0 && !joined && !(count > 1 && separators[2] ~ /^\t/)) {
+        printf "%d\t%d\t%s\t\n", NR, starts[1], name
+        for (i = 2; i <= count; i++) printf "%d\t%d\t%s\t%s\n", NR, starts[i], name, tokens[i]
+      }
+
+      if (name in first_lines) {
+        first = first_lines[name]
+        problem(starts[1], name " is already set on line " first, "", 1)
+        merged_count = split(versions, merged, " ")
+        for (i = 1; i <= merged_count; i++) {
+          if (index(entry_versions[first] " ", " " merged[i] " ") == 0) entry_versions[first] = entry_versions[first] " " merged[i]
+        }
+        kind[NR] = "merged"
+        sub(/^[ \t]+/, "", comment)
+        merged_comments[NR] = comment
+        next
+      }
+
+      first_lines[name] = NR
+      kind[NR] = "entry"
+      entry_names[NR] = name
+      entry_versions[NR] = versions
+      entry_comments[NR] = comment
+    }
+
+    END {
+      if (mode == "entries") exit 0
+      if (mode != "fix") exit found
+
+      for (i = 1; i <= NR; i++) {
+        if (kind[i] == "entry") {
+          print entry_names[i] entry_versions[i] entry_comments[i]
+        } else if (kind[i] == "merged") {
+          if (merged_comments[i] != "") print merged_comments[i]
+        } else {
+          print lines[i]
+        }
+      }
+    }
+  '
+
+  if [ "$mode" = "fix" ]; then
+    rewrite_tool_versions_file "$file" -v mode="$mode" -v file="$file" "$program"
+  else
+    awk -v mode="$mode" -v file="$file" "$program" "$file"
+  fi
+}
+
+# Fixes what can be fixed in a version file and says so when it changed.
+fix_tool_versions_file() {
+  local file=$1
+
+  local before
+  before=$(cksum <"$file")
+  lint_tool_versions_file fix "$file" || return 1
+
+  if [ "$(cksum <"$file")" != "$before" ]; then
+    printf "Fixed %s\\n" "$file"
+  fi
+}
+
+# Warns about the problems of a version file, as `asdf local` and `asdf global`
+# do before writing to it.
+warn_tool_versions_problems() {
+  local file=$1
+
+  local problems
+  if problems=$(lint_tool_versions_file doctor "$file"); then
+    return 0
+  fi
+
+  local problem
+  while IFS=$'\t' read -r problem _; do
+    asdf_warn "$problem"
+  done <<<"$problems"
+  asdf_warn "run \`asdf check $file\` for details, or \`asdf check --fix $file\` to fix them"
+}
+
+# Reports the tools of a version file whose plugin or version is not
+# installed, in the format of the report mode of lint_tool_versions_file.
+# Returns 1 when it found one.
+check_tool_versions_installed() {
+  local file=$1
+
+  local line at name version found=0 missing_plugins=" "
+  while IFS=$'\t' read -r line at name version; do
+    [ -n "$line" ] || continue
+
+    if [ -z "$version" ]; then
+      if [ ! -d "$(get_plugin_path "$name")" ]; then
+        printf "%s:%d:%d: %s is not an installed plugin\\n    %s\\n" "$file" "$line" "$at" "$name" "$(sed -n "${line}p" "$file")"
+        missing_plugins+="$name "
+        found=1
+      fi
+    elif [[ "$missing_plugins" != *" $name "* ]] && ! tool_version_installed "$name" "$version"; then
+      printf "%s:%d:%d: %s %s is not installed\\n    %s\\n" "$file" "$line" "$at" "$name" "$version" "$(sed -n "${line}p" "$file")"
+      found=1
+    fi
+  done <<<"$(lint_tool_versions_file entries "$file")"
+
+  return "$found"
+}
+
+# Reports the versions set by legacy version files, for the current directory,
+# that are not installed. Returns 1 when it found one.
+check_legacy_versions_installed() {
+  local plugin_name version_and_path version_file version found=0
+
+  for plugin_name in $(list_installed_plugins); do
+    version_and_path=$(find_versions "$plugin_name" "$PWD")
+    version_file=${version_and_path#*|}
+    if [ -z "$version_and_path" ] || [ ! -f "$version_file" ] ||
+      [[ " $(plugin_legacy_filenames "$plugin_name") " != *" $(basename "$version_file") "* ]]; then
+      continue
+    fi
+
+    for version in ${version_and_path%%|*}; do
+      if ! tool_version_installed "$plugin_name" "$version"; then
+        printf "%s: %s %s is not installed\\n" "$version_file" "$plugin_name" "$version"
+        found=1
+      fi
+    done
+  done
+
+  return "$found"
+}
+
+# Returns 0 when a version of a tool, as read from a version file, stands for
+# an installed version.
+tool_version_installed() {
+  local plugin_name=$1
+  local version=$2
+
+  case $version in
+  system)
+    return 0
+    ;;
+  path:*)
+    [ -d "${version#path:}" ]
+    ;;
+  *)
+    [ -n "$(installed_versions_for "$plugin_name" "$version")" ]
+    ;;
+  esac
+}
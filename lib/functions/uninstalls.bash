@@ -0,0 +1,162 @@
+# Uninstalling versions, shared by `asdf uninstall` and `asdf prune`. Needs
+# lib/functions/versions.bash and lib/commands/reshim.bash.
+
+# Prints the installed versions of a plugin that the given versions, as read
+# from a version file, stand for, one per line. latest, latest:<version> and
+# version constraints stand for the newest matching installed version.
+installed_versions_for() {
+  local plugin_name=$1
+  shift
+
+  local version
+  for version in "$@"; do
+    case $version in
+    system | path:*) ;;
+    latest)
+      (latest_command "$plugin_name" --installed-only 2>/dev/null)
+      ;;
+    latest:*)
+      (latest_command "$plugin_name" "${version#latest:}" --installed-only 2>/dev/null)
+      ;;
+    *)
+      if is_version_constraint "$version"; then
+        find_installed_version_for_constraint "$plugin_name" "$version"
+      elif [ -d "$(find_install_path "$plugin_name" "$version")" ]; then
+        printf "%s\\n" "$version"
+      fi
+      ;;
+    esac
+  done | awk '!seen[$0]++'
+}
+
+# Uninstalls several versions of a plugin as items of the current batch,
+# going on after a failure unless --fail-fast is given. The shims are cleaned
+# up once all the versions are uninstalled rather than after each of them.
+uninstall_tool_versions() {
+  local plugin_name=$1
+  shift
+
+  local ASDF_UNINSTALL_KEEP_SHIMS=yes
+  local version
+  for version in "$@"; do
+    batch_run "$plugin_name $version" uninstall_tool_version "$plugin_name" "$version"
+    batch_should_stop && break
+  done
+
+  # A dry run removes nothing, so its plan covers every version
+  local uninstalled=()
+  for version in "$@"; do
+    if asdf_dry_run || [ ! -d "$(find_install_path "$plugin_name" "$version")" ]; then
+      uninstalled+=("$version")
+    fi
+  done
+  remove_shims_for_versions "$plugin_name" "${uninstalled[@]}"
+}
+
+uninstall_tool_version() {
+  local plugin_name=$1
+  local full_version=$2
+  local plugin_path
+  plugin_path=$(get_plugin_path "$plugin_name")
+
+  check_if_plugin_exists "$plugin_name"
+
+  IFS=':' read -r -a version_info <<<"$full_version"
+  if [ "${version_info[0]}" = "ref" ]; then
+    local install_type="${version_info[0]}"
+    local version="${version_info[1]}"
+  else
+    local install_type="version"
+    local version="${version_info[0]}"
+  fi
+
+  local install_path
+  install_path=$(get_install_path "$plugin_name" "$install_type" "$version")
+
+  if [ ! -d "$install_path" ]; then
+    display_error "No such version"
+    exit 1
+  fi
+
+  asdf_run_hook "pre_asdf_uninstall_${plugin_name}" "$full_version"
+  if [ -z "$ASDF_UNINSTALL_KEEP_SHIMS" ]; then
+    remove_shims_for_version "$plugin_name" "$full_version"
+  fi
+
+  local receipt_path
+  receipt_path=$(get_receipt_path "$plugin_name" "$install_type" "$version")
+
+  if asdf_dry_run; then
+    if [ -f "${plugin_path}/bin/uninstall" ]; then
+      dry_run_note "run ${plugin_path}/bin/uninstall with ASDF_INSTALL_TYPE=$install_type ASDF_INSTALL_VERSION=$version ASDF_INSTALL_PATH=$install_path"
+    else
+      dry_run_note "remove $install_path"
+    fi
+    [ ! -f "$receipt_path" ] || dry_run_note "remove $receipt_path"
+  elif [ -f "${plugin_path}/bin/uninstall" ]; then
+    (
+      export ASDF_INSTALL_TYPE=$install_type
+      export ASDF_INSTALL_VERSION=$version
+      export ASDF_INSTALL_PATH=$install_path
+      run_plugin_script "${plugin_path}/bin/uninstall"
+    ) || exit 1
+  else
+    rm -rf "$install_path"
+  fi
+
+  if ! asdf_dry_run; then
+    rm -f "$receipt_path" "$(asdf_data_dir)/reshim-stamps/$plugin_name/$full_version"
+  fi
+
+  asdf_run_hook "post_asdf_uninstall_${plugin_name}" "$full_version"
+}
+
+remove_shims_for_version() {
+  local plugin_name=$1
+  local full_version=$2
+  local shim_path
+  while IFS= read -r shim_path; do
+    remove_shim_for_version "$plugin_name" "$full_version" "$shim_path"
+  done <<<"$(plugin_shims "$plugin_name" "$full_version")"
+  update_shim_index
+}
+
+# Removes several versions of a plugin from its shims in one pass over the
+# shims, removing the shims no version is left in, and updates the shim index
+# once.
+remove_shims_for_versions() {
+  local plugin_name=$1
+  shift
+  [ "$#" -gt 0 ] || return 0
+
+  local shims_path
+  shims_path="$(asdf_data_dir)/shims"
+
+  local shim_name shim_path remaining
+  while IFS= read -r shim_name; do
+    [ -n "$shim_name" ] || continue
+    shim_path="$shims_path/$shim_name"
+    remaining=$(awk -v plugin="$plugin_name" -v versions="$*" '
+      BEGIN { count = split(versions, list, " "); for (i = 1; i <= count; i++) removed[list[i]] = 1 }
+      $1 == "#" && $2 == "asdf-plugin:" && !($3 == plugin && $4 in removed) { print }
+    ' "$shim_path")
+
+    if asdf_dry_run; then
+      if [ -z "$remaining" ]; then
+        dry_run_note "remove $shim_path"
+      else
+        dry_run_note "remove $plugin_name $* from $shim_path"
+      fi
+    elif [ -z "$remaining" ]; then
+      rm -f "$shim_path"
+    else
+      awk -v plugin="$plugin_name" -v versions="$*" '
+        BEGIN { count = split(versions, list, " "); for (i = 1; i <= count; i++) removed[list[i]] = 1 }
+        !($1 == "#" && $2 == "asdf-plugin:" && $3 == plugin && $4 in removed) { print }
+      ' "$shim_path" >"$shim_path.tmp" && cat "$shim_path.tmp" >"$shim_path"
+      rm -f "$shim_path.tmp"
+    fi
+  done <<<"$(shim_index | awk -v plugin="$plugin_name" '$2 == plugin && !seen[$1]++ { print $1 }')"
+
+  update_shim_index
+}
@@ -1,7 +1,35 @@
 version_command() {
   local cmd=$1
+  local pin=false
+  local legacy=false
+  local positional=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    --pin)
+      pin=true
+      shift
+      ;;
+    --legacy)
+      legacy=true
+      shift
+      ;;
+    *)
+      positional+=("$1")
+      shift
+      ;;
+    esac
+  done
+
+  set -- "${positional[@]}"
+
   local plugin_name=$2
 
+  if [ "$pin" = true ] && [ "$#" -le 2 ]; then
+    pin_version_file "$(version_command_file "$cmd")" "$plugin_name"
+    return
+  fi
+
   if [ "$#" -lt "3" ]; then
     if [ "$cmd" = "global" ]; then
       printf "Usage: asdf global <name> <version>\\n"
@@ -14,26 +42,16 @@ version_command() {
   shift 2
   local versions=("$@")
 
-  local file_name
   local file
+  file=$(version_command_file "$cmd")
 
-  file_name="$(version_file_name)"
-
-  if [ "$cmd" = "global" ]; then
-    file="$HOME/$file_name"
-  elif [ "$cmd" = "local-tree" ]; then
-    file=$(find_tool_versions)
-  else # cmd = local
-    file="$(pwd)/$file_name"
-  fi
+  check_if_plugin_exists "$plugin_name"
 
-  if [ -L "$file" ]; then
-    # Resolve file path if symlink
-    file="$(resolve_symlink "$file")"
+  local legacy_file=""
+  if [ "$legacy" = true ]; then
+    legacy_file=$(legacy_version_file_for "$plugin_name" "$(dirname "$file")") || exit 1
   fi
 
-  check_if_plugin_exists "$plugin_name"
-
   declare -a resolved_versions
   local item
   for item in "${!versions[@]}"; do
@@ -60,19 +78,211 @@ version_command() {
     resolved_versions+=("$version")
   done
 
-  if [ -f "$file" ] && grep "^$plugin_name " "$file" >/dev/null; then
-    sed -i.bak -e "s|^$plugin_name .*$|$plugin_name ${resolved_versions[*]}|" "$file"
-    rm -f "$file".bak
+  if [ -f "$file" ]; then
+    warn_tool_versions_problems "$file"
+  fi
+
+  set_tool_versions "$file" "$plugin_name" "${resolved_versions[@]}"
+
+  if [ -n "$legacy_file" ]; then
+    write_legacy_version_file "$plugin_name" "$legacy_file" "${resolved_versions[0]}"
+  fi
+}
+
+# Prints the path of the legacy version file of a plugin in a directory, named
+# after the first of its legacy version files, or fails with an error when
+# legacy version files are off or the plugin has none.
+legacy_version_file_for() {
+  local plugin_name=$1
+  local dir=$2
+
+  if [ "$(get_asdf_config_value "legacy_version_file")" != "yes" ]; then
+    display_error "--legacy needs legacy_version_file = yes in your .asdfrc"
+    return 1
+  fi
+
+  local legacy_filenames=()
+  IFS=' ' read -r -a legacy_filenames <<<"$(plugin_legacy_filenames "$plugin_name")"
+
+  if [ "${#legacy_filenames[@]}" -eq 0 ]; then
+    display_error "The $plugin_name plugin has no legacy version file"
+    return 1
+  fi
+
+  printf "%s\\n" "$dir/${legacy_filenames[0]}"
+}
+
+# Writes a version to a legacy version file with the plugin's
+# bin/write-legacy-file, or as the only line of the file when the plugin has
+# no such script.
+write_legacy_version_file() {
+  local plugin_name=$1
+  local file=$2
+  local version=$3
+
+  local write_script
+  write_script="$(get_plugin_path "$plugin_name")/bin/write-legacy-file"
+  if [ -f "$write_script" ]; then
+    run_with_plugin_env_filter "$write_script" "$file" "$version"
+  elif [ "$(basename "$file")" = "rust-toolchain.toml" ]; then
+    printf "[toolchain]\\nchannel = \"%s\"\\n" "$version" >"$file"
   else
-    # Add a trailing newline at the end of the file if missing
-    [[ -n "$(tail -c1 "$file")" && -f "$file" ]] && printf '\n' >>"$file"
+    printf "%s\\n" "$version" >"$file"
+  fi
+}
+
+# Prints the concrete version a latest or latest:<version> version resolves
+# to, and any other version unchanged.
+resolve_latest_version() {
+  local plugin_name=$1
+  local version=$2
+
+  case "$version" in
+  latest | latest:*) ;;
+  *)
+    printf "%s\\n" "$version"
+    return 0
+    ;;
+  esac
+
+  local query=${version#latest}
+  local resolved
+  resolved=$(latest_command "$plugin_name" "${query#:}")
+  [ -n "$resolved" ] || return 1
+
+  printf "%s\\n" "$resolved"
+}
+
+# Prints the newest version available for a plugin that satisfies a
+# constraint, or fails with an error when there is none.
+resolve_version_constraint() {
+  local plugin_name=$1
+  local constraint=$2
+
+  local versions version
+  versions=$(list_all_command "$plugin_name") || return 1
+  version=$(filter_versions_by_constraint "$constraint" <<<"$versions" | sort_versions | tail -n 1)
+
+  if [ -z "$version" ]; then
+    display_error "No version of $plugin_name satisfies $constraint"
+    return 1
+  fi
+  printf "%s\\n" "$version"
+}
+
+# Writes the concrete version in place of a latest[:<version>] version in the
+# version file it was read from. Versions set some other way, such as in an
+# environment variable or a legacy version file, are left alone with a warning.
+pin_tool_version() {
+  local version_file=$1
+  local plugin_name=$2
+  local version=$3
+  local resolved=$4
+
+  if [ "$version" = "$resolved" ]; then
+    return 0
+  fi
+
+  if [ ! -f "$version_file" ] || ! [[ " $(version_file_names) " == *" $(basename "$version_file") "* ]]; then
+    asdf_warn "Not pinning $plugin_name $version, it is set by $version_file"
+    return 0
+  fi
+
+  if asdf_dry_run; then
+    dry_run_note "pin $plugin_name $version to $resolved in $version_file"
+    return 0
+  fi
+
+  replace_tool_version "$version_file" "$plugin_name" "$version" "$resolved"
+  printf "Pinned %s %s to %s in %s\\n" "$plugin_name" "$version" "$resolved" "$version_file"
+}
+
+# Pins the latest[:<version>] versions of a version file, or only those of the
+# given plugin, to the latest installed version they resolve to.
+pin_version_file() {
+  local file=$1
+  local only_plugin=$2
+
+  if [ ! -f "$file" ]; then
+    display_error "No version file at $file"
+    exit 1
+  fi
+
+  local plugin_name versions version resolved
+  local pinned=false
+  while read -r plugin_name versions; do
+    if [ -z "$plugin_name" ] || { [ -n "$only_plugin" ] && [ "$plugin_name" != "$only_plugin" ]; }; then
+      continue
+    fi
+
+    for version in $versions; do
+      resolved=$(resolve_latest_version "$plugin_name" "$version") || exit 1
+      [ "$version" != "$resolved" ] || continue
+
+      if ! (check_if_version_exists "$plugin_name" "$resolved"); then
+        version_not_installed_text "$plugin_name" "$resolved" 1>&2
+        exit 1
+      fi
+
+      pin_tool_version "$file" "$plugin_name" "$version" "$resolved"
+      pinned=true
+    done
+  done <<<"$(strip_tool_version_comments "$file")"
 
-    # Add a new version line to the end of the file
-    printf "%s %s\\n" "$plugin_name" "${resolved_versions[*]}" >>"$file"
+  if [ "$pinned" = false ]; then
+    printf "No latest versions to pin in %s\\n" "$file"
   fi
 }
 
+# Prints the version file the global, local or local-tree command writes to.
+# That is the first version file already in the directory, if any.
+version_command_file() {
+  local cmd=$1
+  local file_name
+  local file
+
+  file_name="$(version_file_name)"
+
+  if [ "$cmd" = "global" ]; then
+    file=$(version_file_in_dir "$HOME") || file="$HOME/$file_name"
+  elif [ "$cmd" = "local-tree" ]; then
+    file=$(find_tool_versions)
+  else # cmd = local
+    file=$(version_file_in_dir "$(pwd)") || file="$(pwd)/$file_name"
+  fi
+
+  if [ -L "$file" ]; then
+    # Resolve file path if symlink
+    file="$(resolve_symlink "$file")"
+  fi
+
+  printf "%s\\n" "$file"
+}
+
 list_all_command() {
+  local json=false
+  local refresh=false
+  local positional=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    --json)
+      json=true
+      shift
+      ;;
+    --refresh)
+      refresh=true
+      shift
+      ;;
+    *)
+      positional+=("$1")
+      shift
+      ;;
+    esac
+  done
+
+  set -- "${positional[@]}"
+
   local plugin_name=$1
   local query=$2
   local plugin_path
@@ -82,21 +292,48 @@ list_all_command() {
   plugin_path=$(get_plugin_path "$plugin_name")
   check_if_plugin_exists "$plugin_name"
 
-  local temp_dir
-  temp_dir=${TMPDIR:-/tmp}
+  local cache_file
+  cache_file=$(get_list_all_cache_path "$plugin_name")
+
+  # Offline, the cached versions are used however old they are
+  if asdf_offline; then
+    if [ "$refresh" = true ]; then
+      require_online "refresh the versions of $plugin_name"
+      exit 1
+    elif [ ! -f "$cache_file" ]; then
+      display_error "No cached versions of $plugin_name to list while offline (--offline or ASDF_OFFLINE is set)"
+      exit 1
+    fi
+  fi
+
+  local tmp_dir
+  tmp_dir=$(make_tmp_dir "list-all-${plugin_name}")
 
   # Capture return code to allow error handling
-  std_out_file="$(mktemp "$temp_dir/asdf-command-list-all-${plugin_name}.stdout.XXXXXX")"
-  std_err_file="$(mktemp "$temp_dir/asdf-command-list-all-${plugin_name}.stderr.XXXXXX")"
-  return_code=0 && "${plugin_path}/bin/list-all" >"$std_out_file" 2>"$std_err_file" || return_code=$?
-
-  if [[ $return_code -ne 0 ]]; then
-    # Printing all output to allow plugin to handle error formatting
-    printf "Plugin %s's list-all callback script failed with output:\\n" "${plugin_name}" >&2
-    printf "%s\\n" "$(cat "$std_err_file")" >&2
-    printf "%s\\n" "$(cat "$std_out_file")" >&2
-    rm "$std_out_file" "$std_err_file"
-    exit 1
+  std_out_file="$tmp_dir/stdout"
+  std_err_file="$tmp_dir/stderr"
+
+  if asdf_offline || { [ "$refresh" = false ] && list_all_cache_is_fresh "$cache_file"; }; then
+    asdf_debug "reading the $plugin_name versions from $cache_file"
+    cp "$cache_file" "$std_out_file"
+  else
+    return_code=0 && TMPDIR=$tmp_dir run_plugin_script "${plugin_path}/bin/list-all" >"$std_out_file" 2>"$std_err_file" || return_code=$?
+
+    if [[ $return_code -ne 0 ]]; then
+      # Printing all output to allow plugin to handle error formatting
+      printf "Plugin %s's list-all callback script failed with output:\\n" "${plugin_name}" >&2
+      printf "%s\\n" "$(cat "$std_err_file")" >&2
+      printf "%s\\n" "$(cat "$std_out_file")" >&2
+      rm -rf "$tmp_dir"
+      exit 1
+    fi
+
+    # Written next to the cache and moved into place, so another command
+    # never reads a partly written cache
+    if [ "$(get_asdf_config_value "list_all_cache_duration")" != "0" ]; then
+      mkdir -p "$(dirname "$cache_file")"
+      cp "$std_out_file" "$cache_file.$$" && mv -f "$cache_file.$$" "$cache_file"
+    fi
   fi
 
   if [[ $query ]]; then
@@ -107,30 +344,99 @@ list_all_command() {
     output=$(cat "$std_out_file")
   fi
 
+  rm -rf "$tmp_dir"
+
   if [ -z "$output" ]; then
     display_error "No compatible versions available ($plugin_name $query)"
     exit 1
   fi
 
-  IFS=' ' read -r -a versions_list <<<"$output"
+  # Plugins don't always list versions in order
+  IFS=$'\n' read -rd '' -a versions_list <<<"$(tr ' ' '\n' <<<"$output" | sort_versions)"
+
+  if [ "$json" = true ]; then
+    json_string_array "${versions_list[@]}"
+    printf "\\n"
+    return
+  fi
 
   for version in "${versions_list[@]}"; do
     printf "%s\\n" "${version}"
   done
+}
+
+# Returns 0 when a plugin's cached bin/list-all output is younger than
+# list_all_cache_duration minutes.
+list_all_cache_is_fresh() {
+  local cache_file=$1
 
-  # Remove temp files if they still exist
-  rm "$std_out_file" "$std_err_file" || true
+  local duration
+  duration=$(get_asdf_config_value "list_all_cache_duration")
+
+  if [ ! -f "$cache_file" ] || ! [[ "${duration:-60}" =~ ^[1-9][0-9]*$ ]]; then
+    return 1
+  fi
+
+  [ -z "$(find "$cache_file" -mmin +"${duration:-60}" -print)" ]
 }
 
 latest_command() {
   DEFAULT_QUERY="[0-9]"
 
+  local json=false
+  local porcelain=false
+  local short=false
+  local quiet=false
+  local installed_only=false
+  local full_paths=false
+  local list_all_args=()
+  local positional=()
+
+  while [[ $# -gt 0 ]]; do
+    case $1 in
+    --json)
+      json=true
+      shift
+      ;;
+    --refresh)
+      list_all_args+=(--refresh)
+      shift
+      ;;
+    --full-paths)
+      full_paths=true
+      shift
+      ;;
+    --porcelain)
+      porcelain=true
+      shift
+      ;;
+    --short)
+      short=true
+      shift
+      ;;
+    -q | --quiet)
+      quiet=true
+      shift
+      ;;
+    --installed-only)
+      installed_only=true
+      shift
+      ;;
+    *)
+      positional+=("$1")
+      shift
+      ;;
+    esac
+  done
+
+  set -- "${positional[@]}"
+
   local plugin_name=$1
   local query=$2
   local plugin_path
 
   if [ "$plugin_name" == "--all" ]; then
-    latest_all
+    latest_all "$json" "$porcelain" "$query" "$full_paths" "$short"
   fi
 
   [[ -z $query ]] && query="$DEFAULT_QUERY"
@@ -140,70 +446,259 @@ latest_command() {
 
   local versions
 
-  if [ -f "${plugin_path}/bin/latest-stable" ]; then
-    versions=$("${plugin_path}"/bin/latest-stable "$query")
+  if [ "$installed_only" = true ]; then
+    versions=$(list_installed_versions "$plugin_name" |
+      grep -E "^\\s*$query" |
+      filter_stable_versions "$plugin_name" |
+      sort_versions |
+      tail -1)
+    if [ -z "${versions}" ]; then
+      [ "$quiet" = true ] || display_error "No compatible versions installed ($plugin_name $query)"
+      exit 1
+    fi
+  elif asdf_offline; then
+    if [ "${#list_all_args[@]}" -gt 0 ]; then
+      require_online "refresh the versions of $plugin_name"
+      exit 1
+    fi
+    versions=$(offline_latest_version "$plugin_name" "$query")
+    if [ -z "${versions}" ]; then
+      [ "$quiet" = true ] || display_error "No compatible versions cached or installed ($plugin_name $query)"
+      exit 1
+    fi
+  elif [ -f "${plugin_path}/bin/latest-stable" ]; then
+    versions=$(run_plugin_script "${plugin_path}/bin/latest-stable" "$query")
     if [ -z "${versions}" ]; then
       # this branch requires this print to mimic the error from the list-all branch
-      printf "No compatible versions available (%s %s)\\n" "$plugin_name" "$query" >&2
+      [ "$quiet" = true ] || printf "No compatible versions available (%s %s)\\n" "$plugin_name" "$query" >&2
       exit 1
     fi
   else
-    # pattern from xxenv-latest (https://github.com/momo-lab/xxenv-latest)
-    versions=$(list_all_command "$plugin_name" "$query" |
-      grep -ivE "(^Available versions:|-src|-dev|-latest|-stm|[-\\.]rc|-milestone|-alpha|-beta|[-\\.]pre|-next|(a|b|c)[0-9]+|snapshot|master)" |
-      sed 's/^[[:space:]]\+//' |
+    versions=$(list_all_command "$plugin_name" "$query" "${list_all_args[@]}" |
+      filter_stable_versions "$plugin_name" |
+      sort_versions |
       tail -1)
     if [ -z "${versions}" ]; then
       exit 1
     fi
   fi
 
-  printf "%s\\n" "$versions"
+  if [ "$quiet" = true ]; then
+    # Only answer whether the latest version is installed, via exit status
+    list_installed_versions "$plugin_name" | grep -qxF "$versions"
+    exit
+  fi
+
+  if [ "$json" = true ]; then
+    printf '{"plugin":%s,"version":%s}\n' "$(json_string "$plugin_name")" "$(json_string "$versions")"
+  else
+    printf "%s\\n" "$versions"
+  fi
+}
+
+# Prints the latest stable version of a plugin that begins with a query without
+# using the network, from its cached versions or else its installed versions.
+offline_latest_version() {
+  local plugin_name=$1
+  local query=$2
+
+  local cache_file
+  cache_file=$(get_list_all_cache_path "$plugin_name")
+
+  if [ -f "$cache_file" ]; then
+    tr ' ' '\n' <"$cache_file"
+  else
+    list_installed_versions "$plugin_name"
+  fi |
+    grep -E "^\\s*$query" |
+    filter_stable_versions "$plugin_name" |
+    sort_versions |
+    tail -1
+}
+
+# Removes pre-releases and other unstable versions from a list of versions
+filter_stable_versions() {
+  local plugin_name=$1
+  grep -ivE "$(latest_filter_pattern "$plugin_name")" |
+    sed 's/^[[:space:]]\+//'
+}
+
+# Prints the pattern of versions that are never the latest. The latest_filter
+# setting and then latest_filter_<plugin> replace the pattern, or add to it
+# when they start with a +.
+latest_filter_pattern() {
+  local plugin_name=$1
+  # pattern from xxenv-latest (https://github.com/momo-lab/xxenv-latest)
+  local pattern="(^Available versions:|-src|-dev|-latest|-stm|[-\\.]rc|-milestone|-alpha|-beta|[-\\.]pre|-next|(a|b|c)[0-9]+|snapshot|master)"
+
+  local key filter
+  for key in latest_filter ${plugin_name:+"latest_filter_$plugin_name"}; do
+    filter=$(get_asdf_config_value "$key")
+    case "$filter" in
+    "") ;;
+    +*) pattern="$pattern|${filter#+}" ;;
+    *) pattern=$filter ;;
+    esac
+  done
+
+  printf "%s\\n" "$pattern"
 }
 
 latest_all() {
-  local plugins_path
-  plugins_path=$(get_plugin_path)
-
-  if find "$plugins_path" -mindepth 1 -type d &>/dev/null; then
-    for plugin_path in "$plugins_path"/*/; do
-      plugin_name=$(basename "$plugin_path")
-
-      # Retrieve the version of the plugin
-      local version
-      if [ -f "${plugin_path}/bin/latest-stable" ]; then
-        # We can't filter by a concrete query because different plugins might
-        # have different queries.
-        version=$("${plugin_path}"/bin/latest-stable "")
-        if [ -z "${version}" ]; then
-          version="unknown"
-        fi
+  local json=$1
+  local porcelain=$2
+  local query=$3
+  local full_paths=$4
+  local short=$5
+
+  local rows
+  rows=$(latest_all_versions "$query")
+
+  if [ "$json" = true ]; then
+    local plugin_name version resolved_by installed_status separator=""
+    printf "["
+    while IFS=$'\t' read -r plugin_name version _ resolved_by installed_status; do
+      [ -n "$plugin_name" ] || continue
+      printf '%s{"plugin":%s,"version":%s,"installed":%s}' "$separator" \
+        "$(json_string "$plugin_name")" "$(json_string "$version")" \
+        "$([ "$installed_status" = "installed" ] && printf true || printf false)"
+      separator=","
+    done <<<"$rows"
+    printf "]\\n"
+  elif [ -z "$rows" ]; then
+    printf "%s\\n" 'No plugins installed'
+  elif [ "$porcelain" = true ]; then
+    cut -f 1,2,5 <<<"$rows"
+  elif [ "$short" = true ]; then
+    cut -f 1,2 <<<"$rows" | tr '\t' ' '
+  else
+    local max_width=""
+    if [ "$full_paths" != true ] && [ -t 1 ]; then
+      max_width=$(terminal_width)
+    fi
+    print_table "$(printf "PLUGIN\\tLATEST\\tQUERY\\tSOURCE\\tSTATUS\\n%s" "$rows")" "$max_width" 4
+  fi
+  exit 0
+}
+
+# Prints one tab separated line per plugin with the plugin name, its latest
+# stable version (or "unknown"), the query used, how the version was found
+# (bin/latest-stable, bin/list-all or, offline, the cached or installed
+# versions) and whether it is "installed" or
+# "missing". Every output of `latest --all` and `outdated` is built from these.
+# Only the given plugins are looked up, or every installed plugin when none
+# are given.
+latest_all_versions() {
+  local query=$1
+  shift
+
+  local plugin_names=("$@")
+  if [ "$#" -eq 0 ]; then
+    local plugins_path
+    plugins_path=$(get_plugin_path)
+
+    if ! find "$plugins_path" -mindepth 1 -type d &>/dev/null; then
+      return 0
+    fi
+
+    local plugin_dir
+    for plugin_dir in "$plugins_path"/*/; do
+      [ -d "$plugin_dir" ] && plugin_names+=("$(basename "$plugin_dir")")
+    done
+  fi
+
+  local plugin_name plugin_path
+  for plugin_name in "${plugin_names[@]}"; do
+    plugin_path=$(get_plugin_path "$plugin_name")
+
+    # Retrieve the version of the plugin
+    local version
+    local resolved_by
+    if asdf_offline; then
+      if [ -f "$(get_list_all_cache_path "$plugin_name")" ]; then
+        resolved_by="cached versions"
       else
-        # pattern from xxenv-latest (https://github.com/momo-lab/xxenv-latest)
-        version=$(list_all_command "$plugin_name" |
-          grep -ivE "(^Available version:|-src|-dev|-latest|-stm|[-\\.]rc|-alpha|-beta|[-\\.]pre|-next|(a|b|c)[0-9]+|snapshot|master)" |
-          sed 's/^[[:space:]]\+//' |
-          tail -1)
-        if [ -z "${version}" ]; then
-          version="unknown"
-        fi
+        resolved_by="installed versions"
       fi
+      version=$(offline_latest_version "$plugin_name" "$query")
+    elif [ -f "${plugin_path}/bin/latest-stable" ]; then
+      resolved_by="bin/latest-stable"
+      # Without a query we can't filter by a concrete one because different
+      # plugins might have different queries.
+      version=$(run_plugin_script "${plugin_path}/bin/latest-stable" "$query")
+    else
+      resolved_by="bin/list-all"
+      version=$(list_all_command "$plugin_name" "$query" |
+        filter_stable_versions "$plugin_name" |
+        sort_versions |
+        tail -1)
+    fi
+    if [ -z "${version}" ]; then
+      version="unknown"
+    fi
 
-      local installed_status
-      installed_status="missing"
+    local installed_status="missing"
+    if list_installed_versions "$plugin_name" | grep -qxF "$version"; then
+      installed_status="installed"
+    fi
 
-      local installed_versions
-      installed_versions=$(list_installed_versions "$plugin_name")
+    printf "%s\\t%s\\t%s\\t%s\\t%s\\n" "$plugin_name" "$version" "${query:-(any)}" "$resolved_by" "$installed_status"
+  done
+}
 
-      if [ -n "$installed_versions" ] && printf '%s\n' "$installed_versions" | grep -q "^$version\$"; then
-        installed_status="installed"
-      fi
-      printf "%s\\t%s\\t%s\\n" "$plugin_name" "$version" "$installed_status"
-    done
-  else
-    printf "%s\\n" 'No plugins installed'
+# Prints tab separated rows, the first of which is the header, as an aligned
+# table. With colors the last column is styled as success when "installed",
+# error when the row has an "unknown" version and warning otherwise.
+# A table wider than max_width has the values in its path_field, counted from
+# 1, shortened to their end. Tables without a path_field are never shortened.
+print_table() {
+  local rows=$1
+  local max_width=${2:-}
+  local path_field=${3:-0}
+  local success="" error="" warning=""
+  if use_color; then
+    success=$(style_code success)
+    error=$(style_code error)
+    warning=$(style_code warning)
   fi
-  exit 0
+
+  printf "%s\\n" "$rows" | awk -F '\t' -v success="$success" -v error="$error" -v warning="$warning" -v max_width="$max_width" -v path_field="$path_field" '
+    NF {
+      lines[++count] = $0
+      if (NF > fields_count) fields_count = NF
+      for (i = 1; i <= NF; i++) if (length($i) > width[i]) width[i] = length($i)
+    }
+    END {
+      total = 0
+      for (i = 1; i <= fields_count; i++) {
+        total += width[i] + (i < fields_count ? 2 : 0)
+      }
+      if (max_width > 0 && path_field > 0 && total > max_width) {
+        shortened = width[path_field] - (total - max_width)
+        if (shortened < 10) shortened = 10
+        if (shortened < width[path_field]) width[path_field] = shortened
+        else shortened = 0
+      }
+      for (n = 1; n <= count; n++) {
+        split(lines[n], fields, "\t")
+        if (shortened && length(fields[path_field]) > shortened) {
+          fields[path_field] = "..." substr(fields[path_field], length(fields[path_field]) - shortened + 4)
+        }
+        line = ""
+        unknown = 0
+        for (i = 1; i < fields_count; i++) {
+          line = line sprintf("%-" (width[i] + 2) "s", fields[i])
+          if (fields[i] == "unknown") unknown = 1
+        }
+        last = fields[fields_count]
+        if (success != "" && n > 1) {
+          if (unknown) last = "\033[" error "m" last "\033[0m"
+          else if (last == "installed") last = "\033[" success "m" last "\033[0m"
+          else last = "\033[" warning "m" last "\033[0m"
+        }
+        print line last
+      }
+    }'
 }
 
 local_command() {
@@ -0,0 +1,73 @@
+#!/usr/bin/env bash
+
+set -euo pipefail
+
+# TODO: Check that this is the repository __TOOL_NAME__ releases are published in
+GH_REPO="__TOOL_REPO__"
+TOOL_NAME="__TOOL_NAME__"
+TOOL_TEST="__TOOL_NAME__ --version"
+
+fail() {
+  printf "asdf-%s: %s\n" "$TOOL_NAME" "$*" >&2
+  exit 1
+}
+
+curl_opts=(-fsSL)
+
+# Authenticated requests to GitHub are less likely to be rate limited
+if [ -n "${GITHUB_API_TOKEN:-}" ]; then
+  curl_opts=("${curl_opts[@]}" -H "Authorization: token $GITHUB_API_TOKEN")
+fi
+
+# Sorts versions numerically, oldest first, as asdf expects from bin/list-all
+sort_versions() {
+  sed 'h; s/[+-]/./g; s/.p\([[:digit:]]\)/.z\1/; s/$/.z/; G; s/\n/ /' |
+    LC_ALL=C sort -t. -k 1,1 -k 2,2n -k 3,3n -k 4,4n -k 5,5n | awk '{print $2}'
+}
+
+list_github_tags() {
+  git ls-remote --tags --refs "$GH_REPO" |
+    grep -o 'refs/tags/.*' | cut -d/ -f3- |
+    sed 's/^v//'
+}
+
+list_all_versions() {
+  # TODO: Adapt this if the tags of __TOOL_NAME__ are not all versions
+  list_github_tags
+}
+
+download_release() {
+  local version=$1
+  local filename=$2
+
+  # TODO: Adapt this to the URL of the release archives of __TOOL_NAME__
+  local url="$GH_REPO/archive/v${version}.tar.gz"
+
+  printf "* Downloading %s release %s...\n" "$TOOL_NAME" "$version"
+  curl "${curl_opts[@]}" -o "$filename" -C - "$url" || fail "Could not download $url"
+}
+
+install_version() {
+  local install_type=$1
+  local version=$2
+  local install_path=$3
+
+  if [ "$install_type" != "version" ]; then
+    fail "asdf-$TOOL_NAME supports release installs only"
+  fi
+
+  (
+    mkdir -p "$install_path/bin"
+    # TODO: Adapt this if the release archives of __TOOL_NAME__ need to be built
+    cp -r "$ASDF_DOWNLOAD_PATH"/. "$install_path/bin"
+
+    local tool_cmd
+    tool_cmd=$(cut -d ' ' -f 1 <<<"$TOOL_TEST")
+    test -x "$install_path/bin/$tool_cmd" || fail "Expected $install_path/bin/$tool_cmd to be executable."
+
+    printf "%s %s installation was successful!\n" "$TOOL_NAME" "$version"
+  ) || (
+    rm -rf "$install_path"
+    fail "An error occurred while installing $TOOL_NAME $version."
+  )
+}
@@ -19,6 +19,21 @@ asdf_version() {
   fi
 }
 
+# Prints the version along with the git commit and commit date asdf was built
+# from. Installs without a git checkout report both as unknown.
+asdf_build_info() {
+  local git_rev="unknown"
+  local git_date="unknown"
+  if [ -d "$(asdf_dir)/.git" ]; then
+    git_rev="$(git --git-dir "$(asdf_dir)/.git" rev-parse HEAD)"
+    git_date="$(git --git-dir "$(asdf_dir)/.git" log -1 --format=%cd --date=short HEAD)"
+  fi
+
+  printf "version: v%s\\n" "$(cat "$(asdf_dir)/version.txt")"
+  printf "commit: %s\\n" "$git_rev"
+  printf "date: %s\\n" "$git_date"
+}
+
 asdf_dir() {
   if [ -z "$ASDF_DIR" ]; then
     local current_script_path=${BASH_SOURCE[0]}
@@ -50,6 +65,17 @@ asdf_data_dir() {
   printf "%s\\n" "$data_dir"
 }
 
+# Creates a temporary directory private to a single operation under the data
+# dir and prints its path. Callers are responsible for removing it.
+make_tmp_dir() {
+  local operation=$1
+  local tmp_root
+  tmp_root="$(asdf_data_dir)/tmp"
+
+  mkdir -p "$tmp_root"
+  mktemp -d "$tmp_root/${operation}-XXXXXX"
+}
+
 get_install_path() {
   local plugin=$1
   local install_type=$2
@@ -88,6 +114,51 @@ get_download_path() {
   fi
 }
 
+get_receipt_path() {
+  local plugin=$1
+  local install_type=$2
+  local version=$3
+
+  local receipts_dir
+  receipts_dir="$(asdf_data_dir)/receipts"
+
+  if [ "$install_type" = "version" ]; then
+    printf "%s/%s/%s\\n" "$receipts_dir" "$plugin" "$version"
+  elif [ "$install_type" = "path" ]; then
+    return
+  else
+    printf "%s/%s/%s-%s\\n" "$receipts_dir" "$plugin" "$install_type" "$version"
+  fi
+}
+
+# Prints the file the bin/list-all output of a plugin is cached in
+get_list_all_cache_path() {
+  local plugin=$1
+  printf "%s/cache/list-all/%s\\n" "$(asdf_data_dir)" "$plugin"
+}
+
+# Removes the cached bin/list-all output of a plugin, or of every plugin
+clear_list_all_cache() {
+  local plugin=$1
+
+  if [ -n "$plugin" ]; then
+    rm -f "$(get_list_all_cache_path "$plugin")"
+  else
+    rm -rf "$(asdf_data_dir)/cache/list-all"
+  fi
+}
+
+list_installed_plugins() {
+  local plugins_path
+  plugins_path=$(get_plugin_path)
+
+  local plugin_path
+  for plugin_path in "$plugins_path"/*/; do
+    [[ -d "$plugin_path" ]] || break
+    basename "$plugin_path"
+  done
+}
+
 list_installed_versions() {
   local plugin_name=$1
   local plugin_path
@@ -100,10 +171,40 @@ list_installed_versions() {
     for install in "${plugin_installs_path}"/*/; do
       [[ -e "$install" ]] || break
       basename "$install" | sed 's/^ref-/ref:/'
-    done
+    done | sort_versions
   fi
 }
 
+# Sorts versions from oldest to newest, one per line. Each version gets a sort
+# key where numbers are zero padded so 0.10.0 sorts after 0.9.0, words such as
+# rc or beta sort before the end of a version so 1.0.0-rc1 sorts before 1.0.0,
+# and Ruby style patch levels (-p123) sort after it. A leading v is ignored.
+sort_versions() {
+  awk '
+    NF {
+      rest = $1
+      sub(/^v/, "", rest)
+      key = ""
+      while (rest != "") {
+        if (match(rest, /^[0-9]+/)) {
+          number = substr(rest, 1, RLENGTH)
+          sub(/^0+/, "", number)
+          key = key "#" substr("000000000000", 1, 12 - length(number)) number
+        } else if (match(rest, /^p[0-9]/) && key != "") {
+          RLENGTH = 1
+          key = key "$"
+        } else if (match(rest, /^[A-Za-z]+/)) {
+          key = key "!" tolower(substr(rest, 1, RLENGTH))
+        } else {
+          RLENGTH = 1
+        }
+        rest = substr(rest, RLENGTH + 1)
+      }
+      print key "\"", $1
+    }
+  ' | LC_ALL=C sort -k 1,1 -k 2,2 | awk '{ print $2 }'
+}
+
 check_if_plugin_exists() {
   local plugin_name=$1
 
@@ -119,6 +220,132 @@ check_if_plugin_exists() {
   fi
 }
 
+# Returns 0 when the plugin name contains glob characters, such as `node*`.
+is_plugin_pattern() {
+  [[ $1 == *[\*\?\[]* ]]
+}
+
+# Prints the installed plugins matching a glob pattern, one per line, or fails
+# with an error when none match.
+expand_plugin_pattern() {
+  local pattern=$1
+  local plugin_name
+  local matches=""
+
+  while IFS= read -r plugin_name; do
+    # shellcheck disable=SC2053
+    if [ -n "$plugin_name" ] && [[ $plugin_name == $pattern ]]; then
+      matches+="$plugin_name"$'\n'
+    fi
+  done <<<"$(list_installed_plugins)"
+
+  if [ -z "$matches" ]; then
+    display_error "No installed plugins match $pattern"
+    return 1
+  fi
+
+  printf "%s" "$matches"
+}
+
+# Asks the user to confirm an action, reading the answer from stdin. Anything
+# but y or yes, including no answer at all, declines.
+confirm_action() {
+  local question=$1
+  local answer
+
+  printf "%s [y/N] " "$question"
+  if ! read -r answer; then
+    printf "\\n"
+    return 1
+  fi
+
+  case "$answer" in
+  y | Y | yes | YES) return 0 ;;
+  *) return 1 ;;
+  esac
+}
+
+# Returns 0 when a version is a constraint on versions rather than a version,
+# such as ^18, ~3.11 or >=1.20,<1.22.
+is_version_constraint() {
+  [[ "$1" =~ ^(\^|~|>|<|=) ]]
+}
+
+# Prints the versions read from stdin, one per line, that satisfy a
+# constraint. A constraint is one or more comma separated clauses, all of
+# which must hold:
+#   ^1.2.3  compatible with 1.2.3: at least 1.2.3 and below 2, or below 0.3
+#           for a 0.x version
+#   ~1.2.3  at least 1.2.3 and below 1.3, or below 2 for ~1
+#   >1.2  >=1.2  <1.2  <=1.2  compared with 1.2 padded with zeros
+#   =1.2    any 1.2.x version
+# Only release versions, made of numbers and dots with an optional leading v,
+# satisfy a constraint.
+filter_versions_by_constraint() {
+  awk -v constraint="$1" '
+    function compare(a, b,    count_a, count_b, parts_a, parts_b, i) {
+      count_a = split(a, parts_a, ".")
+      count_b = split(b, parts_b, ".")
+      for (i = 1; i <= (count_a > count_b ? count_a : count_b); i++) {
+        if (parts_a[i] + 0 != parts_b[i] + 0) return parts_a[i] + 0 < parts_b[i] + 0 ? -1 : 1
+      }
+      return 0
+    }
+
+    # Prints a version with its part at index increased by one and any later
+    # parts dropped
+    function bump(version, index_to_bump,    parts, count, i, bumped) {
+      count = split(version, parts, ".")
+      bumped = ""
+      for (i = 1; i < index_to_bump; i++) bumped = bumped parts[i] "."
+      return bumped (parts[index_to_bump] + 1)
+    }
+
+    function satisfies(version, clause,    operator, bound, parts, count, i) {
+      match(clause, /^(\^|~|>=|<=|>|<|=)/)
+      operator = substr(clause, 1, RLENGTH)
+      bound = substr(clause, RLENGTH + 1)
+      sub(/^v/, "", bound)
+      if (bound !~ /^[0-9]+(\.[0-9]+)*$/) return 0
+
+      count = split(bound, parts, ".")
+      if (operator == ">=") return compare(version, bound) >= 0
+      if (operator == "<=") return compare(version, bound) <= 0
+      if (operator == ">") return compare(version, bound) > 0
+      if (operator == "<") return compare(version, bound) < 0
+      if (operator == "=") return compare(version, bound) >= 0 && compare(version, bump(bound, count)) < 0
+      if (operator == "~") return compare(version, bound) >= 0 && compare(version, bump(bound, count > 1 ? 2 : 1)) < 0
+
+      # ^ keeps the first part that is not zero
+      for (i = 1; i < count && parts[i] == 0; i++) {}
+      return compare(version, bound) >= 0 && compare(version, bump(bound, i)) < 0
+    }
+
+    BEGIN { clauses_count = split(constraint, clauses, ",") }
+
+    NF {
+      version = $1
+      sub(/^v/, "", version)
+      if (version !~ /^[0-9]+(\.[0-9]+)*$/) next
+      for (i = 1; i <= clauses_count; i++) {
+        if (!satisfies(version, clauses[i])) next
+      }
+      print $1
+    }
+  '
+}
+
+# Prints the newest installed version of a plugin that satisfies a constraint.
+find_installed_version_for_constraint() {
+  local plugin_name=$1
+  local constraint=$2
+
+  list_installed_versions "$plugin_name" |
+    filter_versions_by_constraint "$constraint" |
+    sort_versions |
+    tail -n 1
+}
+
 check_if_version_exists() {
   local plugin_name=$1
   local version=$2
@@ -133,6 +360,38 @@ check_if_version_exists() {
   fi
 }
 
+# Prints a line for each deprecated plugin API the plugin relies on
+plugin_deprecations() {
+  local plugin_path
+  plugin_path=$(get_plugin_path "$1")
+
+  if [ -f "${plugin_path}/bin/get-version-from-legacy-file" ] && [ ! -f "${plugin_path}/bin/list-legacy-filenames" ]; then
+    printf "bin/get-version-from-legacy-file is deprecated, use bin/list-legacy-filenames and bin/parse-legacy-file\\n"
+  fi
+
+  if [ -f "${plugin_path}/bin/install" ] && [ ! -f "${plugin_path}/bin/download" ]; then
+    printf "bin/install without bin/download is deprecated, move downloading to bin/download\\n"
+  fi
+}
+
+# Exits with an error when the deprecated_plugin_policy setting is fail and the
+# plugin relies on a deprecated plugin API
+check_plugin_deprecations() {
+  local plugin_name=$1
+
+  if [ "$(get_asdf_config_value "deprecated_plugin_policy")" != "fail" ]; then
+    return 0
+  fi
+
+  local deprecations
+  deprecations=$(plugin_deprecations "$plugin_name")
+  if [ -n "$deprecations" ]; then
+    display_error "$plugin_name plugin uses deprecated plugin APIs and deprecated_plugin_policy is fail:"
+    display_error "$deprecations"
+    exit 1
+  fi
+}
+
 version_not_installed_text() {
   local plugin_name=$1
   local version=$2
@@ -148,85 +407,434 @@ get_plugin_path() {
   fi
 }
 
+# Decides whether stdout and stderr are colored. Colors are used on a terminal
+# unless NO_COLOR is set, and `asdf --color always|never`, or ASDF_COLOR,
+# overrides both. This runs once, before any command substitution, whose
+# output is never a terminal.
+init_colors() {
+  ASDF_COLOR_STDOUT=no
+  ASDF_COLOR_STDERR=no
+
+  case "${ASDF_COLOR:-auto}" in
+  always)
+    ASDF_COLOR_STDOUT=yes
+    ASDF_COLOR_STDERR=yes
+    ;;
+  never) ;;
+  *)
+    if [ -z "${NO_COLOR:-}" ]; then
+      [ -t 1 ] && ASDF_COLOR_STDOUT=yes
+      [ -t 2 ] && ASDF_COLOR_STDERR=yes
+    fi
+    ;;
+  esac
+}
+
+# Returns 0 when the output to stdout, or stderr when given 2, is colored
+use_color() {
+  local fd=${1:-1}
+  [ -n "${ASDF_COLOR_STDOUT:-}" ] || init_colors
+
+  if [ "$fd" = "2" ]; then
+    [ "$ASDF_COLOR_STDERR" = "yes" ]
+  else
+    [ "$ASDF_COLOR_STDOUT" = "yes" ]
+  fi
+}
+
+# Prints the escape code of an output style. Every command uses these styles so
+# that they look alike: success for installed versions and passed checks, error
+# for errors, missing versions and failed checks, warning for warnings and
+# anything else that needs attention, and current for the current version.
+style_code() {
+  case "$1" in
+  success) printf "32\\n" ;;
+  error) printf "31\\n" ;;
+  warning) printf "33\\n" ;;
+  current) printf "1;32\\n" ;;
+  esac
+}
+
+# Prints text, without a newline, in an output style when stdout, or stderr
+# when given 2, is colored.
+styled() {
+  local style=$1
+  local text=$2
+  local fd=${3:-1}
+
+  if use_color "$fd"; then
+    printf "\\033[%sm%s\\033[0m" "$(style_code "$style")" "$text"
+  else
+    printf "%s" "$text"
+  fi
+}
+
+# Prints the number of columns of the terminal, from COLUMNS or the terminal
+# itself, or nothing when it is unknown. Callers check that their output is a
+# terminal first, as output to files and pipes is never shortened.
+terminal_width() {
+  local width=${COLUMNS:-}
+  if ! [[ "$width" =~ ^[0-9]+$ ]]; then
+    width=$(stty size 2>/dev/null </dev/tty | cut -d ' ' -f 2)
+  fi
+
+  if [[ "$width" =~ ^[1-9][0-9]*$ ]]; then
+    printf "%s\\n" "$width"
+  fi
+}
+
+# Shortens a path to fit in the given number of columns, first by writing
+# $HOME as ~ and then by replacing its leading directories with "...", so
+# the file name is kept. Paths are left as they are without a width, and
+# never shortened to less than 10 columns.
+shorten_path() {
+  local path=$1
+  local width=$2
+
+  if [ -z "$width" ] || [ "${#path}" -le "$width" ]; then
+    printf "%s\\n" "$path"
+    return 0
+  fi
+
+  if [ "$width" -lt 10 ]; then
+    width=10
+  fi
+
+  if [[ "$path" == "$HOME/"* ]]; then
+    path="~${path#"$HOME"}"
+  fi
+
+  if [ "${#path}" -gt "$width" ]; then
+    path="...${path:$((${#path} - width + 3))}"
+  fi
+
+  printf "%s\\n" "$path"
+}
+
 display_error() {
-  printf "%s\\n" "$1" >&2
+  printf "%s\\n" "$(styled error "$1" 2)" >&2
 }
 
-get_version_in_dir() {
-  local plugin_name=$1
-  local search_path=$2
-  local legacy_filenames=$3
+# Warnings are collected while a command runs and printed to stderr once it
+# finishes, so they are not mixed in with the command output. Without a
+# warnings file they are printed immediately.
+asdf_warn() {
+  if [ -z "$ASDF_WARNINGS_FILE" ]; then
+    printf "%s %s\\n" "$(styled warning "warning:" 2)" "$1" >&2
+    return 0
+  fi
+
+  mkdir -p "$(dirname "$ASDF_WARNINGS_FILE")"
+  printf "%s\\n" "$1" >>"$ASDF_WARNINGS_FILE"
+}
+
+# Prints each collected warning once, as JSON objects when
+# ASDF_WARNINGS_FORMAT is json.
+print_warnings() {
+  if [ -z "$ASDF_WARNINGS_FILE" ] || [ ! -f "$ASDF_WARNINGS_FILE" ]; then
+    return 0
+  fi
+
+  local warning
+  while IFS= read -r warning; do
+    if [ "$ASDF_WARNINGS_FORMAT" = "json" ]; then
+      printf '{"warning":%s}\n' "$(json_string "$warning")" >&2
+    else
+      printf "%s %s\\n" "$(styled warning "warning:" 2)" "$warning" >&2
+    fi
+  done <<<"$(awk '!seen[$0]++' "$ASDF_WARNINGS_FILE")"
+
+  rm -f "$ASDF_WARNINGS_FILE"
+}
+
+# Returns 0 when a command was given --dry-run, in which case it prints the
+# changes and plugin scripts it would make or run instead.
+asdf_dry_run() {
+  [ -n "${ASDF_DRY_RUN:-}" ]
+}
+
+# Prints a change a dry run would make.
+dry_run_note() {
+  printf "Would %s\\n" "$1"
+}
+
+# Commands that work through a batch of items, such as installing every
+# version in the version files or updating every plugin, carry on when an item
+# fails and report every failure once they finish. With --fail-fast, or
+# fail_fast set to yes, they stop after the first failure instead.
+batch_start() {
+  ASDF_BATCH_TOTAL=0
+  ASDF_BATCH_FAILURES=()
+  if [ -z "${ASDF_FAIL_FAST:-}" ] && [ "$(get_asdf_config_value "fail_fast")" = "yes" ]; then
+    ASDF_FAIL_FAST=yes
+  fi
+}
+
+# Runs one item of a batch in a subshell, so that an item exiting does not end
+# the batch, and records the item as failed when it fails.
+batch_run() {
+  local label=$1
+  shift
+
+  batch_add_item
+  ("$@") || batch_add_failure "$label"
+}
+
+# Counts an item that is run outside of batch_run, such as in the background.
+batch_add_item() {
+  ASDF_BATCH_TOTAL=$((ASDF_BATCH_TOTAL + 1))
+}
+
+batch_add_failure() {
+  local label=$1
+  local detail=$2
+  ASDF_BATCH_FAILURES+=("${label}${detail:+: $detail}")
+}
+
+# Returns 0 when the batch should not start any more items.
+batch_should_stop() {
+  [ -n "${ASDF_FAIL_FAST:-}" ] && [ "${#ASDF_BATCH_FAILURES[@]}" -gt 0 ]
+}
+
+# Prints the failed items of the batch to stderr. Fails if any item failed.
+batch_report() {
+  local action=$1
+
+  [ "${#ASDF_BATCH_FAILURES[@]}" -gt 0 ] || return 0
 
-  local asdf_version
+  {
+    printf "\\n%s failed for %s of %s:\\n" "$action" "${#ASDF_BATCH_FAILURES[@]}" "$ASDF_BATCH_TOTAL"
+    printf "  %s\\n" "${ASDF_BATCH_FAILURES[@]}"
+    if [ -n "${ASDF_FAIL_FAST:-}" ]; then
+      printf "Stopped after the first failure\\n"
+    fi
+  } >&2
+  return 1
+}
+
+# Returns 0 when debug logging is enabled, with ASDF_DEBUG or `asdf --verbose`.
+asdf_debug_enabled() {
+  [ -n "${ASDF_DEBUG:-}" ] && [ "$ASDF_DEBUG" != "0" ]
+}
+
+# Prints the milliseconds since the epoch. Shells without EPOCHREALTIME, such
+# as bash 3, only have whole seconds.
+asdf_now_ms() {
+  if [ -n "${EPOCHREALTIME:-}" ]; then
+    local now=${EPOCHREALTIME/[.,]/}
+    printf "%s\\n" "$((10#$now / 1000))"
+  else
+    printf "%s\\n" "$(($(date +%s) * 1000))"
+  fi
+}
+
+# Prints a debug message on stderr with the milliseconds since asdf started,
+# only when debug logging is enabled.
+asdf_debug() {
+  asdf_debug_enabled || return 0
+  : "${ASDF_DEBUG_STARTED_AT:=$(asdf_now_ms)}"
+  printf "asdf: [%5sms] %s\\n" "$(($(asdf_now_ms) - ASDF_DEBUG_STARTED_AT))" "$1" >&2
+}
 
-  file_name=$(version_file_name)
-  asdf_version=$(parse_asdf_version_file "$search_path/$file_name" "$plugin_name")
+# The name of the version file that asdf writes, the first of the names it
+# reads.
+version_file_name() {
+  local file_names
+  read -r -a file_names <<<"$(version_file_names)"
+  printf "%s" "${file_names[0]}"
+}
 
-  if [ -n "$asdf_version" ]; then
-    printf "%s\\n" "$asdf_version|$search_path/$file_name"
+# Prints the names of the version files read in each directory, space
+# separated and in search order: ASDF_DEFAULT_TOOL_VERSIONS_FILENAME when
+# set, otherwise the tool_versions_filenames setting.
+version_file_names() {
+  if [ -n "${ASDF_DEFAULT_TOOL_VERSIONS_FILENAME:-}" ]; then
+    printf "%s\\n" "$ASDF_DEFAULT_TOOL_VERSIONS_FILENAME"
     return 0
   fi
 
-  for filename in $legacy_filenames; do
-    local legacy_version
-    legacy_version=$(parse_legacy_version_file "$search_path/$filename" "$plugin_name")
+  local file_names
+  file_names=$(get_upward_search_setting "ASDF_TOOL_VERSIONS_FILENAMES" "tool_versions_filenames")
+  printf "%s\\n" "${file_names:-.tool-versions}"
+}
+
+# Prints the path of the first version file found in a directory, or returns 1
+# when there is none.
+version_file_in_dir() {
+  local dir=$1
+  local file_name
 
-    if [ -n "$legacy_version" ]; then
-      printf "%s\\n" "$legacy_version|$search_path/$filename"
+  for file_name in $(version_file_names); do
+    if [ -f "$dir/$file_name" ]; then
+      printf "%s\\n" "$dir/$file_name"
       return 0
     fi
   done
+  return 1
 }
 
-version_file_name() {
-  printf "%s" "${ASDF_DEFAULT_TOOL_VERSIONS_FILENAME:-.tool-versions}"
+# Returns 0 when ASDF_STRICT is set, which disables every way of picking a
+# version other than the exact versions in the version files: environment
+# variables, legacy version files and the fallbacks for missing versions.
+asdf_strict() {
+  [ -n "${ASDF_STRICT:-}" ] && [ "$ASDF_STRICT" != "0" ]
+}
+
+# Returns 0 when asdf was given --offline or ASDF_OFFLINE is set, in which case
+# commands use the cached plugin repository, the cached versions of plugins and
+# the installed versions instead of the network.
+asdf_offline() {
+  [ -n "${ASDF_OFFLINE:-}" ] && [ "$ASDF_OFFLINE" != "0" ]
+}
+
+# Fails with an error saying what needs the network when asdf is offline.
+require_online() {
+  if asdf_offline; then
+    display_error "Cannot $1 while offline (--offline or ASDF_OFFLINE is set)"
+    return 1
+  fi
 }
 
 find_versions() {
   local plugin_name=$1
   local search_path=$2
 
+  each_version_origin "$plugin_name" "$search_path" print_version_origin || true
+}
+
+# Prints "<version>|<origin>" for the origin of a version, as the callback
+# of each_version_origin.
+print_version_origin() {
+  local kind=$1
+  local origin=$2
+  local version=$3
+
+  [ -n "$version" ] || return 1
+
+  if [ "$kind" = "env" ]; then
+    printf "%s\\n" "$version|$origin environment variable"
+  else
+    printf "%s\\n" "$version|$origin"
+  fi
+}
+
+# Goes through the places the version of a plugin is set in, in the order
+# they take precedence, and calls the callback with the kind of each place,
+# the place and the version it sets, if any. The kind is "env" for the
+# ASDF_${TOOL}_VERSION variable, "file" for a version file, which may not
+# exist, and "resolvers" for the version resolvers, with the resolver that
+# found the version. Stops at the first place for which the callback
+# succeeds, and fails when there is none.
+each_version_origin() {
+  local plugin_name=$1
+  local search_path=$2
+  local callback=$3
+
   local version
-  version=$(get_version_from_env "$plugin_name")
-  if [ -n "$version" ]; then
-    local upcase_name
-    upcase_name=$(printf "%s\\n" "$plugin_name" | tr '[:lower:]-' '[:upper:]_')
-    local version_env_var="ASDF_${upcase_name}_VERSION"
+  if ! asdf_strict; then
+    local version_env_var
+    version_env_var="ASDF_$(printf "%s\\n" "$plugin_name" | tr '[:lower:]-' '[:upper:]_')_VERSION"
+    version=${!version_env_var:-}
+    [ -n "$version" ] && asdf_debug "$plugin_name: $version from $version_env_var"
+    "$callback" env "$version_env_var" "$version" && return 0
+  fi
 
-    printf "%s\\n" "$version|$version_env_var environment variable"
-    return 0
+  if [ -n "${ASDF_TOOL_VERSIONS_FILE:-}" ]; then
+    check_version_file "$plugin_name" "$callback" "$ASDF_TOOL_VERSIONS_FILE" false
+    return
   fi
 
-  local plugin_path
-  plugin_path=$(get_plugin_path "$plugin_name")
-  local legacy_config
-  legacy_config=$(get_asdf_config_value "legacy_version_file")
-  local legacy_list_filenames_script
-  legacy_list_filenames_script="${plugin_path}/bin/list-legacy-filenames"
   local legacy_filenames=""
+  if [ "$(get_asdf_config_value "legacy_version_file")" = "yes" ] && ! asdf_strict; then
+    legacy_filenames=$(plugin_legacy_filenames "$plugin_name")
+  fi
+
+  local file_names
+  file_names=$(version_file_names)
+
+  local search_paths dir file_name
+  IFS=$'\n' read -rd '' -a search_paths <<<"$(upward_search_paths "$search_path")"
+  for dir in "${search_paths[@]}" "$HOME"; do
+    for file_name in $file_names; do
+      check_version_file "$plugin_name" "$callback" "$dir/$file_name" false && return 0
+    done
+    for file_name in $legacy_filenames; do
+      check_version_file "$plugin_name" "$callback" "$dir/$file_name" true && return 0
+    done
+  done
 
-  if [ "$legacy_config" = "yes" ] && [ -f "$legacy_list_filenames_script" ]; then
-    legacy_filenames=$($legacy_list_filenames_script)
+  if ! asdf_strict && [ -n "$(get_asdf_config_value "version_resolvers")" ]; then
+    local resolver
+    IFS='|' read -r version resolver <<<"$(get_version_from_resolvers "$plugin_name" "$search_path")"
+    "$callback" resolvers "$resolver" "$version" && return 0
   fi
 
-  while [ "$search_path" != "/" ]; do
-    version=$(get_version_in_dir "$plugin_name" "$search_path" "$legacy_filenames")
-    if [ -n "$version" ]; then
-      printf "%s\\n" "$version"
-      return 0
+  if [ -n "${ASDF_DEFAULT_TOOL_VERSIONS_FILENAME:-}" ]; then
+    check_version_file "$plugin_name" "$callback" "$ASDF_DEFAULT_TOOL_VERSIONS_FILENAME" false && return 0
+  fi
+
+  return 1
+}
+
+# Reads the version of a plugin from a version file, or a legacy version file
+# when legacy is true, and calls the callback of each_version_origin with it.
+check_version_file() {
+  local plugin_name=$1
+  local callback=$2
+  local file=$3
+  local legacy=$4
+
+  local version=""
+  if [ -f "$file" ]; then
+    if [ "$legacy" = true ]; then
+      version=$(parse_legacy_version_file "$file" "$plugin_name")
+    else
+      version=$(parse_asdf_version_file "$file" "$plugin_name")
     fi
-    search_path=$(dirname "$search_path")
-  done
+    asdf_debug "$plugin_name: ${version:-not set} in $file"
+  fi
+
+  "$callback" file "$file" "$version"
+}
+
+# Asks the version resolvers listed in version_resolvers, in that order, for
+# the version of a plugin in a directory and prints the first
+# "<version>|<resolver>" one of them finds. "plugin" stands for the
+# bin/resolve-version script of the plugin, other names for executables in
+# $ASDF_DATA_DIR/resolvers. A resolver is run with the plugin name and the
+# directory and prints the versions, space separated, or nothing.
+get_version_from_resolvers() {
+  local plugin_name=$1
+  local search_path=$2
 
-  get_version_in_dir "$plugin_name" "$HOME" "$legacy_filenames"
+  local resolvers
+  resolvers=$(get_asdf_config_value "version_resolvers")
 
-  if [ -f "$ASDF_DEFAULT_TOOL_VERSIONS_FILENAME" ]; then
-    versions=$(parse_asdf_version_file "$ASDF_DEFAULT_TOOL_VERSIONS_FILENAME" "$plugin_name")
-    if [ -n "$versions" ]; then
-      printf "%s\\n" "$versions|$ASDF_DEFAULT_TOOL_VERSIONS_FILENAME"
+  local resolver resolver_path version
+  for resolver in $resolvers; do
+    if [ "$resolver" = "plugin" ]; then
+      resolver_path="$(get_plugin_path "$plugin_name")/bin/resolve-version"
+      [ -f "$resolver_path" ] || continue
+    else
+      resolver_path="$(asdf_data_dir)/resolvers/$resolver"
+      if [ ! -x "$resolver_path" ]; then
+        asdf_warn "version resolver $resolver is not an executable in $(asdf_data_dir)/resolvers"
+        continue
+      fi
+    fi
+
+    if ! version=$(run_with_plugin_env_filter "$resolver_path" "$plugin_name" "$search_path"); then
+      asdf_warn "version resolver $resolver_path failed for $plugin_name"
+      continue
+    fi
+    version=$(head -n 1 <<<"$version")
+    asdf_debug "$plugin_name: ${version:-not set} from $resolver_path"
+
+    if [ -n "$version" ]; then
+      printf "%s|%s\\n" "$version" "$resolver_path"
       return 0
     fi
-  fi
+  done
 }
 
 display_no_version_set() {
@@ -252,6 +860,15 @@ find_install_path() {
 
   if [ "$version" = "system" ]; then
     printf "\\n"
+  elif is_version_constraint "$version"; then
+    # A constraint uses the newest installed version that satisfies it
+    local installed_version
+    installed_version=$(find_installed_version_for_constraint "$plugin_name" "$version")
+    if [ -n "$installed_version" ]; then
+      get_install_path "$plugin_name" "version" "$installed_version"
+    else
+      printf "\\n"
+    fi
   elif [ "${version_info[0]}" = "ref" ]; then
     local install_type="${version_info[0]}"
     local version="${version_info[1]}"
@@ -282,7 +899,7 @@ get_custom_executable_path() {
     local relative_path
     # shellcheck disable=SC2001
     relative_path=$(printf "%s\\n" "$executable_path" | sed -e "s|${install_path}/||")
-    relative_path="$("${plugin_path}/bin/exec-path" "$install_path" "$cmd" "$relative_path")"
+    relative_path="$(run_with_plugin_env_filter "${plugin_path}/bin/exec-path" "$install_path" "$cmd" "$relative_path")"
     executable_path="$install_path/$relative_path"
   fi
 
@@ -337,17 +954,93 @@ parse_legacy_version_file() {
 
   if [ -f "$file_path" ]; then
     if [ -f "$parse_legacy_script" ]; then
-      "$parse_legacy_script" "$file_path"
+      run_with_plugin_env_filter "$parse_legacy_script" "$file_path"
+    elif [ ! -f "${plugin_path}/bin/list-legacy-filenames" ]; then
+      parse_builtin_legacy_file "$file_path"
     else
       cat "$file_path"
     fi
   fi
 }
 
+# Prints the legacy version file names of a plugin, space separated: the
+# ones its bin/list-legacy-filenames lists or, for a plugin without that
+# script, the ones asdf reads itself for some well known tools.
+plugin_legacy_filenames() {
+  local plugin_name=$1
+  local list_script
+  list_script="$(get_plugin_path "$plugin_name")/bin/list-legacy-filenames"
+
+  if [ -f "$list_script" ]; then
+    run_with_plugin_env_filter "$list_script"
+    return
+  fi
+
+  case "$plugin_name" in
+  nodejs | node)
+    printf "%s\\n" ".nvmrc .node-version package.json"
+    ;;
+  python)
+    printf "%s\\n" ".python-version"
+    ;;
+  ruby)
+    printf "%s\\n" ".ruby-version"
+    ;;
+  rust)
+    printf "%s\\n" "rust-toolchain.toml rust-toolchain"
+    ;;
+  esac
+}
+
+# Prints the versions set in one of the legacy version files asdf reads
+# itself. A partial version such as 18 or 3.11, or a range from the engines
+# of a package.json, is turned into a version constraint.
+parse_builtin_legacy_file() {
+  local file_path=$1
+
+  case "$(basename "$file_path")" in
+  .nvmrc | .node-version)
+    awk 'NF && $1 !~ /^#/ { sub(/^v/, "", $1); print $1; exit }' "$file_path" | partial_version_to_constraint
+    ;;
+  .python-version)
+    awk 'NF && $1 !~ /^#/ { print $1 }' "$file_path" | partial_version_to_constraint | tr '\n' ' ' | sed 's/ $//'
+    ;;
+  .ruby-version)
+    awk 'NF && $1 !~ /^#/ { sub(/^ruby-/, "", $1); print $1; exit }' "$file_path"
+    ;;
+  rust-toolchain.toml | rust-toolchain)
+    if grep -q "channel" "$file_path"; then
+      sed -n 's/^[[:space:]]*channel[[:space:]]*=[[:space:]]*"\([^"]*\)".*/\1/p' "$file_path" | head -n 1
+    else
+      awk 'NF { print $1; exit }' "$file_path"
+    fi
+    ;;
+  package.json)
+    tr -d '\n\r' <"$file_path" |
+      sed -n 's/.*"engines"[[:space:]]*:[[:space:]]*{\([^}]*\)}.*/\1/p' |
+      sed -n 's/.*"node"[[:space:]]*:[[:space:]]*"\([^"]*\)".*/\1/p' |
+      sed -e 's/|.*//' -e 's/\([<>=~^]\)[[:space:]]*/\1/g' -e 's/^[[:space:]]*//' -e 's/[[:space:]]*$//' \
+        -e 's/[[:space:]][[:space:]]*/,/g' -e 's/\([<>=~^,]\)v/\1/g' -e 's/^v//' |
+      partial_version_to_constraint
+    ;;
+  *)
+    cat "$file_path"
+    ;;
+  esac
+}
+
+# Turns each partial version read from stdin, like 18, 3.11 or 18.x, into a
+# constraint on the versions starting with it. Other lines are kept as they
+# are.
+partial_version_to_constraint() {
+  sed -E -e 's/^([0-9]+(\.[0-9]+)?)(\.[xX*])+$/=\1/' -e 's/^([0-9]+(\.[0-9]+)?)$/=\1/'
+}
+
+# Prints the versions set for a plugin in a directory, the current directory
+# by default.
 get_preset_version_for() {
   local plugin_name=$1
-  local search_path
-  search_path=$(pwd)
+  local search_path=${2:-$PWD}
   local version_and_path
   version_and_path=$(find_versions "$plugin_name" "$search_path")
   local version
@@ -356,6 +1049,21 @@ get_preset_version_for() {
   printf "%s\\n" "$version"
 }
 
+# Prints a setting for a plugin, which is <key>_<plugin> when it is set and
+# <key> otherwise.
+get_plugin_config_value() {
+  local key=$1
+  local plugin_name=$2
+  local value
+
+  value=$(get_asdf_config_value "${key}_${plugin_name}")
+  if [ -z "$value" ]; then
+    value=$(get_asdf_config_value "$key")
+  fi
+
+  printf "%s\\n" "$value"
+}
+
 get_asdf_config_value_from_file() {
   local config_path=$1
   local key=$2
@@ -387,6 +1095,41 @@ get_asdf_config_value() {
     get_asdf_config_value_from_file "$default_config_path" "$key"
 }
 
+# Lists every key set in the default config file or in any config file that
+# overrides it.
+asdf_config_keys() {
+  local config_path=${ASDF_CONFIG_FILE:-"$HOME/.asdfrc"}
+  local default_config_path=${ASDF_CONFIG_DEFAULT_FILE:-"$(asdf_dir)/defaults"}
+  local local_config_path
+  local_config_path="$(find_file_upwards ".asdfrc")"
+
+  asdf_config_keys_in_files "$default_config_path" "$config_path" "$local_config_path"
+}
+
+asdf_config_keys_in_files() {
+  local file
+  for file in "$@"; do
+    if [ -f "$file" ]; then
+      grep -E "^\\s*[[:alnum:]_]+\\s*=" "$file" | sed -e 's/=.*$//' -e 's/[[:space:]]//g'
+    fi
+  done | awk '!seen[$0]++'
+}
+
+# Whether asdf reads a config key. Hook and per-plugin keys are named by the
+# user and are always accepted.
+is_known_config_key() {
+  local key=$1
+  local default_config_path=${ASDF_CONFIG_DEFAULT_FILE:-"$(asdf_dir)/defaults"}
+
+  case "$key" in
+  pre_* | post_* | latest_filter_* | fallback_to_system_* | missing_version_behavior_* | auto_reshim_*)
+    return 0
+    ;;
+  esac
+
+  asdf_config_keys_in_files "$default_config_path" | grep -qx "$key"
+}
+
 # Whether the plugin shortname repo needs to be synced
 # 0: if no sync needs to occur
 # 1: if sync needs to occur
@@ -403,12 +1146,21 @@ repository_needs_update() {
     update_file_name="repo-updated"
     # `find` outputs filename if it has not been modified in plugin_repository_last_check_duration setting.
     sync_required=$(find "$update_file_dir" -name "$update_file_name" -type f -mmin +"${plugin_repository_last_check_duration:-60}" -print)
+
+    # A repository without a record of its last sync is always stale
+    if [ ! -f "$update_file_dir/$update_file_name" ]; then
+      sync_required="yes"
+    fi
   fi
 
   [ "$sync_required" ]
 }
 
+# Clones the plugin short-name repository, or updates it once it is older than
+# plugin_repository_last_check_duration. Pass "refresh" to update it regardless.
+# An update that fails, for example when offline, falls back to the cached copy.
 initialize_or_update_repository() {
+  local refresh=$1
   local repository_url
   local repository_path
 
@@ -418,19 +1170,102 @@ initialize_or_update_repository() {
     exit 1
   fi
 
-  repository_url=$(asdf_repository_url)
-  repository_path=$(asdf_data_dir)/repository
+  repository_url=$(asdf_repository_url)
+  repository_path=$(asdf_data_dir)/repository
+
+  # Offline, the cached repository is used however old it is
+  if asdf_offline; then
+    if [ ! -d "$repository_path" ] || [ "$refresh" = "refresh" ]; then
+      require_online "sync the plugin repository"
+      exit 1
+    fi
+    return 0
+  fi
+
+  if [ ! -d "$repository_path" ]; then
+    printf "initializing plugin repository..."
+    plugin_git clone "$repository_url" "$repository_path"
+  elif [ "$refresh" = "refresh" ]; then
+    printf "updating plugin repository..."
+    update_repository "$repository_path"
+  elif repository_needs_update; then
+    if [ "$(get_asdf_config_value "stale_while_revalidate")" = "yes" ]; then
+      # Serve the stale index now and refresh it in the background
+      (ASDF_WARNINGS_FILE="" update_repository "$repository_path" &) >/dev/null 2>&1
+    else
+      printf "updating plugin repository..."
+      update_repository "$repository_path"
+    fi
+  fi
+
+  mkdir -p "$(asdf_data_dir)/tmp"
+  touch "$(asdf_data_dir)/tmp/repo-updated"
+}
+
+update_repository() {
+  local repository_path=$1
+  local update_output
+
+  if ! update_output=$(cd "$repository_path" && plugin_git fetch 2>&1 && git reset --hard origin/master 2>&1); then
+    printf "\\n"
+    asdf_warn "could not update the plugin repository, using the cached copy"
+    return 1
+  fi
+
+  printf "%s\\n" "$update_output"
+}
 
-  if [ ! -d "$repository_path" ]; then
-    printf "initializing plugin repository..."
-    git clone "$repository_url" "$repository_path"
-  elif repository_needs_update; then
-    printf "updating plugin repository..."
-    (cd "$repository_path" && git fetch && git reset --hard origin/master)
+# Runs git for a plugin or plugin repository operation. Credentials come from
+# the user's git configuration and, when set, the git_credential_helper
+# setting, so they never need to be part of a URL.
+plugin_git() {
+  local credential_helper
+  credential_helper=$(get_asdf_config_value "git_credential_helper")
+
+  if [ -n "$credential_helper" ]; then
+    with_proxy git -c "credential.helper=$credential_helper" "$@"
+  else
+    with_proxy git "$@"
   fi
+}
 
-  mkdir -p "$(asdf_data_dir)/tmp"
-  touch "$(asdf_data_dir)/tmp/repo-updated"
+# Runs a command that reaches the network through the proxy set with the proxy
+# setting or ASDF_PROXY, which git, curl and wget read from the standard proxy
+# variables. The no_proxy setting or ASDF_NO_PROXY lists the hosts and domains
+# reached directly. Without them the proxy variables are left as they are.
+with_proxy() {
+  local proxy no_proxy_hosts
+  proxy=${ASDF_PROXY:-$(get_asdf_config_value "proxy")}
+  no_proxy_hosts=${ASDF_NO_PROXY:-$(get_asdf_config_value "no_proxy")}
+
+  if [ -z "$proxy" ] && [ -z "$no_proxy_hosts" ]; then
+    "$@"
+    return
+  fi
+
+  (
+    if [ -n "$proxy" ]; then
+      export http_proxy=$proxy https_proxy=$proxy HTTPS_PROXY=$proxy all_proxy=$proxy ALL_PROXY=$proxy
+    fi
+    if [ -n "$no_proxy_hosts" ]; then
+      export no_proxy=$no_proxy_hosts NO_PROXY=$no_proxy_hosts
+    fi
+    "$@"
+  )
+}
+
+# Prints a URL without any password or token embedded in it, for use in
+# output, logs and hook environments. SSH user names are kept as they are not
+# secret and are part of how the repository is reached.
+redact_url() {
+  case "$1" in
+  ssh://* | git+ssh://* | ssh+git://*)
+    printf "%s\\n" "$1" | sed -E 's#^([^:]*://[^/@:]*):[^/@]*@#\1@#'
+    ;;
+  *)
+    printf "%s\\n" "$1" | sed -E 's#^([[:alpha:]][[:alnum:]+.-]*://)[^/@]*@#\1#'
+    ;;
+  esac
 }
 
 get_plugin_source_url() {
@@ -445,22 +1280,102 @@ get_plugin_source_url() {
 }
 
 find_tool_versions() {
-  find_file_upwards "$(version_file_name)"
+  if [ -n "${ASDF_TOOL_VERSIONS_FILE:-}" ]; then
+    printf "%s\\n" "$ASDF_TOOL_VERSIONS_FILE"
+  else
+    # shellcheck disable=SC2046
+    find_file_upwards $(version_file_names)
+  fi
+}
+
+# Reads versions from the given file only, instead of the version files found
+# from the current directory upwards and in $HOME. The file is exported so the
+# shims a command runs read it too.
+use_tool_versions_file() {
+  local file=$1
+
+  if [ ! -f "$file" ]; then
+    display_error "No such version file: $file"
+    exit 1
+  fi
+
+  export ASDF_TOOL_VERSIONS_FILE
+  ASDF_TOOL_VERSIONS_FILE="$(cd "$(dirname "$file")" && pwd)/$(basename "$file")"
 }
 
 find_file_upwards() {
-  local name="$1"
-  local search_path
-  search_path=$(pwd)
+  local name
+  local search_paths search_path
+  IFS=$'\n' read -rd '' -a search_paths <<<"$(upward_search_paths "$(pwd)")"
+  for search_path in "${search_paths[@]}"; do
+    for name in "$@"; do
+      if [ -f "$search_path/$name" ]; then
+        printf "%s\\n" "${search_path}/$name"
+        return 0
+      fi
+    done
+  done
+}
+
+# Prints the directories to look in for version and config files, starting at
+# the given path and walking up towards the root. The walk is limited by the
+# `search_max_depth` and `search_skip_paths` settings.
+upward_search_paths() {
+  local search_path=$1
+  local max_depth skip_paths
+  max_depth=$(get_search_max_depth)
+  skip_paths=$(get_upward_search_setting "ASDF_SEARCH_SKIP_PATHS" "search_skip_paths")
+
+  local depth=0
+  local skip_path skip_path_list
   while [ "$search_path" != "/" ]; do
-    if [ -f "$search_path/$name" ]; then
-      printf "%s\\n" "${search_path}/$name"
+    if [ -n "$max_depth" ] && [ "$depth" -gt "$max_depth" ]; then
       return 0
     fi
+
+    if [ -n "$skip_paths" ]; then
+      IFS=':' read -r -a skip_path_list <<<"$skip_paths"
+      for skip_path in "${skip_path_list[@]}"; do
+        if [ "${search_path%/}" = "${skip_path%/}" ]; then
+          return 0
+        fi
+      done
+    fi
+
+    printf "%s\\n" "$search_path"
     search_path=$(dirname "$search_path")
+    depth=$((depth + 1))
   done
 }
 
+# Prints the search_max_depth setting, or nothing when it is unset. A value
+# that is not a whole number is reported as a warning and treated as unset.
+get_search_max_depth() {
+  local max_depth
+  max_depth=$(get_upward_search_setting "ASDF_SEARCH_MAX_DEPTH" "search_max_depth")
+
+  if [ -n "$max_depth" ] && ! [[ "$max_depth" =~ ^[0-9]+$ ]]; then
+    asdf_warn "search_max_depth must be a whole number, ignoring '$max_depth'"
+    return 0
+  fi
+
+  printf "%s\\n" "$max_depth"
+}
+
+# The upward search limits and the version file names are read from the
+# environment or the user config file only, as the local .asdfrc is itself
+# found by searching upwards.
+get_upward_search_setting() {
+  local env_var=$1
+  local key=$2
+
+  if [ -n "${!env_var}" ]; then
+    printf "%s\\n" "${!env_var}"
+  else
+    get_asdf_config_value_from_file "${ASDF_CONFIG_FILE:-"$HOME/.asdfrc"}" "$key"
+  fi
+}
+
 resolve_symlink() {
   local symlink
   symlink="$1"
@@ -503,7 +1418,7 @@ list_plugin_bin_paths() {
       export ASDF_INSTALL_TYPE=$install_type
       export ASDF_INSTALL_VERSION=$version
       export ASDF_INSTALL_PATH=$install_path
-      "${plugin_path}/bin/list-bin-paths"
+      run_with_plugin_env_filter "${plugin_path}/bin/list-bin-paths"
     )
   else
     local space_separated_list_of_bin_paths="bin"
@@ -617,10 +1532,75 @@ is_executable() {
   return 1
 }
 
+# Prints "<shim> <plugin> <version>" for each "# asdf-plugin:" line of every
+# shim, read from the shim files themselves.
+scan_shims() {
+  local shims_path
+  shims_path="$(asdf_data_dir)/shims"
+
+  set -- "$shims_path"/*
+  if [ ! -e "$1" ]; then
+    return 0
+  fi
+
+  awk '/^# asdf-plugin: / { shim = FILENAME; sub(/.*\//, "", shim); print shim, $3, $4 }' "$@" 2>/dev/null
+}
+
+shim_index_path() {
+  printf "%s\\n" "$(asdf_data_dir)/shim-index"
+}
+
+# Rewrites the shim index from the shim files. The index is kept outside the
+# shims directory so that it is only trusted while it is newer than the
+# directory, which changes whenever a shim is added, rewritten or removed.
+update_shim_index() {
+  local index_path
+  index_path=$(shim_index_path)
+
+  # The index is only a cache of the shims, which a dry run leaves alone
+  if asdf_dry_run; then
+    return 0
+  fi
+
+  if [ ! -d "$(asdf_data_dir)/shims" ]; then
+    rm -f "$index_path"
+    return 0
+  fi
+
+  if scan_shims >"$index_path.$$" 2>/dev/null; then
+    mv -f "$index_path.$$" "$index_path"
+  else
+    rm -f "$index_path.$$"
+    return 1
+  fi
+}
+
+# Prints the shim index, refreshing it first if any shim changed since it was
+# written. Falls back to scanning the shims when the index can't be written.
+shim_index() {
+  local index_path shims_path
+  index_path=$(shim_index_path)
+  shims_path="$(asdf_data_dir)/shims"
+
+  if [ ! "$index_path" -nt "$shims_path" ]; then
+    update_shim_index 2>/dev/null
+  fi
+
+  if [ "$index_path" -nt "$shims_path" ]; then
+    cat "$index_path"
+  else
+    scan_shims
+  fi
+}
+
 plugin_shims() {
   local plugin_name=$1
   local full_version=$2
-  grep -lx "# asdf-plugin: $plugin_name $full_version" "$(asdf_data_dir)/shims"/* 2>/dev/null
+  local shims_path
+  shims_path="$(asdf_data_dir)/shims"
+
+  shim_index | awk -v plugin="$plugin_name" -v version="$full_version" -v dir="$shims_path" '
+    $2 == plugin && $3 == version && !seen[$1]++ { print dir "/" $1 }'
 }
 
 shim_plugin_versions() {
@@ -629,7 +1609,7 @@ shim_plugin_versions() {
   local shim_path
   shim_path="$(asdf_data_dir)/shims/${executable_name}"
   if [ -x "$shim_path" ]; then
-    grep "# asdf-plugin: " "$shim_path" 2>/dev/null | sed -e "s/# asdf-plugin: //" | uniq
+    shim_index | awk -v shim="$executable_name" '$1 == shim { print $2, $3 }' | uniq
   else
     printf "asdf: unknown shim %s\\n" "$executable_name"
     return 1
@@ -642,13 +1622,124 @@ shim_plugins() {
   local shim_path
   shim_path="$(asdf_data_dir)/shims/${executable_name}"
   if [ -x "$shim_path" ]; then
-    grep "# asdf-plugin: " "$shim_path" 2>/dev/null | sed -e "s/# asdf-plugin: //" | cut -d' ' -f 1 | uniq
+    shim_index | awk -v shim="$executable_name" '$1 == shim { print $2 }' | uniq
   else
     printf "asdf: unknown shim %s\\n" "$executable_name"
     return 1
   fi
 }
 
+# Prints the plugins a shim may run. When ASDF_<TOOL>_VERSION is set for some
+# of the plugins providing the shim, only those are considered, so an
+# override always selects its own plugin's executable rather than one from a
+# version file, and a version it names that is not installed is an error.
+# Overridden plugins are tried in alphabetical order.
+shim_candidate_plugins() {
+  local shim_name=$1
+  local plugins
+  IFS=$'\n' read -rd '' -a plugins <<<"$(shim_plugins "$shim_name")"
+
+  local overridden_plugins=() plugin_name
+  if ! asdf_strict; then
+    for plugin_name in "${plugins[@]}"; do
+      if [ -n "$(get_version_from_env "$plugin_name")" ]; then
+        overridden_plugins+=("$plugin_name")
+      fi
+    done
+  fi
+
+  if [ "${#overridden_plugins[@]}" -gt 0 ]; then
+    printf "%s\\n" "${overridden_plugins[@]}" | sort
+  else
+    printf "%s\\n" "${plugins[@]}"
+  fi
+}
+
+# Replaces one version of a plugin in a version file. The other versions on
+# the plugin's line and the rest of the file are left as they are, down to
+# the spacing between them.
+replace_tool_version() {
+  local file=$1
+  local plugin_name=$2
+  local old_version=$3
+  local new_version=$4
+
+  # shellcheck disable=SC2016
+  rewrite_tool_versions_file "$file" -v plugin="$plugin_name" -v old="$old_version" -v new="$new_version" '
+    $1 == plugin && !done {
+      rest = $0
+      head = ""
+      fields = 0
+      while (match(rest, /[^ \t]+/)) {
+        token = substr(rest, RSTART, RLENGTH)
+        if (token ~ /^#/) break
+        if (fields++ > 0 && token == old) {
+          $0 = head substr(rest, 1, RSTART - 1) new substr(rest, RSTART + RLENGTH)
+          done = 1
+          break
+        }
+        head = head substr(rest, 1, RSTART + RLENGTH - 1)
+        rest = substr(rest, RSTART + RLENGTH)
+      }
+    }
+    { print }
+  '
+}
+
+# Sets the versions of a plugin in a version file. The first line of the
+# plugin keeps its indentation and comment, and the other lines, including
+# comments and blank lines, stay as they are. A plugin that isn't in the file
+# yet is added at the end.
+set_tool_versions() {
+  local file=$1
+  local plugin_name=$2
+  shift 2
+  local versions="$*"
+
+  if [ -f "$file" ] && strip_tool_version_comments "$file" | awk -v plugin="$plugin_name" '$1 == plugin { found = 1 } END { exit !found }'; then
+    # shellcheck disable=SC2016
+    rewrite_tool_versions_file "$file" -v plugin="$plugin_name" -v versions="$versions" '
+      !done {
+        line = $0
+        sub(/#.*/, "", line)
+        split(line, fields, " ")
+        if (fields[1] == plugin) {
+          match($0, /^[ \t]*/)
+          indent = substr($0, 1, RLENGTH)
+          comment = ""
+          if (match($0, /[ \t]*#/)) comment = substr($0, RSTART)
+          print indent plugin " " versions comment
+          done = 1
+          next
+        }
+      }
+      { print }
+    '
+  else
+    # Add a trailing newline at the end of the file if missing
+    [[ -f "$file" && -n "$(tail -c1 "$file")" ]] && printf '\n' >>"$file"
+
+    # Add a new version line to the end of the file
+    printf "%s %s\\n" "$plugin_name" "$versions" >>"$file"
+  fi
+}
+
+# Runs an awk program over a version file and writes its output back into the
+# same file, so that symlinks and permissions are kept.
+rewrite_tool_versions_file() {
+  local file=$1
+  shift
+
+  local tmp_file="$file.$$"
+  if awk "$@" "$file" >"$tmp_file"; then
+    cat "$tmp_file" >"$file"
+    rm -f "$tmp_file"
+  else
+    rm -f "$tmp_file"
+    return 1
+  fi
+}
+
 strip_tool_version_comments() {
   local tool_version_path="$1"
   # Use sed to strip comments from the tool version file
@@ -661,16 +1752,274 @@ strip_tool_version_comments() {
   sed '/^[[:blank:]]*#/d;s/#.*//;s/[[:blank:]]*$//' "$tool_version_path"
 }
 
+# Runs a plugin script and records the invocation in the plugin script trace
+# that `asdf doctor --trace-last` reports on. The script finds the download
+# helper in ASDF_FETCH and is given the proxy settings.
+run_plugin_script() {
+  local script_path=$1
+  shift
+
+  local started_at=$SECONDS
+  local started_at_ms=""
+  if asdf_debug_enabled; then
+    started_at_ms=$(asdf_now_ms)
+    asdf_debug "running ${script_path#"$(asdf_data_dir)/plugins/"}${*:+ $*} with $(plugin_script_env | tr '\n' ' ')"
+  fi
+
+  local exit_code=0
+  ASDF_FETCH="$(asdf_dir)/bin/private/asdf-fetch" with_proxy run_with_plugin_env_filter "$script_path" "$@" || exit_code=$?
+
+  if [ -n "$started_at_ms" ]; then
+    asdf_debug "${script_path#"$(asdf_data_dir)/plugins/"} exited with $exit_code after $(($(asdf_now_ms) - started_at_ms))ms"
+  fi
+
+  record_plugin_script_trace "$script_path" "$exit_code" "$((SECONDS - started_at))" "$@"
+  return "$exit_code"
+}
+
+# Prints the ASDF_ variables passed to plugin scripts, one per line, leaving
+# out any that look like credentials and asdf's own debug settings. The user
+# and password in ASDF_PROXY are removed.
+plugin_script_env() {
+  local env_line
+  env | grep "^ASDF_" | grep -v "^ASDF_DEBUG" | grep -viE "^[^=]*(token|secret|password|key)[^=]*=" | sort |
+    while IFS= read -r env_line; do
+      case "$env_line" in
+      ASDF_PROXY=*)
+        printf "ASDF_PROXY=%s\\n" "$(redact_url "${env_line#ASDF_PROXY=}")"
+        ;;
+      *)
+        printf "%s\\n" "$env_line"
+        ;;
+      esac
+    done
+}
+
+# Appends one tab separated line to the trace file, keeping only the most
+# recent plugin_trace_size lines. Only ASDF_ variables are recorded from the
+# environment, separated by the ASCII unit separator, and never ones that look
+# like credentials.
+record_plugin_script_trace() {
+  local script_path=$1
+  local exit_code=$2
+  local duration=$3
+  shift 3
+
+  local trace_size
+  trace_size=$(get_asdf_config_value "plugin_trace_size")
+  if ! [[ "$trace_size" =~ ^[0-9]+$ ]] || [ "$trace_size" -eq 0 ]; then
+    return 0
+  fi
+
+  local plugin_name
+  plugin_name=$(basename "$(dirname "$(dirname "$script_path")")")
+
+  local args=""
+  if [ "$#" -gt 0 ]; then
+    args=$(printf "%q " "$@")
+  fi
+
+  local env_summary
+  env_summary=$(plugin_script_env | tr '\n' '\037')
+
+  local trace_file
+  trace_file="$(asdf_data_dir)/trace"
+  printf "%s\\t%s\\t%s\\t%s\\t%s\\t%s\\t%s\\t%s\\n" "$(date -u +%Y-%m-%dT%H:%M:%SZ)" "$plugin_name" \
+    "bin/$(basename "$script_path")" "$exit_code" "$duration" "$(pwd)" "${args% }" "${env_summary%$'\037'}" >>"$trace_file"
+
+  # Parallel installs append to the trace while it is trimmed, so it is moved
+  # aside under a name of its own first and the lines kept are appended back,
+  # after any recorded in the meantime
+  if [ "$(wc -l <"$trace_file")" -gt "$trace_size" ]; then
+    local rotated_file="$trace_file.${BASHPID:-$$}"
+    mv -f "$trace_file" "$rotated_file" 2>/dev/null &&
+      tail -n "$trace_size" "$rotated_file" >>"$trace_file"
+    rm -f "$rotated_file"
+  fi
+}
+
+# Prints the names of exported variables that plugin scripts and hooks must
+# not see: those matching a plugin_env_denylist pattern and no
+# plugin_env_allowlist pattern. An allowlist without a denylist denies
+# everything else, except HOME, TMPDIR and the proxy variables. PATH and the
+# ASDF_ variables asdf passes to plugins are never removed.
+plugin_env_filtered_names() {
+  local denylist allowlist
+  denylist=$(get_asdf_config_value "plugin_env_denylist")
+  allowlist=$(get_asdf_config_value "plugin_env_allowlist")
+  if [ -z "$denylist" ] && [ -z "$allowlist" ]; then
+    return 0
+  fi
+
+  local deny_patterns allow_patterns
+  IFS=' ' read -r -a deny_patterns <<<"${denylist:-*}"
+  IFS=' ' read -r -a allow_patterns <<<"$allowlist"
+
+  local name pattern denied
+  while IFS= read -r name; do
+    case "$name" in
+    PATH | ASDF_*) continue ;;
+    esac
+    if [ -z "$denylist" ]; then
+      case "$name" in
+      HOME | TMPDIR | http_proxy | https_proxy | all_proxy | no_proxy | HTTP_PROXY | HTTPS_PROXY | ALL_PROXY | NO_PROXY) continue ;;
+      esac
+    fi
+
+    denied=false
+    for pattern in "${deny_patterns[@]}"; do
+      # shellcheck disable=SC2053
+      if [[ "$name" == $pattern ]]; then
+        denied=true
+        break
+      fi
+    done
+    if [ "$denied" = "false" ]; then
+      continue
+    fi
+
+    for pattern in "${allow_patterns[@]}"; do
+      # shellcheck disable=SC2053
+      if [[ "$name" == $pattern ]]; then
+        denied=false
+        break
+      fi
+    done
+    if [ "$denied" = "true" ]; then
+      printf "%s\\n" "$name"
+    fi
+  done <<<"$(compgen -e)"
+}
+
+# Runs a plugin script or hook without the variables excluded by
+# plugin_env_denylist. When nothing is excluded it runs in the current shell,
+# as it always has.
+run_with_plugin_env_filter() {
+  local filtered_names
+  IFS=$'\n' read -rd '' -a filtered_names <<<"$(plugin_env_filtered_names)"
+
+  if [ -z "${filtered_names[*]}" ]; then
+    "$@"
+  else
+    (
+      unset "${filtered_names[@]}" 2>/dev/null
+      "$@"
+    )
+  fi
+}
+
+# Prints the project a command runs in: the directory of the closest version
+# file, or the current directory when there is none.
+current_project_path() {
+  local tool_versions_path
+  tool_versions_path=$(find_tool_versions)
+  if [ -n "$tool_versions_path" ]; then
+    dirname "$tool_versions_path"
+  else
+    pwd
+  fi
+}
+
+# Appends "<epoch> <project> <plugin> <version> <shim>", tab separated, to the
+# shim metrics log when shim_metrics is enabled. Recording never stops the
+# shim from running.
+record_shim_metric() {
+  local shim_name=$1
+  local plugin_name=$2
+  local version=$3
+
+  if [ "$(get_asdf_config_value "shim_metrics")" != "yes" ]; then
+    return 0
+  fi
+
+  printf "%s\\t%s\\t%s\\t%s\\t%s\\n" "$(date +%s)" "$(current_project_path)" "$plugin_name" "$version" "$shim_name" \
+    >>"$(asdf_data_dir)/metrics" 2>/dev/null || true
+}
+
+# Reshims a version when one of its bin directories changed since the last
+# check, so executables that tools such as `npm install --global` add get
+# shims without running `asdf reshim`. Shims only call it when auto_reshim is
+# enabled for the plugin. The last check is recorded in
+# $ASDF_DATA_DIR/reshim-stamps. A failing reshim never stops the shim from
+# running.
+auto_reshim() {
+  local plugin_name=$1
+  local full_version=$2
+
+  if [ "$full_version" = "system" ] || [[ "$full_version" == "path:"* ]]; then
+    return 0
+  fi
+
+  local stamp_path
+  stamp_path="$(asdf_data_dir)/reshim-stamps/$plugin_name/$full_version"
+
+  local exec_paths
+  IFS=$'\n' read -rd '' -a exec_paths <<<"$(list_plugin_exec_paths "$plugin_name" "$full_version")"
+  if [ -f "$stamp_path" ] && [ -z "$(find "${exec_paths[@]}" -maxdepth 0 -newer "$stamp_path" 2>/dev/null)" ]; then
+    return 0
+  fi
+
+  # The stamp is written first, so changes made while reshimming are caught
+  # the next time
+  mkdir -p "$(dirname "$stamp_path")" 2>/dev/null && touch "$stamp_path" 2>/dev/null
+  asdf_debug "$plugin_name $full_version: bin directories changed, reshimming"
+  (
+    # shellcheck source=lib/commands/reshim.bash
+    . "$(asdf_dir)/lib/commands/reshim.bash"
+    reshim_command "$plugin_name" "$full_version"
+  ) >/dev/null 2>&1 || true
+}
+
+# Whether the shim runs a command that installs executables, listed in
+# auto_reshim_commands, so it is reshimmed after the command exits.
+is_auto_reshim_command() {
+  local plugin_name=$1
+  local shim_name=$2
+
+  local command_name
+  for command_name in $(get_plugin_config_value "auto_reshim_commands" "$plugin_name"); do
+    [ "$command_name" = "$shim_name" ] && return 0
+  done
+  return 1
+}
+
+# Runs the command set for a hook with the remaining arguments as $1, $2 and
+# so on. A hook set to the path of an executable file runs that file
+# directly. Other hooks run in the asdf shell, or with hook_shell -c when it
+# is set, so they also work where bash is not the shell users write them for.
 asdf_run_hook() {
   local hook_name=$1
   local hook_cmd
   hook_cmd="$(get_asdf_config_value "$hook_name")"
-  if [ -n "$hook_cmd" ]; then
+  if [ -z "$hook_cmd" ]; then
+    return 0
+  fi
+
+  local hook_path=${hook_cmd/#\~\//$HOME/}
+  local hook_shell=()
+  if [[ "$hook_path" != */* ]] || [ ! -f "$hook_path" ] || [ ! -x "$hook_path" ]; then
+    hook_path=""
+    IFS=' ' read -r -a hook_shell <<<"$(get_asdf_config_value "hook_shell")"
+  fi
+
+  if asdf_dry_run; then
+    dry_run_note "run hook $hook_name: $hook_cmd"
+    return 0
+  fi
+
+  if [ -n "$hook_path" ]; then
+    asdf_debug "running hook $hook_name: $hook_path"
+    run_with_plugin_env_filter "$hook_path" "${@:2}"
+  elif [ "${#hook_shell[@]}" -gt 0 ]; then
+    asdf_debug "running hook $hook_name with ${hook_shell[*]}: $hook_cmd"
+    run_with_plugin_env_filter "${hook_shell[@]}" -c "$hook_cmd" "$hook_name" "${@:2}"
+  else
+    asdf_debug "running hook $hook_name: $hook_cmd"
     asdf_hook_fun() {
       unset asdf_hook_fun
       ev'al' "$hook_cmd" # ignore banned command just here
     }
-    asdf_hook_fun "${@:2}"
+    run_with_plugin_env_filter asdf_hook_fun "${@:2}"
   fi
 }
 
@@ -682,7 +2031,7 @@ get_shim_versions() {
 
 preset_versions() {
   shim_name=$1
-  shim_plugin_versions "${shim_name}" | cut -d' ' -f 1 | uniq | xargs -IPLUGIN bash -c ". $(asdf_dir)/lib/utils.bash; printf \"%s %s\\n\" PLUGIN \$(get_preset_version_for PLUGIN)"
+  shim_candidate_plugins "${shim_name}" | xargs -IPLUGIN bash -c ". $(asdf_dir)/lib/utils.bash; printf \"%s %s\\n\" PLUGIN \$(get_preset_version_for PLUGIN)"
 }
 
 select_from_preset_version() {
@@ -705,13 +2054,14 @@ select_version() {
   # Note that multiple plugin versions can be set for a single plugin.
   # These are separated by a space. e.g. python 3.7.2 2.7.15
   # For each plugin/version pair, we check if it is present in the shim
-  local search_path
-  search_path=$(pwd)
+  # The versions are looked up from the given directory, the current
+  # directory by default.
+  local search_path=${2:-$PWD}
   local shim_versions
   IFS=$'\n' read -rd '' -a shim_versions <<<"$(get_shim_versions "$shim_name")"
 
   local plugins
-  IFS=$'\n' read -rd '' -a plugins <<<"$(shim_plugins "$shim_name")"
+  IFS=$'\n' read -rd '' -a plugins <<<"$(shim_candidate_plugins "$shim_name")"
 
   for plugin_name in "${plugins[@]}"; do
     local version_and_path
@@ -721,7 +2071,21 @@ select_version() {
     version_and_path=$(find_versions "$plugin_name" "$search_path")
     IFS='|' read -r version_string _path <<<"$version_and_path"
     IFS=' ' read -r -a usable_plugin_versions <<<"$version_string"
+    # Later versions on the line are fallbacks for when the first is missing
+    if asdf_strict; then
+      usable_plugin_versions=("${usable_plugin_versions[@]:0:1}")
+    fi
     for plugin_version in "${usable_plugin_versions[@]}"; do
+      # A constraint picks the newest version providing the shim that
+      # satisfies it
+      if is_version_constraint "$plugin_version"; then
+        plugin_version=$(printf "%s\\n" "${shim_versions[@]}" |
+          awk -v plugin="$plugin_name" '$1 == plugin { print $2 }' |
+          filter_versions_by_constraint "$plugin_version" |
+          sort_versions |
+          tail -n 1)
+        [ -n "$plugin_version" ] || continue
+      fi
       for plugin_and_version in "${shim_versions[@]}"; do
         local plugin_shim_name
         local plugin_shim_version
@@ -740,6 +2104,87 @@ select_version() {
   done
 }
 
+# Picks the version to run a shim with when no version is set for any of the
+# plugins providing it, following missing_version_behavior, or
+# missing_version_behavior_<plugin> for the first plugin providing it: "error"
+# picks none, "system" the command found outside of asdf and
+# "latest_installed" the newest installed version providing the shim. The
+# choice is reported as a warning so it is never silent.
+select_missing_version() {
+  local shim_name=$1
+
+  local plugins
+  IFS=$'\n' read -rd '' -a plugins <<<"$(shim_plugins "$shim_name")"
+
+  local behavior
+  behavior=$(get_plugin_config_value "missing_version_behavior" "${plugins[0]}")
+  asdf_strict && behavior=error
+
+  case "$behavior" in
+  "" | error)
+    return 0
+    ;;
+  system | latest_installed) ;;
+  *)
+    asdf_warn "unknown missing_version_behavior \"$behavior\", expected error, system or latest_installed"
+    return 0
+    ;;
+  esac
+
+  # A version that is set but not installed is still an error
+  local plugin_name
+  for plugin_name in "${plugins[@]}"; do
+    if [ -n "$(get_preset_version_for "$plugin_name")" ]; then
+      return 0
+    fi
+  done
+
+  plugin_name=${plugins[0]}
+  local version
+  if [ "$behavior" = "system" ]; then
+    if ! PATH=$(remove_path_from_path "$PATH" "$(asdf_data_dir)/shims") command -v "$shim_name" >/dev/null; then
+      return 0
+    fi
+    version=system
+  else
+    version=$(shim_plugin_versions "$shim_name" | awk -v plugin="$plugin_name" '$1 == plugin { print $2 }' | sort_versions | tail -n 1)
+  fi
+
+  if [ -n "$version" ]; then
+    asdf_warn "No version is set for $plugin_name, using $version (missing_version_behavior = $behavior)"
+    printf "%s %s\\n" "$plugin_name" "$version"
+  fi
+}
+
+# Picks the system version of the first plugin providing the shim whose set
+# version is not installed, when fallback_to_system is enabled for it and the
+# command exists outside of asdf. The fallback is reported as a warning.
+select_system_fallback() {
+  local shim_name=$1
+  if asdf_strict; then
+    return 0
+  fi
+
+  local plugins
+  IFS=$'\n' read -rd '' -a plugins <<<"$(shim_candidate_plugins "$shim_name")"
+
+  local plugin_name version
+  for plugin_name in "${plugins[@]}"; do
+    version=$(get_preset_version_for "$plugin_name")
+    if [ -z "$version" ] || [ "$(get_plugin_config_value "fallback_to_system" "$plugin_name")" != "yes" ]; then
+      continue
+    fi
+
+    if ! PATH=$(remove_path_from_path "$PATH" "$(asdf_data_dir)/shims") command -v "$shim_name" >/dev/null; then
+      return 0
+    fi
+
+    asdf_warn "$plugin_name $version is not installed, using system (fallback_to_system = yes)"
+    printf "%s system\\n" "$plugin_name"
+    return 0
+  done
+}
+
 with_shim_executable() {
   local shim_name
   shim_name=$(basename "$1")
@@ -757,6 +2202,14 @@ with_shim_executable() {
     selected_version="$(select_from_preset_version "$shim_name")"
   fi
 
+  if [ -z "$selected_version" ]; then
+    selected_version="$(select_missing_version "$shim_name")"
+  fi
+
+  if [ -z "$selected_version" ]; then
+    selected_version="$(select_system_fallback "$shim_name")"
+  fi
+
   if [ -n "$selected_version" ]; then
     local plugin_name
     local full_version
@@ -771,11 +2224,16 @@ with_shim_executable() {
 
       executable_path=$(PATH=$path command -v "$shim_name")
 
+      install_path=$(find_install_path "$plugin_name" "$full_version")
       if [ -x "${plugin_path}/bin/exec-path" ]; then
-        install_path=$(find_install_path "$plugin_name" "$full_version")
         executable_path=$(get_custom_executable_path "${plugin_path}" "${install_path}" "${executable_path:-${shim_name}}")
       fi
 
+      # Tell the command, and anything it runs, which version the shim picked
+      export ASDF_PLUGIN=$plugin_name
+      export ASDF_VERSION=$full_version
+      export ASDF_INSTALL_PATH=$install_path
+
       "$shim_exec" "$plugin_name" "$full_version" "$executable_path"
     }
 
@@ -788,9 +2246,13 @@ with_shim_executable() {
     preset_plugin_versions=()
     local closest_tool_version
     closest_tool_version=$(find_tool_versions)
+    if [ -z "$closest_tool_version" ]; then
+      # Suggest the global file when no project file exists yet
+      closest_tool_version="$HOME/$(version_file_name)"
+    fi
 
     local shim_plugins
-    IFS=$'\n' read -rd '' -a shim_plugins <<<"$(shim_plugins "$shim_name")"
+    IFS=$'\n' read -rd '' -a shim_plugins <<<"$(shim_candidate_plugins "$shim_name")"
     for shim_plugin in "${shim_plugins[@]}"; do
       local shim_versions
       local version_string
@@ -819,6 +2281,39 @@ with_shim_executable() {
   return 126
 }
 
+# Prints the argument as a JSON string literal.
+json_string() {
+  local value=$1
+  value=${value//\\/\\\\}
+  value=${value//\"/\\\"}
+  value=${value//$'\t'/\\t}
+  value=${value//$'\n'/\\n}
+
+  # Any other control characters are escaped by their code point.
+  if [[ "$value" == *[[:cntrl:]]* ]]; then
+    local code char
+    for ((code = 1; code < 32; code++)); do
+      printf -v char "\\x$(printf "%02x" "$code")"
+      value=${value//"$char"/$(printf '\\u%04x' "$code")}
+    done
+  fi
+
+  printf '"%s"' "$value"
+}
+
+# Prints the arguments as a JSON array of strings.
+json_string_array() {
+  local item
+  local separator=""
+  printf "["
+  for item in "$@"; do
+    printf "%s" "$separator"
+    json_string "$item"
+    separator=","
+  done
+  printf "]"
+}
+
 substitute() {
   # Use Bash substitution rather than sed as it will handle escaping of all
   # strings for us.
@@ -0,0 +1,145 @@
+#!/usr/bin/env bash
+
+# Times version resolution, reshim and shim dispatch against generated
+# fixtures: 50 plugins with 10 executables each (500 shims) and a project
+# whose .tool-versions is 30 directories above the working directory.
+#
+#   scripts/bench.bash [--runs <count>] [--save <file>] [--baseline <file>]
+#
+# --save writes the median of each benchmark to a file, which a later run can
+# be compared to with --baseline.
+
+set -euo pipefail
+
+runs=5
+save_path=""
+baseline_path=""
+
+usage() {
+  printf "usage: scripts/bench.bash [--runs <count>] [--save <file>] [--baseline <file>]\\n" >&2
+  exit 1
+}
+
+while [[ $# -gt 0 ]]; do
+  case $1 in
+  --runs | --save | --baseline)
+    if [ "$#" -lt 2 ]; then
+      printf "%s needs a value\\n" "$1" >&2
+      usage
+    fi
+    case $1 in
+    --runs) runs=$2 ;;
+    --save) save_path=$2 ;;
+    --baseline) baseline_path=$2 ;;
+    esac
+    shift 2
+    ;;
+  *)
+    usage
+    ;;
+  esac
+done
+
+repo_dir=$(cd "$(dirname "$0")/.." && pwd)
+# shellcheck source=lib/utils.bash
+. "$repo_dir/lib/utils.bash"
+base_dir=$(mktemp -dt asdf-bench.XXXX)
+trap 'rm -rf "$base_dir"' EXIT
+
+export HOME="$base_dir/home"
+export ASDF_DIR="$repo_dir"
+export ASDF_DATA_DIR="$HOME/.asdf"
+export ASDF_CONFIG_FILE="$HOME/.asdfrc"
+export PATH="$ASDF_DIR/bin:$ASDF_DATA_DIR/shims:$PATH"
+unset ASDF_DEFAULT_TOOL_VERSIONS_FILENAME ASDF_DEBUG ASDF_STRICT
+
+plugin_count=50
+executable_count=10
+tree_depth=30
+
+create_fixtures() {
+  mkdir -p "$ASDF_DATA_DIR/plugins" "$ASDF_DATA_DIR/installs" "$HOME/project"
+  touch "$ASDF_CONFIG_FILE"
+
+  local plugin_number executable_number plugin_name bin_path
+  for ((plugin_number = 1; plugin_number <= plugin_count; plugin_number++)); do
+    plugin_name=$(printf "tool-%02d" "$plugin_number")
+    cp -r "$repo_dir/test/fixtures/dummy_plugin" "$ASDF_DATA_DIR/plugins/$plugin_name"
+
+    bin_path="$ASDF_DATA_DIR/installs/$plugin_name/1.0.0/bin"
+    mkdir -p "$bin_path"
+    for ((executable_number = 1; executable_number <= executable_count; executable_number++)); do
+      printf "#!/usr/bin/env bash\\nexit 0\\n" >"$bin_path/$plugin_name-$executable_number"
+      chmod +x "$bin_path/$plugin_name-$executable_number"
+    done
+
+    printf "%s 1.0.0\\n" "$plugin_name" >>"$HOME/project/.tool-versions"
+  done
+
+  local depth
+  deep_dir="$HOME/project"
+  for ((depth = 1; depth <= tree_depth; depth++)); do
+    deep_dir="$deep_dir/d$depth"
+  done
+  mkdir -p "$deep_dir"
+}
+
+# Runs a command the given number of times and prints the name, median and
+# minimum duration in milliseconds. The setup command runs before every run
+# and is not timed.
+bench() {
+  local name=$1
+  local setup=$2
+  shift 2
+
+  local durations=() run started_at
+  for ((run = 1; run <= runs; run++)); do
+    $setup
+    started_at=$(asdf_now_ms)
+    (cd "$deep_dir" && "$@") >/dev/null 2>&1
+    durations+=($(($(asdf_now_ms) - started_at)))
+  done
+
+  printf "%s\\n" "${durations[@]}" | sort -n | awk -v name="$name" '
+    { values[NR] = $1 }
+    END { printf "%s\t%s\t%s\n", name, values[int((NR + 1) / 2)], values[1] }'
+}
+
+no_setup() { :; }
+
+remove_shims() {
+  rm -rf "$ASDF_DATA_DIR/shims" "$ASDF_DATA_DIR/shim-index"
+}
+
+run_benchmarks() {
+  remove_shims
+  asdf reshim >/dev/null
+
+  bench "current (50 plugins, depth 30)" no_setup asdf current
+  bench "which (depth 30)" no_setup asdf which tool-25-5
+  bench "shim dispatch (depth 30)" no_setup tool-25-5
+  bench "reshim unchanged (500 shims)" no_setup asdf reshim
+  bench "reshim from scratch (500 shims)" remove_shims asdf reshim
+}
+
+create_fixtures
+results=$(run_benchmarks)
+
+if [ -n "$save_path" ]; then
+  cut -f 1,2 <<<"$results" >"$save_path"
+fi
+
+if [ -n "$baseline_path" ]; then
+  awk -F '\t' '
+    NR == FNR { baseline[$1] = $2; next }
+    FNR == 1 { printf "%-34s%10s%10s%10s%10s\n", "BENCHMARK", "MEDIAN", "MIN", "BASELINE", "CHANGE" }
+    {
+      change = "-"
+      if (($1 in baseline) && baseline[$1] > 0) change = sprintf("%+.0f%%", ($2 - baseline[$1]) * 100 / baseline[$1])
+      printf "%-34s%8sms%8sms%10s%10s\n", $1, $2, $3, ($1 in baseline ? baseline[$1] "ms" : "-"), change
+    }' "$baseline_path" - <<<"$results"
+else
+  awk -F '\t' '
+    NR == 1 { printf "%-34s%10s%10s\n", "BENCHMARK", "MEDIAN", "MIN" }
+    { printf "%-34s%8sms%8sms\n", $1, $2, $3 }' <<<"$results"
+fi
@@ -0,0 +1,70 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  setup_asdf_dir
+  ASDF_FETCH="$ASDF_BIN/private/asdf-fetch"
+  printf "fetched\n" >"$BASE_DIR/source"
+  SOURCE_SHA256=$(sha256sum "$BASE_DIR/source" 2>/dev/null || shasum -a 256 "$BASE_DIR/source")
+  SOURCE_SHA256=${SOURCE_SHA256%% *}
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "asdf-fetch downloads a url to a file" {
+  run "$ASDF_FETCH" "file://$BASE_DIR/source" "$BASE_DIR/target"
+  [ "$status" -eq 0 ]
+  [ "$(cat "$BASE_DIR/target")" = "fetched" ]
+}
+
+@test "asdf-fetch downloads a file with a matching checksum" {
+  run "$ASDF_FETCH" "file://$BASE_DIR/source" "$BASE_DIR/target" --sha256 "$SOURCE_SHA256"
+  [ "$status" -eq 0 ]
+  [ "$(cat "$BASE_DIR/target")" = "fetched" ]
+}
+
+@test "asdf-fetch removes a download with a different checksum" {
+  run "$ASDF_FETCH" "file://$BASE_DIR/source" "$BASE_DIR/target" --sha256 0000
+  [ "$status" -eq 1 ]
+  [ "$output" = "Checksum mismatch for file://$BASE_DIR/source: expected 0000, got $SOURCE_SHA256" ]
+  [ ! -e "$BASE_DIR/target" ]
+  [ ! -e "$BASE_DIR/target.part" ]
+}
+
+@test "asdf-fetch retries a failed download fetch_retries times" {
+  echo "fetch_retries = 1" >"$HOME/.asdfrc"
+
+  run "$ASDF_FETCH" "file://$BASE_DIR/missing" "$BASE_DIR/target"
+  [ "$status" -eq 1 ]
+  [[ "$output" = *"Could not download file://$BASE_DIR/missing, retrying in 2 seconds (1 of 1)"* ]]
+  [[ "$output" = *"Could not download file://$BASE_DIR/missing" ]]
+  [ ! -e "$BASE_DIR/target" ]
+}
+
+@test "asdf-fetch fails with an empty or missing checksum" {
+  run "$ASDF_FETCH" "file://$BASE_DIR/source" "$BASE_DIR/target" --sha256
+  [ "$status" -eq 1 ]
+  [ "$output" = "--sha256 needs a checksum" ]
+
+  run "$ASDF_FETCH" "file://$BASE_DIR/source" "$BASE_DIR/target" --sha256 ""
+  [ "$status" -eq 1 ]
+  [ ! -f "$BASE_DIR/target" ]
+}
+
+@test "asdf-fetch fails without a url and a file" {
+  run "$ASDF_FETCH" "file://$BASE_DIR/source"
+  [ "$status" -eq 1 ]
+  [ "$output" = "usage: \$ASDF_FETCH <url> <file> [--sha256 <checksum>]" ]
+}
+
+@test "plugin scripts find asdf-fetch in ASDF_FETCH" {
+  install_dummy_plugin
+  printf '#!/usr/bin/env bash\n"$ASDF_FETCH" "file://%s/source" "$ASDF_DOWNLOAD_PATH/source"\n' "$BASE_DIR" >"$ASDF_DIR/plugins/dummy/bin/download"
+
+  run asdf install dummy 1.0.0 --keep-download
+  [ "$status" -eq 0 ]
+  [ "$(cat "$ASDF_DIR/downloads/dummy/1.0.0/source")" = "fetched" ]
+}
@@ -0,0 +1,52 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  setup_asdf_dir
+  install_dummy_plugin
+  install_dummy_legacy_plugin
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "cache clear removes the cached versions of a plugin" {
+  run asdf list-all dummy
+  run asdf list-all legacy-dummy
+
+  run asdf cache clear dummy
+  [ "$status" -eq 0 ]
+  [ "$output" = "Cleared the cached versions of dummy" ]
+  [ ! -f "$ASDF_DIR/cache/list-all/dummy" ]
+  [ -f "$ASDF_DIR/cache/list-all/legacy-dummy" ]
+}
+
+@test "cache clear without a name removes the cached versions of every plugin" {
+  run asdf list-all dummy
+  run asdf list-all legacy-dummy
+
+  run asdf cache clear
+  [ "$status" -eq 0 ]
+  [ "$output" = "Cleared the cached versions of all plugins" ]
+  [ ! -d "$ASDF_DIR/cache/list-all" ]
+}
+
+@test "cache clear fails for a plugin that is not installed" {
+  run asdf cache clear nope
+  [ "$status" -eq 1 ]
+  [ "$output" = "No such plugin: nope" ]
+}
+
+@test "plugin update clears the cached versions of the plugin" {
+  install_mock_plugin_repo "dummy"
+  rm -rf "$ASDF_DIR/plugins/dummy"
+  run asdf plugin add "dummy" "${BASE_DIR}/repo-dummy"
+  run asdf list-all dummy
+  [ -f "$ASDF_DIR/cache/list-all/dummy" ]
+
+  run asdf plugin update dummy
+  [ "$status" -eq 0 ]
+  [ ! -f "$ASDF_DIR/cache/list-all/dummy" ]
+}
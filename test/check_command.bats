@@ -0,0 +1,119 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  setup_asdf_dir
+  install_dummy_plugin
+
+  PROJECT_DIR=$HOME/project
+  mkdir -p $PROJECT_DIR
+  cd $PROJECT_DIR
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "check reports no problems for a well formed file" {
+  printf '# tools\n\ndummy 1.0 2.0 # pinned\n' >.tool-versions
+
+  run asdf check
+  [ "$status" -eq 0 ]
+  [ "$output" = "No problems found in $PROJECT_DIR/.tool-versions" ]
+}
+
+@test "check reports the line, column and corrected line of a problem" {
+  printf 'dummy 1.0\nnodejs=20.11.1\n' >.tool-versions
+
+  run asdf check
+  [ "$status" -eq 1 ]
+  [ "${lines[0]}" = "$PROJECT_DIR/.tool-versions:2:7: expected a space between nodejs and its version" ]
+  [ "${lines[1]}" = "    nodejs=20.11.1" ]
+  [ "${lines[2]}" = "    did you mean \`nodejs 20.11.1\`?" ]
+}
+
+@test "check reports lines that are not read" {
+  printf '  dummy 1.0\nruby\t3.1.2\nnodejs 20\r\n' >.tool-versions
+
+  run asdf check
+  [ "$status" -eq 1 ]
+  [[ "$output" == *".tool-versions:1:1: the line is indented, so it is not read"* ]]
+  [[ "$output" == *".tool-versions:2:5: a tab follows ruby, so the line is not read"* ]]
+  [[ "$output" == *".tool-versions:3:10: the line ends with a carriage return, which is read as part of the last version"* ]]
+}
+
+@test "check reports invalid names, missing versions and duplicate entries" {
+  printf 'dummy 1.0 1.0\nnode.js 20\nruby\ndummy 2.0\n' >.tool-versions
+
+  run asdf check
+  [ "$status" -eq 1 ]
+  [[ "$output" == *".tool-versions:1:11: 1.0 is listed twice for dummy"* ]]
+  [[ "$output" == *".tool-versions:2:1: node.js is not a valid tool name"* ]]
+  [[ "$output" == *".tool-versions:3:5: no version is set for ruby"* ]]
+  [[ "$output" == *".tool-versions:4:1: dummy is already set on line 1"* ]]
+}
+
+@test "check --fix normalizes whitespace and merges duplicate entries" {
+  printf '# tools\n  dummy=1.0   # pinned\r\nruby\t3.1.2\n\ndummy 2.0 1.0 # also\n' >.tool-versions
+
+  run asdf check --fix
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "Fixed $PROJECT_DIR/.tool-versions" ]
+  [ "$(cat .tool-versions)" = $'# tools\ndummy 1.0 2.0   # pinned\nruby 3.1.2\n\n# also' ]
+}
+
+@test "check --fix reports the problems it could not fix" {
+  printf 'dummy  1.0\nruby\n' >.tool-versions
+
+  run asdf check --fix
+  [ "$status" -eq 1 ]
+  [[ "$output" == *".tool-versions:2:5: no version is set for ruby"* ]]
+  [ "$(cat .tool-versions)" = $'dummy 1.0\nruby' ]
+}
+
+@test "check --installed reports plugins and versions that are not installed" {
+  install_dummy_version "1.0.0"
+  printf 'dummy 1.0.0 ^1 2.0.0 system\nnodejs 20\n' >.tool-versions
+
+  run asdf check --installed
+  [ "$status" -eq 1 ]
+  [ "${lines[0]}" = "$PROJECT_DIR/.tool-versions:1:16: dummy 2.0.0 is not installed" ]
+  [ "${lines[1]}" = "    dummy 1.0.0 ^1 2.0.0 system" ]
+  [ "${lines[2]}" = "$PROJECT_DIR/.tool-versions:2:1: nodejs is not an installed plugin" ]
+  [ "${lines[3]}" = "    nodejs 20" ]
+}
+
+@test "check --installed passes when every version is installed" {
+  install_dummy_version "1.0.0"
+  echo 'dummy 1.0.0' >.tool-versions
+
+  run asdf check --installed
+  [ "$status" -eq 0 ]
+  [ "$output" = "No problems found in $PROJECT_DIR/.tool-versions" ]
+}
+
+@test "check --installed reports versions of legacy version files that are not installed" {
+  install_dummy_version "1.0.0"
+  echo 'legacy_version_file = yes' >$HOME/.asdfrc
+  echo 'dummy 1.0.0' >$HOME/.tool-versions
+  echo '3.0.0' >.dummy-version
+
+  run asdf check --installed
+  [ "$status" -eq 1 ]
+  [ "$output" = "$PROJECT_DIR/.dummy-version: dummy 3.0.0 is not installed" ]
+}
+
+@test "check reads the given file" {
+  printf 'dummy\t1.0\n' >$HOME/release.tool-versions
+
+  run asdf check $HOME/release.tool-versions
+  [ "$status" -eq 1 ]
+  [[ "$output" == "$HOME/release.tool-versions:1:6: a tab follows dummy"* ]]
+}
+
+@test "check fails without a version file" {
+  run asdf check
+  [ "$status" -eq 1 ]
+  [ "$output" = "No .tool-versions file found in $PROJECT_DIR or its parents" ]
+}
@@ -0,0 +1,68 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  setup_asdf_dir
+  install_dummy_plugin
+  install_dummy_version "1.0"
+  install_dummy_version "1.1"
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "__complete completes command names" {
+  run asdf __complete "pl"
+  [ "$status" -eq 0 ]
+  [[ "$output" = *"plugin-add"* ]]
+  [[ "$output" = *"plugin-update"* ]]
+  [[ "$output" != *"install"* ]]
+  [[ "$output" != *"__complete"* ]]
+}
+
+@test "__complete completes sub-command names" {
+  run asdf __complete plugin "u"
+  [ "$status" -eq 0 ]
+  [ "$output" = "update" ]
+}
+
+@test "__complete completes installed plugin names" {
+  run asdf __complete uninstall ""
+  [ "$status" -eq 0 ]
+  [ "$output" = "dummy" ]
+}
+
+@test "__complete completes installed versions" {
+  run asdf __complete uninstall dummy ""
+  [ "$status" -eq 0 ]
+  [ "$output" = "$(printf "%s\n" 1.0 1.1)" ]
+}
+
+@test "__complete completes installed versions and system for local" {
+  run asdf __complete local dummy "1.1"
+  [ "$status" -eq 0 ]
+  [ "$output" = "1.1" ]
+
+  run asdf __complete local dummy "sys"
+  [ "$output" = "system" ]
+}
+
+@test "__complete completes available versions for install" {
+  run asdf __complete install dummy "2"
+  [ "$status" -eq 0 ]
+  [ "$output" = "2.0.0" ]
+}
+
+@test "__complete treats space separated sub-commands like dashed ones" {
+  run asdf __complete plugin update "--s"
+  [ "$status" -eq 0 ]
+  [ "$output" = "--stash" ]
+}
+
+@test "__complete completes flags" {
+  run asdf __complete latest dummy "--in"
+  [ "$status" -eq 0 ]
+  [ "$output" = "--installed-only" ]
+}
@@ -0,0 +1,45 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  setup_asdf_dir
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "completion command prints the bash completion script" {
+  run asdf completion bash
+  [ "$status" -eq 0 ]
+  [ "$output" = "$(cat "$BATS_TEST_DIRNAME/../completions/asdf.bash")" ]
+}
+
+@test "completion command prints the zsh completion script" {
+  run asdf completion zsh
+  [ "$status" -eq 0 ]
+  [ "$output" = "$(cat "$BATS_TEST_DIRNAME/../completions/_asdf")" ]
+}
+
+@test "completion command prints the fish completion script" {
+  run asdf completion fish
+  [ "$status" -eq 0 ]
+  [ "$output" = "$(cat "$BATS_TEST_DIRNAME/../completions/asdf.fish")" ]
+}
+
+@test "completion command fails for an unknown shell" {
+  run asdf completion tcsh
+  [ "$status" -eq 1 ]
+  [ "$output" = "usage: asdf completion <bash|zsh|fish>" ]
+}
+
+@test "bash completion completes every command" {
+  . "$BATS_TEST_DIRNAME/../completions/asdf.bash"
+  COMP_WORDS=(asdf "")
+  COMP_CWORD=1
+  _asdf
+  [[ " ${COMPREPLY[*]} " = *" completion "* ]]
+  [[ " ${COMPREPLY[*]} " = *" shimexec "* ]]
+  [[ " ${COMPREPLY[*]} " = *" plugin-info "* ]]
+}
@@ -27,6 +27,52 @@ teardown() {
   [ "$output" = "$expected" ]
 }
 
+@test "current warns once about a search_max_depth that is not a whole number" {
+  cd $PROJECT_DIR
+  echo 'dummy 1.1.0' >$PROJECT_DIR/.tool-versions
+  echo "search_max_depth = deep" >$HOME/.asdfrc
+
+  run asdf current "dummy"
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "dummy           1.1.0           $PROJECT_DIR/.tool-versions" ]
+  [ "${lines[1]}" = "warning: search_max_depth must be a whole number, ignoring 'deep'" ]
+  [ "${#lines[@]}" -eq 2 ]
+}
+
+@test "current --tool-versions reads versions from the given file only" {
+  cd $PROJECT_DIR
+  echo 'dummy 1.1.0' >$PROJECT_DIR/.tool-versions
+  echo 'dummy 1.2.0' >$HOME/release.tool-versions
+
+  run asdf current dummy --tool-versions ../release.tool-versions
+  [ "$status" -eq 0 ]
+  [ "$output" = "dummy           1.2.0           $HOME/release.tool-versions" ]
+}
+
+@test "current --tool-versions does not fall back to other version files" {
+  cd $PROJECT_DIR
+  echo 'dummy 1.1.0' >$PROJECT_DIR/.tool-versions
+  touch $HOME/release.tool-versions
+
+  run asdf current dummy --tool-versions $HOME/release.tool-versions
+  [ "$status" -eq 126 ]
+}
+
+@test "current --tool-versions fails for a missing file" {
+  run asdf current --tool-versions $HOME/missing.tool-versions
+  [ "$status" -eq 1 ]
+  [ "$output" = "No such version file: $HOME/missing.tool-versions" ]
+}
+
+@test "current does not shorten paths when the output is not a terminal" {
+  cd $PROJECT_DIR
+  echo 'dummy 1.1.0' >$PROJECT_DIR/.tool-versions
+
+  COLUMNS=40 run asdf current dummy
+  [ "$status" -eq 0 ]
+  [ "$output" = "dummy           1.1.0           $PROJECT_DIR/.tool-versions" ]
+}
+
 @test "current should handle long version name" {
   cd $PROJECT_DIR
   echo "dummy nightly-2000-01-01" >>$PROJECT_DIR/.tool-versions
@@ -58,12 +104,68 @@ teardown() {
   [ "$output" = "$expected" ]
 }
 
+@test "current should ignore the legacy file and environment variable with ASDF_STRICT" {
+  cd $PROJECT_DIR
+  echo 'legacy_version_file = yes' >$HOME/.asdfrc
+  echo '1.2.0' >>$PROJECT_DIR/.dummy-version
+  echo 'dummy 1.1.0' >>$PROJECT_DIR/.tool-versions
+  expected="dummy           1.1.0           $PROJECT_DIR/.tool-versions"
+
+  run env ASDF_STRICT=1 ASDF_DUMMY_VERSION=1.0.0 asdf current "dummy"
+  [ "$status" -eq 0 ]
+  [ "$output" = "$expected" ]
+}
+
+@test "current should read a .nvmrc for a nodejs plugin without legacy filenames" {
+  install_mock_plugin "nodejs"
+  install_mock_plugin_version "nodejs" "18.10.1"
+  install_mock_plugin_version "nodejs" "20.1.0"
+  rm "$ASDF_DIR/plugins/nodejs/bin/list-legacy-filenames" "$ASDF_DIR/plugins/nodejs/bin/parse-legacy-file" "$ASDF_DIR/plugins/nodejs/bin/get-version-from-legacy-file"
+  cd $PROJECT_DIR
+  echo 'legacy_version_file = yes' >$HOME/.asdfrc
+  echo 'v18' >$PROJECT_DIR/.nvmrc
+  expected="nodejs          =18             $PROJECT_DIR/.nvmrc"
+
+  run asdf current "nodejs"
+  [ "$status" -eq 0 ]
+  [ "$output" = "$expected" ]
+}
+
+@test "current should read engines.node from a package.json as a constraint" {
+  install_mock_plugin "nodejs"
+  install_mock_plugin_version "nodejs" "18.10.1"
+  install_mock_plugin_version "nodejs" "20.1.0"
+  rm "$ASDF_DIR/plugins/nodejs/bin/list-legacy-filenames" "$ASDF_DIR/plugins/nodejs/bin/parse-legacy-file" "$ASDF_DIR/plugins/nodejs/bin/get-version-from-legacy-file"
+  cd $PROJECT_DIR
+  echo 'legacy_version_file = yes' >$HOME/.asdfrc
+  printf '{\n  "engines": {\n    "node": ">= 18.3 < 20"\n  }\n}\n' >$PROJECT_DIR/package.json
+  expected="nodejs          >=18.3,<20      $PROJECT_DIR/package.json"
+
+  run asdf current "nodejs"
+  [ "$status" -eq 0 ]
+  [ "$output" = "$expected" ]
+}
+
+@test "current should read the channel of a rust-toolchain.toml" {
+  install_mock_plugin "rust"
+  install_mock_plugin_version "rust" "1.70.0"
+  rm "$ASDF_DIR/plugins/rust/bin/list-legacy-filenames" "$ASDF_DIR/plugins/rust/bin/parse-legacy-file" "$ASDF_DIR/plugins/rust/bin/get-version-from-legacy-file"
+  cd $PROJECT_DIR
+  echo 'legacy_version_file = yes' >$HOME/.asdfrc
+  printf '[toolchain]\nchannel = "1.70.0"\n' >$PROJECT_DIR/rust-toolchain.toml
+  expected="rust            1.70.0          $PROJECT_DIR/rust-toolchain.toml"
+
+  run asdf current "rust"
+  [ "$status" -eq 0 ]
+  [ "$output" = "$expected" ]
+}
+
 # TODO: Need to fix plugin error as well
 @test "current should error when the plugin doesn't exist" {
   expected="No such plugin: foobar"
 
   run asdf current "foobar"
-  [ "$status" -eq 1 ]
+  [ "$status" -eq 2 ]
   [ "$output" = "$expected" ]
 }
 
@@ -142,3 +244,96 @@ foobar          1.0.0           $PROJECT_DIR/.tool-versions"
   [ "$status" -eq 0 ]
   [ "$output" = "$expected" ]
 }
+
+@test "current --json should print the current version as JSON" {
+  cd $PROJECT_DIR
+  echo 'dummy 1.1.0' >>$PROJECT_DIR/.tool-versions
+  expected="{\"plugin\":\"dummy\",\"version\":\"1.1.0\",\"source\":\"$PROJECT_DIR/.tool-versions\",\"installed\":true}"
+
+  run asdf current dummy --json
+  [ "$status" -eq 0 ]
+  [ "$output" = "$expected" ]
+}
+
+@test "current --json should print a JSON array for all plugins" {
+  cd $PROJECT_DIR
+  echo 'dummy 9.9.9' >>$PROJECT_DIR/.tool-versions
+  expected="[{\"plugin\":\"dummy\",\"version\":\"9.9.9\",\"source\":\"$PROJECT_DIR/.tool-versions\",\"installed\":false}]"
+
+  run asdf current --json
+  [ "$status" -eq 0 ]
+  [ "$output" = "$expected" ]
+}
+
+@test "current --explain should list the sources checked up to the one that set the version" {
+  mkdir $PROJECT_DIR/app
+  echo 'dummy 1.1.0' >$PROJECT_DIR/.tool-versions
+  echo 'other 1.0' >$PROJECT_DIR/app/.tool-versions
+  cd $PROJECT_DIR/app
+
+  run asdf current dummy --explain
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "dummy" ]
+  [ "${lines[1]}" = "  not set       ASDF_DUMMY_VERSION environment variable" ]
+  [ "${lines[2]}" = "  not set       $PROJECT_DIR/app/.tool-versions" ]
+  [ "${lines[3]}" = "  1.1.0         $PROJECT_DIR/.tool-versions" ]
+  [ "${lines[4]}" = "dummy 1.1.0 is set by $PROJECT_DIR/.tool-versions" ]
+  [ "${#lines[@]}" -eq 5 ]
+}
+
+@test "current --explain should show the environment variable winning" {
+  echo 'dummy 1.1.0' >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run env ASDF_DUMMY_VERSION=1.2.0 asdf current dummy --explain
+  [ "$status" -eq 0 ]
+  [ "${lines[1]}" = "  1.2.0         ASDF_DUMMY_VERSION environment variable" ]
+  [ "${lines[2]}" = "dummy 1.2.0 is set by ASDF_DUMMY_VERSION" ]
+}
+
+@test "current --explain should check the home directory before the version resolvers" {
+  mkdir -p "$ASDF_DIR/resolvers"
+  printf '#!/usr/bin/env bash\necho 1.2.0\n' >"$ASDF_DIR/resolvers/company"
+  chmod +x "$ASDF_DIR/resolvers/company"
+  echo "version_resolvers = company" >$HOME/.asdfrc
+  cd $PROJECT_DIR
+
+  run asdf current dummy --explain
+  [ "$status" -eq 0 ]
+  [ "${lines[2]}" = "  no file       $PROJECT_DIR/.tool-versions" ]
+  [ "${lines[-3]}" = "  no file       $HOME/.tool-versions" ]
+  [ "${lines[-2]}" = "  1.2.0         version_resolvers = company" ]
+  [ "${lines[-1]}" = "dummy 1.2.0 is set by $ASDF_DIR/resolvers/company" ]
+}
+
+@test "current --explain should check legacy files and honor ASDF_STRICT like current" {
+  cd $PROJECT_DIR
+  echo 'legacy_version_file = yes' >$HOME/.asdfrc
+  echo '1.2.0' >$PROJECT_DIR/.dummy-version
+
+  run env ASDF_DUMMY_VERSION=1.0.0 asdf current dummy --explain
+  [ "$status" -eq 0 ]
+  [ "${lines[1]}" = "  1.0.0         ASDF_DUMMY_VERSION environment variable" ]
+
+  run asdf current dummy --explain
+  [ "$status" -eq 0 ]
+  [ "${lines[2]}" = "  no file       $PROJECT_DIR/.tool-versions" ]
+  [ "${lines[3]}" = "  1.2.0         $PROJECT_DIR/.dummy-version" ]
+  [ "${lines[4]}" = "dummy 1.2.0 is set by $PROJECT_DIR/.dummy-version" ]
+
+  echo 'dummy 1.1.0' >$PROJECT_DIR/.tool-versions
+  run env ASDF_STRICT=1 ASDF_DUMMY_VERSION=1.0.0 asdf current dummy --explain
+  [ "$status" -eq 0 ]
+  [ "${lines[1]}" = "  skipped       ASDF_DUMMY_VERSION environment variable (ASDF_STRICT)" ]
+  [ "${lines[2]}" = "  1.1.0         $PROJECT_DIR/.tool-versions" ]
+  [ "${lines[3]}" = "dummy 1.1.0 is set by $PROJECT_DIR/.tool-versions" ]
+}
+
+@test "current --explain should say when no version is set" {
+  cd $PROJECT_DIR
+
+  run asdf current dummy --explain
+  [ "$status" -eq 0 ]
+  [ "${lines[2]}" = "  no file       $PROJECT_DIR/.tool-versions" ]
+  [ "${lines[${#lines[@]} - 1]}" = "No version is set for dummy" ]
+}
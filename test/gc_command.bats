@@ -0,0 +1,112 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  setup_asdf_dir
+  install_dummy_plugin
+
+  mkdir -p "$ASDF_DIR/tmp/install-old" "$ASDF_DIR/tmp/install-new"
+  mkdir -p "$ASDF_DIR/downloads/dummy/1.0.0" "$ASDF_DIR/downloads/dummy/2.0.0"
+  touch -t 202001010000 "$ASDF_DIR/tmp/install-old" "$ASDF_DIR/downloads/dummy/1.0.0"
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "gc command removes temp dirs older than a day by default" {
+  run asdf gc
+  [ "$status" -eq 0 ]
+  [ "$output" = "Removed $ASDF_DIR/tmp/install-old" ]
+  [ ! -d "$ASDF_DIR/tmp/install-old" ]
+  [ -d "$ASDF_DIR/tmp/install-new" ]
+  [ -d "$ASDF_DIR/downloads/dummy/1.0.0" ]
+}
+
+@test "gc command removes downloads older than download_max_age_days" {
+  echo "download_max_age_days = 30" >"$HOME/.asdfrc"
+
+  run asdf gc
+  [ "$status" -eq 0 ]
+  [ ! -d "$ASDF_DIR/downloads/dummy/1.0.0" ]
+  [ -d "$ASDF_DIR/downloads/dummy/2.0.0" ]
+}
+
+@test "gc command removes the oldest downloads above download_cache_max_size_mb" {
+  echo "download_cache_max_size_mb = 1" >"$HOME/.asdfrc"
+  head -c 700000 /dev/zero >"$ASDF_DIR/downloads/dummy/1.0.0/archive"
+  head -c 700000 /dev/zero >"$ASDF_DIR/downloads/dummy/2.0.0/archive"
+  touch -t 202001010000 "$ASDF_DIR/downloads/dummy/1.0.0/archive" "$ASDF_DIR/downloads/dummy/1.0.0"
+
+  run asdf gc
+  [ "$status" -eq 0 ]
+  [ ! -d "$ASDF_DIR/downloads/dummy/1.0.0" ]
+  [ -d "$ASDF_DIR/downloads/dummy/2.0.0" ]
+}
+
+@test "gc command keeps temp dirs and downloads an install changed in the last hour" {
+  echo "tmp_max_age_days = 0" >"$HOME/.asdfrc"
+  echo "download_max_age_days = 0" >>"$HOME/.asdfrc"
+  echo "download_cache_max_size_mb = 0" >>"$HOME/.asdfrc"
+  touch "$ASDF_DIR/tmp/install-old/partial" "$ASDF_DIR/downloads/dummy/1.0.0/partial"
+  touch -t 202001010000 "$ASDF_DIR/tmp/install-new" "$ASDF_DIR/downloads/dummy/2.0.0"
+
+  run asdf gc
+  [ "$status" -eq 0 ]
+  [ -d "$ASDF_DIR/tmp/install-old" ]
+  [ -d "$ASDF_DIR/downloads/dummy/1.0.0" ]
+  [ ! -d "$ASDF_DIR/tmp/install-new" ]
+  [ ! -d "$ASDF_DIR/downloads/dummy/2.0.0" ]
+}
+
+@test "gc command with --dry-run removes nothing" {
+  echo "download_max_age_days = 30" >"$HOME/.asdfrc"
+
+  run asdf gc --dry-run
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"Would remove $ASDF_DIR/tmp/install-old"* ]]
+  [[ "$output" == *"Would remove $ASDF_DIR/downloads/dummy/1.0.0"* ]]
+  [ -d "$ASDF_DIR/tmp/install-old" ]
+  [ -d "$ASDF_DIR/downloads/dummy/1.0.0" ]
+}
+
+@test "install command cleans up old downloads after installing" {
+  echo "download_max_age_days = 30" >"$HOME/.asdfrc"
+
+  run asdf install dummy 1.1.0
+  [ "$status" -eq 0 ]
+  [ ! -d "$ASDF_DIR/downloads/dummy/1.0.0" ]
+  [ ! -d "$ASDF_DIR/tmp/install-old" ]
+}
+
+@test "gc command removes the session files of shells that have exited" {
+  sh -c 'exit 0' &
+  local exited_pid=$!
+  wait "$exited_pid"
+  mkdir -p "$ASDF_DIR/sessions"
+  touch "$ASDF_DIR/sessions/$$" "$ASDF_DIR/sessions/$exited_pid"
+
+  run asdf gc
+  [ "$status" -eq 0 ]
+  [[ "$output" = *"Removed $ASDF_DIR/sessions/$exited_pid"* ]]
+  [ -f "$ASDF_DIR/sessions/$$" ]
+}
+
+@test "gc command fails with unknown arguments" {
+  run asdf gc --everything
+  [ "$status" -eq 1 ]
+  [ "$output" = "usage: asdf gc [--dry-run]" ]
+}
+
+@test "gc command removes shim metrics older than shim_metrics_max_age_days" {
+  echo "shim_metrics_max_age_days = 30" >"$HOME/.asdfrc"
+  printf "1000\t%s\tdummy\t1.0.0\tdummy\n" "$HOME" >"$ASDF_DIR/metrics"
+  printf "%s\t%s\tdummy\t2.0.0\tdummy\n" "$(date +%s)" "$HOME" >>"$ASDF_DIR/metrics"
+
+  run asdf gc
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"Removed 1 shim metrics from $ASDF_DIR/metrics"* ]]
+  [ "$(wc -l <"$ASDF_DIR/metrics")" -eq 1 ]
+  grep "2.0.0" "$ASDF_DIR/metrics"
+}
@@ -25,3 +25,42 @@ teardown() {
   [ "$status" -eq 0 ]
   # TODO: Assert asdf info output is printed
 }
+
+@test "info --paths should print resolved paths and config values with their source" {
+  cd $PROJECT_DIR
+
+  run asdf info --paths
+
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"data_dir	$ASDF_DIR	default"* ]]
+  [[ "$output" == *"shims	$ASDF_DIR/shims	default"* ]]
+  [[ "$output" == *"config.legacy_version_file	no	default"* ]]
+}
+
+@test "info --paths should report config values set in a config file" {
+  cd $PROJECT_DIR
+  printf "legacy_version_file = yes\\n" >$HOME/.asdfrc
+
+  run asdf info --paths
+
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"config.legacy_version_file	yes	file"* ]]
+}
+
+@test "info should warn about unknown config keys" {
+  cd $PROJECT_DIR
+  printf "legacy_verison_file = yes\\npre_asdf_install_dummy = true\\n" >$HOME/.asdfrc
+
+  run asdf info --paths
+
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"warning: unknown config key legacy_verison_file"* ]]
+  [[ "$output" != *"unknown config key pre_asdf_install_dummy"* ]]
+}
+
+@test "info_command does not warn about documented config keys without a default" {
+  echo "search_max_depth = 2" >"$HOME/.asdfrc"
+  run asdf info --paths
+  [ "$status" -eq 0 ]
+  [[ "$output" != *"warning: unknown config key"* ]]
+}
@@ -0,0 +1,64 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  setup_asdf_dir
+  install_dummy_plugin
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "init bash prints the shell setup and the completions" {
+  run asdf init bash
+  [ "$status" -eq 0 ]
+  [[ "${lines[0]}" = "export ASDF_DIR="* ]]
+  [[ "${lines[2]}" = "case \":\$PATH:\" in "* ]]
+  [ "${lines[4]}" = ". \"\$ASDF_DIR/lib/asdf.sh\"" ]
+  [ "${lines[5]}" = ". \"\$ASDF_DIR/completions/asdf.bash\"" ]
+  [ "${#lines[@]}" -eq 6 ]
+}
+
+@test "init loads the completions from a directory that needs quoting" {
+  asdf_dir="$BATS_TMPDIR/it's \$HOME"
+  mkdir -p "$asdf_dir"
+  cp -R "$(dirname "$BATS_TEST_DIRNAME")/bin" "$(dirname "$BATS_TEST_DIRNAME")/lib" "$(dirname "$BATS_TEST_DIRNAME")/completions" "$asdf_dir/"
+
+  run bash -c "eval \"\$(\"\$1/bin/asdf\" init bash)\"; complete -p asdf" _ "$asdf_dir"
+  rm -rf "$asdf_dir"
+  [ "$status" -eq 0 ]
+  [[ "$output" = "complete "*" asdf" ]]
+}
+
+@test "init --no-completions leaves out the completions" {
+  run asdf init zsh --no-completions
+  [ "$status" -eq 0 ]
+  [[ "$output" != *"fpath"* ]]
+}
+
+@test "init nu prints the variables and PATH" {
+  run asdf init nu
+  [ "$status" -eq 0 ]
+  [[ "${lines[0]}" = "\$env.ASDF_DIR = "* ]]
+  [[ "${lines[2]}" = "\$env.PATH = (\$env.PATH | "* ]]
+  [ "${#lines[@]}" -eq 3 ]
+}
+
+@test "init fails for an unknown shell" {
+  run asdf init tcsh
+  [ "$status" -eq 1 ]
+  [ "$output" = "usage: asdf init <bash|zsh|fish|nu|pwsh> [--no-completions] [--command-not-found]" ]
+}
+
+@test "init --command-not-found suggests reshimming a version that provides the command" {
+  run asdf install dummy 1.0
+  touch $ASDF_DIR/installs/dummy/1.0/bin/unshimmed
+  chmod +x $ASDF_DIR/installs/dummy/1.0/bin/unshimmed
+
+  run bash -c "eval \"\$(asdf init bash --command-not-found)\"; unshimmed"
+  [ "$status" -eq 127 ]
+  [ "${lines[0]}" = "unshimmed: command not found" ]
+  [ "${lines[1]}" = "dummy 1.0 provides it but has no shim for it, run \`asdf reshim dummy 1.0\`" ]
+}
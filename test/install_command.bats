@@ -52,6 +52,31 @@ teardown() {
   [ "$status" -eq 0 ]
 }
 
+@test "install_command sets ASDF_CONCURRENCY from the concurrency setting" {
+  echo "concurrency = 3" >$HOME/.asdfrc
+  run asdf install dummy 1.0.0
+  [ "$status" -eq 0 ]
+  run grep "ASDF_CONCURRENCY=3" $ASDF_DIR/installs/dummy/1.0.0/env
+  [ "$status" -eq 0 ]
+}
+
+@test "install_command prefers the ASDF_CONCURRENCY variable to the concurrency setting" {
+  echo "concurrency = 3" >$HOME/.asdfrc
+  ASDF_CONCURRENCY=5 run asdf install dummy 1.0.0
+  [ "$status" -eq 0 ]
+  run grep "ASDF_CONCURRENCY=5" $ASDF_DIR/installs/dummy/1.0.0/env
+  [ "$status" -eq 0 ]
+}
+
+@test "install_command warns about an invalid concurrency setting and uses the number of cores" {
+  echo "concurrency = lots" >$HOME/.asdfrc
+  run asdf install dummy 1.0.0
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"warning: concurrency is lots, which is neither auto nor a positive number, using the number of cores"* ]]
+  run grep "ASDF_CONCURRENCY=[1-9]" $ASDF_DIR/installs/dummy/1.0.0/env
+  [ "$status" -eq 0 ]
+}
+
 @test "install_command without arguments should work in directory containing whitespace" {
   WHITESPACE_DIR="$PROJECT_DIR/whitespace\ dir"
   mkdir -p "$WHITESPACE_DIR"
@@ -196,6 +221,162 @@ teardown() {
   [ $(cat $ASDF_DIR/installs/dummy/1.2.0/version) = "1.2.0" ]
 }
 
+@test "install_command with only name installs every version listed in .tool-versions" {
+  echo 'dummy 1.0.0 1.2.0' >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+  run asdf install dummy 1.0.0
+
+  run asdf install dummy
+  [ "$status" -eq 0 ]
+  [ "$output" = "dummy 1.0.0 is already installed
+
+dummy versions:
+  1.0.0: installed
+  1.2.0: installed" ]
+  [ $(cat $ASDF_DIR/installs/dummy/1.2.0/version) = "1.2.0" ]
+}
+
+@test "install_command with only name installs the other listed versions when one fails" {
+  echo 'dummy 1.0.0 other-dummy 1.2.0' >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf install dummy
+  [ "$status" -eq 1 ]
+  [[ "$output" == *"dummy versions:
+  1.0.0: installed
+  other-dummy: failed
+  1.2.0: installed"* ]]
+  [[ "$output" == *"Install failed for 1 of 3:
+  dummy other-dummy"* ]]
+  [ -d $ASDF_DIR/installs/dummy/1.0.0 ]
+  [ -d $ASDF_DIR/installs/dummy/1.2.0 ]
+}
+
+@test "install_command with --jobs installs multiple tool versions in parallel" {
+  echo 'dummy 1.0.0 1.2.0 1.3.0' >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf install --jobs 3
+  [ "$status" -eq 0 ]
+
+  [ $(cat $ASDF_DIR/installs/dummy/1.0.0/version) = "1.0.0" ]
+  [ $(cat $ASDF_DIR/installs/dummy/1.2.0/version) = "1.2.0" ]
+  [ $(cat $ASDF_DIR/installs/dummy/1.3.0/version) = "1.3.0" ]
+  [ $(grep -c "# asdf-plugin: dummy" $ASDF_DIR/shims/dummy) -eq 3 ]
+}
+
+@test "install_command with --jobs keeps the plugin script trace whole while trimming it" {
+  echo "plugin_trace_size = 2" >$HOME/.asdfrc
+  echo 'dummy 1.0.0 1.1.0 1.2.0 1.3.0 2.0.0' >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf install --jobs 5
+  [ "$status" -eq 0 ]
+
+  [ -z "$(find "$ASDF_DIR" -maxdepth 1 -name "trace.*")" ]
+  [ "$(wc -l <"$ASDF_DIR/trace")" -ge 2 ]
+  [ -z "$(awk -F '\t' 'NF != 8' "$ASDF_DIR/trace")" ]
+}
+
+@test "install_command with --jobs fails when any parallel install fails" {
+  echo 'dummy 1.0.0 other-dummy' >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf install --jobs 2
+  [ "$status" -eq 1 ]
+  [ $(cat $ASDF_DIR/installs/dummy/1.0.0/version) = "1.0.0" ]
+  [ ! -d $ASDF_DIR/installs/dummy/other-dummy ]
+}
+
+@test "install_command continues after a failed install and lists the failures" {
+  echo 'dummy other-dummy 1.0.0' >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf install --jobs 1
+  [ "$status" -eq 1 ]
+  [[ "$output" = *"Install failed for 1 of 2:
+  dummy other-dummy" ]]
+  [ $(cat $ASDF_DIR/installs/dummy/1.0.0/version) = "1.0.0" ]
+}
+
+@test "install_command with --fail-fast stops at the first failed install" {
+  echo 'dummy other-dummy 1.0.0' >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf install --jobs 1 --fail-fast
+  [ "$status" -eq 1 ]
+  [[ "$output" = *"Install failed for 1 of 1:
+  dummy other-dummy
+Stopped after the first failure" ]]
+  [ ! -d $ASDF_DIR/installs/dummy/1.0.0 ]
+}
+
+@test "install_command stops at the first failed install when fail_fast is yes" {
+  echo 'fail_fast = yes' >$HOME/.asdfrc
+  echo 'dummy other-dummy 1.0.0' >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf install --jobs 1
+  [ "$status" -eq 1 ]
+  [ ! -d $ASDF_DIR/installs/dummy/1.0.0 ]
+}
+
+@test "install_command with --jobs prefixes plugin output with the tool and version" {
+  echo 'dummy 1.0.0 other-dummy' >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf install --jobs 2
+  [[ "$output" == *"[dummy other-dummy] Dummy couldn't install version: other-dummy (on purpose)"* ]]
+}
+
+@test "install_command reports progress when ASDF_PROGRESS is set" {
+  ASDF_PROGRESS=1 run asdf install dummy 1.0.0
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "Downloading dummy 1.0.0" ]
+  [ "${lines[1]}" = "Installing dummy 1.0.0" ]
+  [[ "${lines[2]}" == "Installed dummy 1.0.0 in "* ]]
+}
+
+@test "install_command does not report progress when output is not a terminal" {
+  run asdf install dummy 1.0.0
+  [ "$status" -eq 0 ]
+  [ "$output" = "" ]
+}
+
+@test "install_command fails when --jobs is not a positive integer" {
+  run asdf install --jobs 0
+  [ "$status" -eq 1 ]
+  [ "$output" = "--jobs must be a positive integer" ]
+}
+
+@test "install_command fails when --jobs has no value" {
+  run asdf install --jobs
+  [ "$status" -eq 1 ]
+  [ "$output" = "--jobs must be a positive integer" ]
+}
+
+@test "with_install_lock releases the lock when the command exits" {
+  . "$(dirname "$BATS_TEST_DIRNAME")/lib/functions/installs.bash"
+  ASDF_INSTALL_LOCK="$BASE_DIR/install-lock"
+  mkdir -p "$ASDF_INSTALL_LOCK"
+
+  run with_install_lock exit 3
+  [ "$status" -eq 3 ]
+  [ ! -d "$ASDF_INSTALL_LOCK/held" ]
+}
+
+@test "with_install_lock takes over a lock left by a process that is gone" {
+  . "$(dirname "$BATS_TEST_DIRNAME")/lib/functions/installs.bash"
+  ASDF_INSTALL_LOCK="$BASE_DIR/install-lock"
+  mkdir -p "$ASDF_INSTALL_LOCK/held"
+  printf "999999\n" >"$ASDF_INSTALL_LOCK/held/pid"
+
+  run with_install_lock printf "locked"
+  [ "$status" -eq 0 ]
+  [ "$output" = "locked" ]
+  [ ! -d "$ASDF_INSTALL_LOCK/held" ]
+}
+
 @test "install_command doesn't install system version" {
   run asdf install dummy system
   [ "$status" -eq 0 ]
@@ -220,6 +401,87 @@ EOM
   [ "$output" == "HEY 1.0.0 FROM dummy" ]
 }
 
+@test "install command passes a JSON summary of the install to post install hooks" {
+  cat >$HOME/.asdfrc <<-'EOM'
+post_asdf_install_dummy = cat "$ASDF_INSTALL_SUMMARY_FILE"
+EOM
+
+  run asdf install dummy 1.0.0
+  [ "$status" -eq 0 ]
+  [[ "$output" == '{"plugin":"dummy","version":"1.0.0","install_type":"version","install_path":"'"$ASDF_DIR"'/installs/dummy/1.0.0","duration_seconds":'* ]]
+  [[ "$output" == *'"download_size_kb":'[0-9]*',"trace_path":"'"$ASDF_DIR"'/trace"}' ]]
+  [ -z "$(ls -A "$ASDF_DIR/tmp")" ]
+}
+
+@test "install command runs hooks with hook_shell when it is set" {
+  cat >$HOME/.asdfrc <<-'EOM'
+hook_shell = sh
+pre_asdf_install_dummy = printf '%s %s\n' "$0" "$1"; [ -z "$BASH_VERSION" ] && echo not bash
+EOM
+
+  run asdf install dummy 1.0.0
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "pre_asdf_install_dummy 1.0.0" ]
+  [ "${lines[1]}" = "not bash" ]
+}
+
+@test "install command runs a hook set to an executable file directly" {
+  mkdir -p $HOME/hooks
+  printf '#!/bin/sh\necho "hook ran with $*"\n' >$HOME/hooks/pre-install
+  chmod +x $HOME/hooks/pre-install
+  cat >$HOME/.asdfrc <<-'EOM'
+pre_asdf_install_dummy = ~/hooks/pre-install
+EOM
+
+  run asdf install dummy 1.0.0
+  [ "$status" -eq 0 ]
+  [ "$output" = "hook ran with 1.0.0" ]
+}
+
+@test "install command does not pass denylisted variables to hooks" {
+  cat >$HOME/.asdfrc <<-'EOM'
+plugin_env_denylist = AWS_*
+pre_asdf_install_dummy = echo ${AWS_SECRET_ACCESS_KEY:-unset} ${AWS_REGION:-unset} $1
+EOM
+
+  AWS_SECRET_ACCESS_KEY=secret AWS_REGION=region run asdf install dummy 1.0.0
+  [ "$output" == "unset unset 1.0.0" ]
+}
+
+@test "install command passes allowlisted variables to plugin scripts" {
+  cat >$HOME/.asdfrc <<-'EOM'
+plugin_env_denylist = AWS_* http_proxy
+plugin_env_allowlist = AWS_REGION
+EOM
+  sed -i.bak '2i\
+echo download ${AWS_SECRET_ACCESS_KEY:-unset} ${AWS_REGION:-unset} ${http_proxy:-unset} ${ASDF_INSTALL_VERSION}
+' "$ASDF_DIR/plugins/dummy/bin/download"
+
+  AWS_SECRET_ACCESS_KEY=secret AWS_REGION=region http_proxy=proxy run asdf install dummy 1.0.0
+  [ "$status" -eq 0 ]
+  [ "$output" == "download unset region unset 1.0.0" ]
+}
+
+@test "install command only passes allowlisted variables when there is no denylist" {
+  cat >$HOME/.asdfrc <<-'EOM'
+plugin_env_allowlist = AWS_REGION
+pre_asdf_install_dummy = echo ${AWS_REGION:-unset} ${HOME:-unset} ${OTHER:-unset} $1
+EOM
+
+  AWS_REGION=region OTHER=other run asdf install dummy 1.0.0
+  [ "$output" == "region $HOME unset 1.0.0" ]
+}
+
+@test "install command keeps HOME, TMPDIR and the proxy variables with only an allowlist" {
+  cat >$HOME/.asdfrc <<-'EOM'
+plugin_env_allowlist = AWS_REGION
+pre_asdf_install_dummy = echo ${HOME:-unset} ${TMPDIR:-unset} ${https_proxy:-unset} ${NO_PROXY:-unset} $1
+EOM
+
+  https_proxy=proxy NO_PROXY=localhost run asdf install dummy 1.0.0
+  [[ "$output" == "$HOME $ASDF_DIR/tmp/"*" proxy localhost 1.0.0" ]]
+}
+
 @test "install command without arguments installs versions from legacy files" {
   echo 'legacy_version_file = yes' >$HOME/.asdfrc
   echo '1.2.0' >>$PROJECT_DIR/.dummy-version
@@ -286,3 +548,288 @@ EOM
   [ ! -d $ASDF_DIR/installs/dummy-broken/1.1.0 ]
   [ "$output" == "Download failed!" ]
 }
+
+@test "install_command sets TMPDIR to a private directory under the data dir" {
+  run asdf install dummy 1.0.0
+  [ "$status" -eq 0 ]
+  run grep "^TMPDIR=$ASDF_DIR/tmp/install-dummy-" $ASDF_DIR/installs/dummy/1.0.0/env
+  [ "$status" -eq 0 ]
+}
+
+@test "install_command removes its temporary directory on success and failure" {
+  run asdf install dummy 1.0.0
+  [ "$status" -eq 0 ]
+  run asdf install dummy-broken 1.0.0
+  [ "$status" -eq 1 ]
+  [ -z "$(find $ASDF_DIR/tmp -mindepth 1 -maxdepth 1 -type d)" ]
+}
+
+install_signed_download_script() {
+  mkdir -p "$BASE_DIR/bin"
+  cat <<'EOF_GPG' >"$BASE_DIR/bin/gpg"
+#!/usr/bin/env bash
+signature="${*: -2:1}"
+grep -q "good" "$signature" || {
+  echo "BAD signature" >&2
+  exit 1
+}
+EOF_GPG
+  chmod +x "$BASE_DIR/bin/gpg"
+  PATH="$BASE_DIR/bin:$PATH"
+
+  cat <<'EOF_DOWNLOAD' >"$ASDF_DIR/plugins/dummy/bin/download"
+#!/usr/bin/env bash
+echo "data" >"$ASDF_DOWNLOAD_PATH/dummy.tar.gz"
+echo "$DUMMY_SIGNATURE" >"$ASDF_DOWNLOAD_PATH/dummy.tar.gz.asc"
+EOF_DOWNLOAD
+}
+
+@test "install_command verifies download signatures against signature_keyring" {
+  install_signed_download_script
+  echo "signature_keyring = ~/keyring.gpg" >"$HOME/.asdfrc"
+
+  DUMMY_SIGNATURE=good run asdf install dummy 1.0.0
+  [ "$status" -eq 0 ]
+  [ -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+}
+
+@test "install_command fails and removes the download when a signature does not verify" {
+  install_signed_download_script
+  echo "signature_keyring = ~/keyring.gpg" >"$HOME/.asdfrc"
+
+  DUMMY_SIGNATURE=bad run asdf install dummy 1.0.0
+  [ "$status" -eq 1 ]
+  [[ "$output" == *"Signature verification failed for dummy.tar.gz"* ]]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+  [ ! -d "$ASDF_DIR/downloads/dummy/1.0.0" ]
+}
+
+@test "install_command ignores signatures when no keyring is configured" {
+  install_signed_download_script
+
+  DUMMY_SIGNATURE=bad run asdf install dummy 1.0.0
+  [ "$status" -eq 0 ]
+}
+
+@test "install_command fails without a verified signature when require_signatures is yes" {
+  echo "require_signatures = yes" >"$HOME/.asdfrc"
+
+  run asdf install dummy 1.0.0
+  [ "$status" -eq 1 ]
+  [ "$output" = "No verified signatures found in the download and require_signatures is yes" ]
+}
+
+@test "install command removes the partial install and download when cancelled" {
+  cat >"$ASDF_DIR/plugins/dummy/bin/download" <<'EOM'
+#!/usr/bin/env bash
+touch "$ASDF_DOWNLOAD_PATH/partial" "$HOME/download-started"
+sleep 1
+EOM
+
+  asdf install dummy 1.0.0 >/dev/null &
+  local pid=$!
+  while [ ! -f "$HOME/download-started" ]; do sleep 0.1; done
+  kill -TERM "$pid"
+  status=0
+  wait "$pid" || status=$?
+
+  [ "$status" -eq 1 ]
+  [ ! -d "$ASDF_DIR/downloads/dummy/1.0.0" ]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+}
+
+@test "install command keeps an earlier download when cancelled" {
+  mkdir -p "$ASDF_DIR/downloads/dummy/1.0.0"
+  touch "$ASDF_DIR/downloads/dummy/1.0.0/kept"
+  install_fixture_plugin "dummy" "slow:download"
+
+  asdf install dummy 1.0.0 >/dev/null &
+  local pid=$!
+  while [ ! -f "$HOME/download-started" ]; do sleep 0.1; done
+  kill -TERM "$pid"
+  status=0
+  wait "$pid" || status=$?
+
+  [ "$status" -eq 1 ]
+  [ -f "$ASDF_DIR/downloads/dummy/1.0.0/kept" ]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+}
+
+@test "install command installs with a plugin that has no bin/download" {
+  install_fixture_plugin "dummy" "no-download"
+
+  run asdf install dummy 1.0.0
+  [ "$status" -eq 0 ]
+  [ -f "$ASDF_DIR/installs/dummy/1.0.0/version" ]
+}
+
+@test "install command fails without installing when bin/download fails" {
+  install_fixture_plugin "dummy" "failing:download"
+
+  run asdf install dummy 1.0.0
+  [ "$status" -eq 1 ]
+  [ "$output" = "download failed" ]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+  [ ! -d "$ASDF_DIR/downloads/dummy/1.0.0" ]
+}
+
+@test "install command fails without installing when bin/install fails" {
+  install_fixture_plugin "dummy" "failing:install"
+
+  run asdf install dummy 1.0.0
+  [ "$status" -eq 1 ]
+  [ "$output" = "install failed" ]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+}
+
+@test "install command installs a version of every plugin matching a pattern" {
+  install_mock_plugin "tool1"
+  install_mock_plugin "tool2"
+
+  run asdf install 'tool*' 1.0.0
+  [ "$status" -eq 0 ]
+  [ -d "$ASDF_DIR/installs/tool1/1.0.0" ]
+  [ -d "$ASDF_DIR/installs/tool2/1.0.0" ]
+  [ ! -d "$ASDF_DIR/installs/dummy" ]
+}
+
+@test "install command installs the set versions of plugins matching a pattern" {
+  install_mock_plugin "tool1"
+  install_mock_plugin "tool2"
+  cd "$PROJECT_DIR"
+  echo 'tool1 1.1.0' >"$PROJECT_DIR/.tool-versions"
+
+  run asdf install 'tool*'
+  [ "$status" -eq 0 ]
+  [ -d "$ASDF_DIR/installs/tool1/1.1.0" ]
+  [ ! -d "$ASDF_DIR/installs/tool2" ]
+}
+
+@test "install command fails when no plugin matches a pattern" {
+  run asdf install 'nothing*' 1.0.0
+  [ "$status" -eq 1 ]
+  [ "$output" = "No installed plugins match nothing*" ]
+}
+
+@test "install command --pin writes the resolved latest version to .tool-versions" {
+  cd "$PROJECT_DIR"
+  echo "dummy latest:1 1.0.0" >"$PROJECT_DIR/.tool-versions"
+
+  run asdf install --pin
+  [ "$status" -eq 0 ]
+  [[ "$output" = *"Pinned dummy latest:1 to 1.1.0 in $PROJECT_DIR/.tool-versions"* ]]
+  [ -d "$ASDF_DIR/installs/dummy/1.1.0" ]
+  [ "$(cat "$PROJECT_DIR/.tool-versions")" = "dummy 1.1.0 1.0.0" ]
+}
+
+@test "install command without --pin keeps latest in .tool-versions" {
+  cd "$PROJECT_DIR"
+  echo "dummy latest" >"$PROJECT_DIR/.tool-versions"
+
+  run asdf install dummy
+  [ "$status" -eq 0 ]
+  [ -d "$ASDF_DIR/installs/dummy/2.0.0" ]
+  [ "$(cat "$PROJECT_DIR/.tool-versions")" = "dummy latest" ]
+}
+
+@test "install command rejects --pin with an explicit version" {
+  run asdf install dummy latest --pin
+  [ "$status" -eq 1 ]
+  [ "$output" = "--pin only applies to versions read from .tool-versions files" ]
+}
+
+@test "install command refuses latest versions with ASDF_STRICT" {
+  cd "$PROJECT_DIR"
+  echo "dummy latest" >"$PROJECT_DIR/.tool-versions"
+
+  run env ASDF_STRICT=1 asdf install
+  [ "$status" -eq 1 ]
+  [ "$output" = "dummy latest in $PROJECT_DIR/.tool-versions is not an exact version, which ASDF_STRICT requires" ]
+}
+
+@test "install command logs version files and plugin scripts with --verbose" {
+  cd "$PROJECT_DIR"
+  echo "dummy 1.0.0" >"$PROJECT_DIR/.tool-versions"
+
+  run asdf --verbose install dummy
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"] asdf install dummy runs "*"command-install.bash"* ]]
+  [[ "$output" == *"] dummy: 1.0.0 in $PROJECT_DIR/.tool-versions"* ]]
+  [[ "$output" == *"] running dummy/bin/download with "*"ASDF_INSTALL_VERSION=1.0.0"* ]]
+  [[ "$output" == *"] dummy/bin/install exited with 0 after "*"ms"* ]]
+}
+
+@test "install command logs nothing without ASDF_DEBUG" {
+  cd "$PROJECT_DIR"
+  echo "dummy 1.0.0" >"$PROJECT_DIR/.tool-versions"
+
+  run env ASDF_DEBUG=0 asdf install dummy
+  [ "$status" -eq 0 ]
+  [[ "$output" != *"asdf: ["* ]]
+}
+
+@test "install command --dry-run prints the plan without installing" {
+  echo "pre_asdf_install_dummy = echo will install" >"$HOME/.asdfrc"
+
+  run asdf install dummy 1.0.0 --dry-run
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "Would create $ASDF_DIR/downloads/dummy/1.0.0" ]
+  [[ "${lines[1]}" = "Would run $ASDF_DIR/plugins/dummy/bin/download with "*"ASDF_INSTALL_VERSION=1.0.0"* ]]
+  [ "${lines[2]}" = "Would create $ASDF_DIR/installs/dummy/1.0.0" ]
+  [ "${lines[3]}" = "Would run hook pre_asdf_install_dummy: echo will install" ]
+  [[ "${lines[4]}" = "Would run $ASDF_DIR/plugins/dummy/bin/install with "* ]]
+  [[ "$output" = *"Would reshim dummy 1.0.0"* ]]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+  [ ! -d "$ASDF_DIR/downloads/dummy/1.0.0" ]
+  [ ! -f "$ASDF_DIR/shims/dummy" ]
+}
+
+@test "install command --tool-versions installs the versions in the given file" {
+  echo "dummy 1.0.0" >$PROJECT_DIR/.tool-versions
+  echo "dummy 1.1.0" >$HOME/release.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf install --tool-versions ../release.tool-versions
+  [ "$status" -eq 0 ]
+  [ -d "$ASDF_DIR/installs/dummy/1.1.0" ]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+}
+
+@test "install command installs the newest available version satisfying a constraint" {
+  echo "dummy ~1.0" >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf install
+  [ "$status" -eq 0 ]
+  [ -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.1.0" ]
+}
+
+@test "install command fails for a constraint no version satisfies" {
+  echo "dummy >=3" >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf install dummy
+  [ "$status" -eq 1 ]
+  [[ "$output" == *"No version of dummy satisfies >=3"* ]]
+}
+
+@test "install command --file installs the versions in the given file" {
+  echo "dummy 1.0.0" >$PROJECT_DIR/.tool-versions
+  mkdir $PROJECT_DIR/ci
+  echo "dummy 1.1.0" >$PROJECT_DIR/ci/tool-versions.ci
+  cd $PROJECT_DIR
+
+  run asdf install --file ci/tool-versions.ci
+  [ "$status" -eq 0 ]
+  [ -d "$ASDF_DIR/installs/dummy/1.1.0" ]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+}
+
+@test "install command --tool-versions does not take a version" {
+  echo "dummy 1.1.0" >$HOME/release.tool-versions
+
+  run asdf install dummy 1.0.0 --tool-versions $HOME/release.tool-versions
+  [ "$status" -eq 1 ]
+  [ "$output" = "--tool-versions only applies to versions read from a version file" ]
+}
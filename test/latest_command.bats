@@ -27,6 +27,35 @@ teardown() {
   [ "$status" -eq 0 ]
 }
 
+@test "[latest_command - dummy_plugin] falls back to bin/list-all without bin/latest-stable" {
+  install_fixture_plugin "dummy" "no-latest-stable"
+  run asdf latest dummy
+  [ "$output" = "2.0.0" ]
+  [ "$status" -eq 0 ]
+}
+
+@test "[latest_command - dummy_plugin] --refresh lists the versions again instead of reading the cache" {
+  install_fixture_plugin "dummy" "no-latest-stable"
+  run asdf latest dummy
+  [ "$output" = "2.0.0" ]
+
+  printf '#!/usr/bin/env bash\necho "3.0.0"\n' >"$ASDF_DIR/plugins/dummy/bin/list-all"
+
+  run asdf latest dummy
+  [ "$output" = "2.0.0" ]
+
+  run asdf latest dummy --refresh
+  [ "$status" -eq 0 ]
+  [ "$output" = "3.0.0" ]
+}
+
+@test "[latest_command - dummy_plugin] fails when bin/latest-stable fails" {
+  install_fixture_plugin "dummy" "failing:latest-stable"
+  run asdf latest dummy
+  [ "$status" -eq 1 ]
+  [[ "$output" == *"No compatible versions available (dummy [0-9])" ]]
+}
+
 @test "[latest_command - dummy_plugin] an invalid version should return an error" {
   run asdf latest dummy 3
   [ "$(echo "No compatible versions available (dummy 3)")" == "$output" ]
@@ -84,20 +113,136 @@ teardown() {
   [ "$status" -eq 1 ]
 }
 
+@test "[latest_command - dummy_legacy_plugin] latest_filter starting with + adds to the exclusion pattern" {
+  echo "latest_filter = +^5" >"$HOME/.asdfrc"
+  run asdf latest legacy-dummy
+  [ "$status" -eq 0 ]
+  [ "$output" = "4.0.0" ]
+}
+
+@test "[latest_command - dummy_legacy_plugin] latest_filter_<plugin> replaces the exclusion pattern" {
+  echo "latest_filter_legacy-dummy = ^[45]" >"$HOME/.asdfrc"
+  run asdf latest legacy-dummy
+  [ "$status" -eq 0 ]
+  [ "$output" = "3.0.0-beta2" ]
+}
+
 ################################
 ####      latest --all      ####
 ################################
 @test "[latest_command - all plugins] shows the latest stable version of all plugins" {
   run asdf install dummy 2.0.0
   run asdf install legacy-dummy 4.0.0
-  run asdf latest --all
+  run asdf latest --all --porcelain
   echo "output $output"
   [ "$(echo -e "dummy\t2.0.0\tinstalled\nlegacy-dummy\t5.1.0\tmissing\n")" == "$output" ]
   [ "$status" -eq 0 ]
 }
 
 @test "[latest_command - all plugins] not installed plugin should return missing" {
-  run asdf latest --all
+  run asdf latest --all --porcelain
   [ "$(echo -e "dummy\t2.0.0\tmissing\nlegacy-dummy\t5.1.0\tmissing\n")" == "$output" ]
   [ "$status" -eq 0 ]
 }
+
+@test "[latest_command - all plugins] shows a table with the query and how the latest version was found" {
+  run asdf install dummy 2.0.0
+  run asdf latest --all
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "PLUGIN        LATEST  QUERY  SOURCE             STATUS" ]
+  [ "${lines[1]}" = "dummy         2.0.0   (any)  bin/latest-stable  installed" ]
+  [ "${lines[2]}" = "legacy-dummy  5.1.0   (any)  bin/list-all       missing" ]
+}
+
+@test "[latest_command - all plugins] --short shows only the plugin and its version" {
+  run asdf install dummy 2.0.0
+  run asdf latest --all --short
+  [ "$status" -eq 0 ]
+  [ "$output" = $'dummy 2.0.0\nlegacy-dummy 5.1.0' ]
+}
+
+@test "[latest_command - all plugins] applies the query to all plugins" {
+  run asdf latest --all 1 --porcelain
+  [ "$status" -eq 0 ]
+  [ "$(echo -e "dummy\t1.1.0\tmissing\nlegacy-dummy\t1.1.0\tmissing\n")" == "$output" ]
+}
+
+@test "[latest_command - dummy_plugin] --json shows latest stable version as JSON" {
+  run asdf latest dummy --json
+  [ "$status" -eq 0 ]
+  [ "$output" = '{"plugin":"dummy","version":"2.0.0"}' ]
+}
+
+@test "[latest_command - all plugins] --json shows latest versions as a JSON array" {
+  run asdf install dummy 2.0.0
+  run asdf latest --all --json
+  [ "$status" -eq 0 ]
+  [ "$output" = '[{"plugin":"dummy","version":"2.0.0","installed":true},{"plugin":"legacy-dummy","version":"5.1.0","installed":false}]' ]
+}
+
+@test "[latest_command - dummy_plugin] --quiet prints nothing and fails when the latest version is not installed" {
+  install_dummy_version "1.1.0"
+  run asdf latest dummy --quiet
+  [ "$output" = "" ]
+  [ "$status" -eq 1 ]
+}
+
+@test "[latest_command - dummy_plugin] --quiet succeeds when the latest version is installed" {
+  install_dummy_version "2.0.0"
+  run asdf latest dummy --quiet
+  [ "$output" = "" ]
+  [ "$status" -eq 0 ]
+}
+
+@test "[latest_command - dummy_plugin] --installed-only shows the latest installed version" {
+  install_dummy_version "1.0.0"
+  install_dummy_version "1.1.0"
+  run asdf latest dummy --installed-only
+  [ "$output" = "1.1.0" ]
+  [ "$status" -eq 0 ]
+}
+
+@test "[latest_command - dummy_plugin] --installed-only sorts versions numerically" {
+  install_dummy_version "1.9.0"
+  install_dummy_version "1.10.0"
+  run asdf latest dummy 1 --installed-only
+  [ "$output" = "1.10.0" ]
+  [ "$status" -eq 0 ]
+}
+
+@test "[latest_command - dummy_plugin] --installed-only does not call the plugin" {
+  install_dummy_version "1.0.0"
+  install_fixture_plugin "dummy" "no-latest-stable" "no-list-all"
+  run asdf latest dummy --installed-only
+  [ "$output" = "1.0.0" ]
+  [ "$status" -eq 0 ]
+}
+
+@test "[latest_command - dummy_plugin] --installed-only fails when no matching version is installed" {
+  install_dummy_version "1.0.0"
+  run asdf latest dummy 2 --installed-only
+  [ "$output" = "No compatible versions installed (dummy 2)" ]
+  [ "$status" -eq 1 ]
+}
+
+@test "[latest_command - dummy_plugin] picks from the cached versions when offline" {
+  run asdf list-all dummy
+
+  run asdf --offline latest dummy 1
+  [ "$status" -eq 0 ]
+  [ "$output" = "1.1.0" ]
+}
+
+@test "[latest_command - dummy_plugin] --refresh fails when offline" {
+  run asdf --offline latest dummy --refresh
+  [ "$status" -eq 1 ]
+  [ "$output" = "Cannot refresh the versions of dummy while offline (--offline or ASDF_OFFLINE is set)" ]
+}
+
+@test "[latest_command - dummy_plugin] picks from the installed versions when offline without cached versions" {
+  install_dummy_version "1.0.0"
+
+  run asdf --offline latest dummy
+  [ "$status" -eq 0 ]
+  [ "$output" = "1.0.0" ]
+}
@@ -58,6 +58,47 @@ teardown() {
   [ "$status" -eq 0 ]
 }
 
+@test "list_command with --color always marks the current version in bold green" {
+  cd $PROJECT_DIR
+  echo 'dummy 1.1.0' >>$PROJECT_DIR/.tool-versions
+  run asdf install dummy 1.0.0
+  run asdf install dummy 1.1.0
+
+  run asdf --color always list dummy
+  [ "$status" -eq 0 ]
+  [ "$(echo -e "  1.0.0\n\033[1;32m *1.1.0\033[0m")" == "$output" ]
+}
+
+@test "list_command with --color always colors even when NO_COLOR is set" {
+  run asdf install dummy 1.0.0
+  echo 'dummy 1.0.0' >>$HOME/.tool-versions
+
+  NO_COLOR=1 run asdf --color=always list dummy
+  [ "$status" -eq 0 ]
+  [[ "$output" == *$'\033[1;32m'* ]]
+}
+
+@test "list_command with ASDF_COLOR=never prints no colors" {
+  run asdf install dummy 1.0.0
+  echo 'dummy 1.0.0' >>$HOME/.tool-versions
+
+  ASDF_COLOR=never run asdf list dummy
+  [ "$status" -eq 0 ]
+  [ " *1.0.0" == "$output" ]
+}
+
+@test "asdf with an invalid --color value should return an error" {
+  run asdf --color sometimes list dummy
+  [ "$status" -eq 1 ]
+  [ "--color must be auto, always or never" == "$output" ]
+}
+
+@test "asdf with --color and no value should return an error" {
+  run asdf --color
+  [ "$status" -eq 1 ]
+  [ "--color must be auto, always or never" == "$output" ]
+}
+
 @test "list_command with version filters installed versions" {
   run asdf install dummy 1.0
   run asdf install dummy 1.1
@@ -111,3 +152,110 @@ teardown() {
   run asdf list-all dummy
   [[ "$output" != *"ignore this error"* ]]
 }
+
+@test "list_command should sort installed versions by version" {
+  install_dummy_version "0.9.0"
+  install_dummy_version "0.10.0"
+  install_dummy_version "1.0.0-rc1"
+  install_dummy_version "1.0.0"
+
+  run asdf list dummy
+  [ "$status" -eq 0 ]
+  [ "$output" = "$(echo -e "  0.9.0\n  0.10.0\n  1.0.0-rc1\n  1.0.0")" ]
+}
+
+@test "list_all_command should sort versions the plugin lists out of order" {
+  cat >"$ASDF_DIR/plugins/dummy/bin/list-all" <<'EOM'
+#!/usr/bin/env bash
+echo "0.10.0 1.0.0 0.9.0"
+EOM
+
+  run asdf list-all dummy
+  [ "$status" -eq 0 ]
+  [ "$output" = "$(echo -e "0.9.0\n0.10.0\n1.0.0")" ]
+}
+
+@test "list_all_command reads the versions cached by an earlier call" {
+  run asdf list-all dummy
+  [ "$status" -eq 0 ]
+
+  printf '#!/usr/bin/env bash\necho "3.0.0"\n' >"$ASDF_DIR/plugins/dummy/bin/list-all"
+
+  run asdf list-all dummy
+  [ "$status" -eq 0 ]
+  [ "$output" = "$(echo -e "1.0.0\n1.1.0\n2.0.0")" ]
+
+  run asdf list-all dummy --refresh
+  [ "$status" -eq 0 ]
+  [ "$output" = "3.0.0" ]
+}
+
+@test "list_all_command does not cache the versions when list_all_cache_duration is 0" {
+  echo "list_all_cache_duration = 0" >$HOME/.asdfrc
+  run asdf list-all dummy
+  [ "$status" -eq 0 ]
+
+  printf '#!/usr/bin/env bash\necho "3.0.0"\n' >"$ASDF_DIR/plugins/dummy/bin/list-all"
+
+  run asdf list-all dummy
+  [ "$status" -eq 0 ]
+  [ "$output" = "3.0.0" ]
+}
+
+@test "list_all_command does not cache a failed list-all" {
+  run asdf list-all dummy-broken
+  [ "$status" -eq 1 ]
+  [ ! -f "$ASDF_DIR/cache/list-all/dummy-broken" ]
+}
+
+@test "list_all_command reads the cached versions however old when offline" {
+  run asdf list-all dummy
+  touch -t 200001010000 "$ASDF_DIR/cache/list-all/dummy"
+  printf '#!/usr/bin/env bash\necho "3.0.0"\n' >"$ASDF_DIR/plugins/dummy/bin/list-all"
+
+  run asdf --offline list-all dummy
+  [ "$status" -eq 0 ]
+  [ "$output" = "$(echo -e "1.0.0\n1.1.0\n2.0.0")" ]
+}
+
+@test "list_all_command fails without cached versions when offline" {
+  run asdf --offline list-all dummy
+  [ "$status" -eq 1 ]
+  [ "$output" = "No cached versions of dummy to list while offline (--offline or ASDF_OFFLINE is set)" ]
+}
+
+@test "list_command --json should list installed versions as JSON" {
+  run asdf install dummy 1.0.0
+  run asdf install dummy 1.1.0
+  cd $PROJECT_DIR
+  echo 'dummy 1.1.0' >>$PROJECT_DIR/.tool-versions
+
+  run asdf list dummy --json
+  [ "$status" -eq 0 ]
+  [ "$output" = '[{"plugin":"dummy","version":"1.0.0","current":false},{"plugin":"dummy","version":"1.1.0","current":true}]' ]
+}
+
+@test "list_command --json escapes control characters in versions" {
+  mkdir -p "$ASDF_DIR/installs/dummy/1.0.0"$'\r'
+
+  run asdf list dummy --json
+  [ "$status" -eq 0 ]
+  [ "$output" = '[{"plugin":"dummy","version":"1.0.0\u000d","current":false}]' ]
+}
+
+@test "list_all_command --json lists available versions as a JSON array" {
+  run asdf list-all dummy --json
+  [ "$status" -eq 0 ]
+  [ "$output" = '["1.0.0","1.1.0","2.0.0"]' ]
+}
+
+@test "list_command lists the plugins matching a pattern" {
+  install_mock_plugin "tool1"
+  install_mock_plugin "tool2"
+  run asdf install tool1 1.0.0
+  run asdf install tool2 1.1.0
+
+  run asdf list 'tool*'
+  [ "$status" -eq 0 ]
+  [ "$output" = "$(printf "tool1\n  1.0.0\ntool2\n  1.1.0")" ]
+}
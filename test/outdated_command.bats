@@ -0,0 +1,74 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  setup_asdf_dir
+  install_dummy_plugin
+  install_dummy_legacy_plugin
+
+  PROJECT_DIR="$HOME/project"
+  mkdir -p "$PROJECT_DIR"
+  cd "$PROJECT_DIR" || exit
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "outdated command shows current versions older than the latest" {
+  install_dummy_version "1.0.0"
+  echo "dummy 1.0.0" >"$PROJECT_DIR/.tool-versions"
+
+  run asdf outdated
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "PLUGIN  CURRENT  LATEST  STATUS" ]
+  [ "${lines[1]}" = "dummy   1.0.0    2.0.0   missing" ]
+  [ "${#lines[@]}" -eq 2 ]
+}
+
+@test "outdated command skips up to date, system and unset versions" {
+  echo "dummy 2.0.0" >"$PROJECT_DIR/.tool-versions"
+  echo "legacy-dummy system" >>"$PROJECT_DIR/.tool-versions"
+
+  run asdf outdated
+  [ "$status" -eq 0 ]
+  [ "$output" = "All tools are up to date" ]
+}
+
+@test "outdated command skips current versions newer than the latest" {
+  echo "dummy 3.0.0" >"$PROJECT_DIR/.tool-versions"
+
+  run asdf outdated
+  [ "$status" -eq 0 ]
+  [ "$output" = "All tools are up to date" ]
+}
+
+@test "outdated command only looks up the latest version of plugins with a current version" {
+  echo "dummy 1.0.0" >"$PROJECT_DIR/.tool-versions"
+  printf '#!/usr/bin/env bash\ntouch "%s/listed"\n' "$HOME" >"$ASDF_DIR/plugins/legacy-dummy/bin/list-all"
+
+  run asdf outdated
+  [ "$status" -eq 0 ]
+  [ "${lines[1]}" = "dummy   1.0.0    2.0.0   missing" ]
+  [ ! -f "$HOME/listed" ]
+}
+
+@test "outdated command prints JSON with --json" {
+  install_dummy_version "2.0.0"
+  echo "dummy 1.0.0" >"$PROJECT_DIR/.tool-versions"
+
+  run asdf outdated --json
+  [ "$status" -eq 0 ]
+  [ "$output" = '[{"plugin":"dummy","current":"1.0.0","latest":"2.0.0","installed":true}]' ]
+
+  echo "dummy 2.0.0" >"$PROJECT_DIR/.tool-versions"
+  run asdf outdated --json
+  [ "$output" = "[]" ]
+}
+
+@test "outdated command rejects unknown arguments" {
+  run asdf outdated --nope
+  [ "$status" -eq 1 ]
+  [ "$output" = "usage: asdf outdated [--json]" ]
+}
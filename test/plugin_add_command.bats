@@ -111,6 +111,91 @@ teardown() {
   [ "$output" = "plugin add path=${ASDF_DIR}/plugins/dummy source_url=${BASE_DIR}/repo-dummy" ]
 }
 
+@test "plugin_add command with --ref checks out and pins the ref" {
+  install_mock_plugin_repo "dummy"
+  git -C "${BASE_DIR}/repo-dummy" tag v1.0.0
+  git -C "${BASE_DIR}/repo-dummy" commit -q --allow-empty -m "new commit"
+
+  run asdf plugin add --ref v1.0.0 "dummy" "${BASE_DIR}/repo-dummy"
+  [ "$status" -eq 0 ]
+  [ "$(git -C "$ASDF_DIR/plugins/dummy" rev-parse HEAD)" = "$(git -C "${BASE_DIR}/repo-dummy" rev-parse v1.0.0)" ]
+  [ "$(git -C "$ASDF_DIR/plugins/dummy" config asdf.ref)" = "v1.0.0" ]
+}
+
+@test "plugin_list command with --refs shows the ref a plugin is pinned to" {
+  install_mock_plugin_repo "dummy"
+  git -C "${BASE_DIR}/repo-dummy" tag v1.0.0
+
+  run asdf plugin add --ref v1.0.0 "dummy" "${BASE_DIR}/repo-dummy"
+  run asdf plugin list --urls --refs
+  [ "$status" -eq 0 ]
+  [[ "$output" == "dummy"*"${BASE_DIR}/repo-dummy v1.0.0 $(git -C "${BASE_DIR}/repo-dummy" rev-parse --short v1.0.0)" ]]
+}
+
+@test "plugin_add command with an unknown --ref does not add the plugin" {
+  install_mock_plugin_repo "dummy"
+
+  run asdf plugin add "dummy" "${BASE_DIR}/repo-dummy" --ref=no-such-ref
+  [ "$status" -eq 1 ]
+  [ "$output" = "Plugin dummy was not added: ${BASE_DIR}/repo-dummy has no branch, tag or commit no-such-ref" ]
+  [ ! -d "$ASDF_DIR/plugins/dummy" ]
+}
+
+@test "plugin_add command with --ref and no value fails" {
+  run asdf plugin add "dummy" --ref
+  [ "$status" -eq 1 ]
+  [ "$output" = "--ref needs a branch, tag or commit" ]
+}
+
+@test "plugin_add command with --test adds a plugin that passes the test" {
+  install_mock_plugin_repo "dummy"
+
+  run asdf plugin add --test "dummy" "${BASE_DIR}/repo-dummy"
+  [ "$status" -eq 0 ]
+  [ "$output" = "plugin add path=${ASDF_DIR}/plugins/dummy source_url=${BASE_DIR}/repo-dummy" ]
+  [ -d "$ASDF_DIR/plugins/dummy" ]
+}
+
+@test "plugin_add command with --test removes a plugin whose list-all prints no versions" {
+  install_mock_plugin_repo "dummy"
+  printf '#!/usr/bin/env bash\n' >"${BASE_DIR}/repo-dummy/bin/list-all"
+  git -C "${BASE_DIR}/repo-dummy" commit -q -am "empty list-all"
+
+  run asdf plugin add --test "dummy" "${BASE_DIR}/repo-dummy"
+  [ "$status" -eq 1 ]
+  [ "$output" = "Plugin dummy failed its test and was not added: bin/list-all printed no versions" ]
+  [ ! -d "$ASDF_DIR/plugins/dummy" ]
+}
+
+@test "plugin_add command with --test removes a plugin without bin/install" {
+  install_mock_plugin_repo "dummy"
+  git -C "${BASE_DIR}/repo-dummy" rm -q bin/install
+  git -C "${BASE_DIR}/repo-dummy" commit -q -m "no install"
+
+  run asdf plugin add --test "dummy" "${BASE_DIR}/repo-dummy"
+  [ "$status" -eq 1 ]
+  [ "$output" = "Plugin dummy failed its test and was not added: it has no bin/install" ]
+  [ ! -d "$ASDF_DIR/plugins/dummy" ]
+}
+
+@test "plugin_add command uses the configured git credential helper" {
+  install_mock_plugin_repo "dummy"
+  local real_git
+  real_git=$(command -v git)
+  mkdir -p "$HOME/git-bin"
+  cat >"$HOME/git-bin/git" <<EOM
+#!/usr/bin/env bash
+printf "%s\\n" "\$*" >>"$HOME/git.log"
+exec "$real_git" "\$@"
+EOM
+  chmod +x "$HOME/git-bin/git"
+  echo "git_credential_helper = store --file ~/.git-credentials" >"$HOME/.asdfrc"
+
+  PATH="$HOME/git-bin:$PATH" run asdf plugin add "dummy" "${BASE_DIR}/repo-dummy"
+  [ "$status" -eq 0 ]
+  grep -F -- "-c credential.helper=store --file ~/.git-credentials clone" "$HOME/git.log"
+}
+
 @test "plugin_add command executes configured pre hook (generic)" {
   install_mock_plugin_repo "dummy"
 
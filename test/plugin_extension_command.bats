@@ -50,6 +50,23 @@ teardown() {
   [[ "$output" == *"asdf $plugin_name foo bar"* ]]
 }
 
+@test "asdf passes --color on to executable extension commands" {
+  plugin_path="$(get_plugin_path dummy)"
+
+  cat <<'EOF' >"$plugin_path/lib/commands/command-foo.bash"
+#!/usr/bin/env bash
+echo "color ${ASDF_COLOR:-unset}"
+EOF
+  chmod +x "$plugin_path/lib/commands/command-foo.bash"
+
+  run asdf --color never dummy foo
+  [ "$status" -eq 0 ]
+  [ "$output" = "color never" ]
+
+  run asdf --color=always dummy foo
+  [ "$output" = "color always" ]
+}
+
 @test "asdf can execute plugin bin commands" {
   plugin_path="$(get_plugin_path dummy)"
 
@@ -37,6 +37,22 @@ foo                           http://example.com/foo"
   [[ "$output" =~ "$expected_plugins_list" ]]
 }
 
+@test "plugin_list_all serves the stale repo without waiting for a sync when stale_while_revalidate is set" {
+  export ASDF_CONFIG_DEFAULT_FILE=$HOME/.asdfrc
+  cat >$ASDF_CONFIG_DEFAULT_FILE <<-EOM
+plugin_repository_last_check_duration = 0
+stale_while_revalidate = yes
+EOM
+  local expected="\
+bar                           http://example.com/bar
+dummy                        *http://example.com/dummy
+foo                           http://example.com/foo"
+
+  run asdf plugin list all
+  [ "$status" -eq 0 ]
+  [ "$output" = "$expected" ]
+}
+
 @test "plugin_list_all no immediate repo sync expected because check_duration is greater than 0" {
   export ASDF_CONFIG_DEFAULT_FILE=$HOME/.asdfrc
   echo 'plugin_repository_last_check_duration = 10' >$ASDF_CONFIG_DEFAULT_FILE
@@ -73,3 +89,46 @@ foo                           http://example.com/foo"
   [ "$status" -eq 0 ]
   [ "$output" = "$expected" ]
 }
+
+@test "plugin_list_all --refresh syncs the repo even when check_duration has not passed" {
+  export ASDF_CONFIG_DEFAULT_FILE=$HOME/.asdfrc
+  echo 'plugin_repository_last_check_duration = never' >$ASDF_CONFIG_DEFAULT_FILE
+
+  run asdf plugin list all --refresh
+  [ "$status" -eq 0 ]
+  [[ "$output" =~ "updating plugin repository..." ]]
+}
+
+@test "plugin_list_all uses the cached repo with a warning when the sync fails" {
+  export ASDF_CONFIG_DEFAULT_FILE=$HOME/.asdfrc
+  echo 'plugin_repository_last_check_duration = 0' >$ASDF_CONFIG_DEFAULT_FILE
+
+  run asdf plugin list all
+  [ "$status" -eq 0 ]
+  [[ "$output" =~ "foo                           http://example.com/foo" ]]
+  [[ "$output" =~ "warning: could not update the plugin repository, using the cached copy" ]]
+}
+
+@test "plugin_list_all syncs the repo when the last sync time is unknown" {
+  rm "$ASDF_DIR/tmp/repo-updated"
+
+  run asdf plugin list all
+  [ "$status" -eq 0 ]
+  [[ "$output" =~ "updating plugin repository..." ]]
+  [ -f "$ASDF_DIR/tmp/repo-updated" ]
+}
+
+@test "plugin_list_all uses the cached repository when offline" {
+  touch -t 200001010000 "$ASDF_DIR/tmp/repo-updated"
+
+  ASDF_OFFLINE=1 run asdf plugin list all
+  [ "$status" -eq 0 ]
+  [[ "$output" != *"updating plugin repository..."* ]]
+  [[ "$output" == *"bar"* ]]
+}
+
+@test "plugin_list_all --refresh fails when offline" {
+  ASDF_OFFLINE=1 run asdf plugin list all --refresh
+  [ "$status" -eq 1 ]
+  [ "$output" = "Cannot sync the plugin repository while offline (--offline or ASDF_OFFLINE is set)" ]
+}
@@ -0,0 +1,82 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  setup_asdf_dir
+  PROJECT_DIR=$HOME/project
+  mkdir -p $PROJECT_DIR
+  cd $PROJECT_DIR
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "plugin_new command creates the required scripts in asdf-<name>" {
+  run asdf plugin new mytool
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "Created the mytool plugin in asdf-mytool" ]
+
+  [ -x "$PROJECT_DIR/asdf-mytool/bin/list-all" ]
+  [ -x "$PROJECT_DIR/asdf-mytool/bin/download" ]
+  [ -x "$PROJECT_DIR/asdf-mytool/bin/install" ]
+  [ -f "$PROJECT_DIR/asdf-mytool/lib/utils.bash" ]
+  [ -f "$PROJECT_DIR/asdf-mytool/README.md" ]
+  [ -f "$PROJECT_DIR/asdf-mytool/.github/workflows/test.yml" ]
+  [ ! -e "$PROJECT_DIR/asdf-mytool/bin/latest-stable" ]
+
+  run grep -r "__TOOL_" "$PROJECT_DIR/asdf-mytool"
+  [ "$status" -eq 1 ]
+  grep -q 'TOOL_NAME="mytool"' "$PROJECT_DIR/asdf-mytool/lib/utils.bash"
+}
+
+@test "plugin_new command creates the optional callbacks in the given directory" {
+  run asdf plugin new mytool plugin --repo https://example.com/mytool --optional-callbacks
+  [ "$status" -eq 0 ]
+
+  [ -x "$PROJECT_DIR/plugin/bin/latest-stable" ]
+  [ -x "$PROJECT_DIR/plugin/bin/list-legacy-filenames" ]
+  [ -x "$PROJECT_DIR/plugin/bin/parse-legacy-file" ]
+  [ -x "$PROJECT_DIR/plugin/bin/help.overview" ]
+  grep -q 'GH_REPO="https://example.com/mytool"' "$PROJECT_DIR/plugin/lib/utils.bash"
+}
+
+@test "plugin_new command creates a plugin that lists the tags of its repository" {
+  git init -q "$BASE_DIR/tool"
+  git -C "$BASE_DIR/tool" -c user.name=Test -c user.email=test@example.com commit -q --allow-empty -m "tool"
+  git -C "$BASE_DIR/tool" tag v1.10.0
+  git -C "$BASE_DIR/tool" tag v1.2.0
+  git -C "$BASE_DIR/tool" tag v2.0.0-rc1
+
+  run asdf plugin new mytool --repo "$BASE_DIR/tool" --optional-callbacks
+  [ "$status" -eq 0 ]
+
+  run "$PROJECT_DIR/asdf-mytool/bin/list-all"
+  [ "$output" = "1.2.0 1.10.0 2.0.0-rc1" ]
+
+  run "$PROJECT_DIR/asdf-mytool/bin/latest-stable"
+  [ "$output" = "1.10.0" ]
+}
+
+@test "plugin_new command refuses a directory that is not empty" {
+  mkdir asdf-mytool
+  touch asdf-mytool/README.md
+
+  run asdf plugin new mytool
+  [ "$status" -eq 1 ]
+  [ "$output" = "asdf-mytool already exists and is not empty" ]
+}
+
+@test "plugin_new command with plugin name not matching valid regex fails" {
+  run asdf plugin new my.tool
+  [ "$status" -eq 1 ]
+  [ "$output" = "my.tool is invalid. Name must match regex ^[[:alpha:][:digit:]_-]+$" ]
+}
+
+@test "plugin_new command with --repo and no value fails" {
+  run asdf plugin new mytool --repo
+  [ "$status" -eq 1 ]
+  [ "$output" = "--repo needs the URL of the tool's repository" ]
+  [ ! -d asdf-mytool ]
+}
@@ -221,3 +221,216 @@ EOM
 UPDATE"
   [[ "$output" = *"${expected_output}" ]]
 }
+
+@test "asdf plugin-update --all prints a status table" {
+  install_mock_plugin_repo "dummy2"
+  run asdf plugin add "dummy2" "${BASE_DIR}/repo-dummy2"
+  git -C "${BASE_DIR}/repo-dummy" commit -q --allow-empty -m "new commit"
+
+  local old_ref new_ref
+  old_ref="$(git -C "$ASDF_DIR/plugins/dummy" rev-parse --short HEAD)"
+  new_ref="$(git -C "${BASE_DIR}/repo-dummy" rev-parse --short HEAD)"
+
+  run asdf plugin-update --all --jobs 2
+  [ "$status" -eq 0 ]
+  [[ "$output" = *"PLUGIN              STATUS    DETAIL"* ]]
+  [[ "$output" = *"dummy               updated   $old_ref -> $new_ref"* ]]
+  [[ "$output" = *"dummy2              current   "* ]]
+}
+
+@test "asdf plugin-update --all fails and reports the reason when an update fails" {
+  run asdf plugin-update --all does-not-exist
+  [ "$status" -eq 1 ]
+  [[ "$output" = *"dummy               failed    fatal: couldn't find remote ref does-not-exist"* ]]
+}
+
+@test "asdf plugin-update --all --fail-fast skips the updates after one fails" {
+  install_mock_plugin_repo "dummy2"
+  run asdf plugin add "dummy2" "${BASE_DIR}/repo-dummy2"
+  echo "# local change" >>"$ASDF_DIR/plugins/dummy/bin/list-all"
+
+  run asdf plugin-update --all --jobs 1 --fail-fast
+  [ "$status" -eq 1 ]
+  [[ "$output" = *"dummy               failed    "* ]]
+  [[ "$output" = *"dummy2              skipped   an earlier update failed"* ]]
+  [[ "$output" = *"Update failed for 1 of 1:
+  dummy"* ]]
+}
+
+@test "asdf plugin-update --all rejects an invalid --jobs value" {
+  run asdf plugin-update --all --jobs 0
+  [ "$status" -eq 1 ]
+  [ "$output" = "--jobs must be a positive integer" ]
+}
+
+@test "asdf plugin-update --all rejects --jobs without a value" {
+  run asdf plugin-update --all --jobs
+  [ "$status" -eq 1 ]
+  [ "$output" = "--jobs must be a positive integer" ]
+}
+
+@test "asdf plugin-update refuses to update a plugin with local changes" {
+  local plugin_path
+  plugin_path="$(get_plugin_path dummy)"
+  echo "# local change" >>"$plugin_path/bin/list-all"
+
+  run asdf plugin-update dummy
+  [ "$status" -eq 1 ]
+  [[ "$output" = *"dummy has local changes, use --force to discard them or --stash to reapply them after updating"* ]]
+  grep -q "# local change" "$plugin_path/bin/list-all"
+}
+
+@test "asdf plugin-update --force discards local changes" {
+  local plugin_path
+  plugin_path="$(get_plugin_path dummy)"
+  echo "# local change" >>"$plugin_path/bin/list-all"
+
+  run asdf plugin-update dummy --force
+  [ "$status" -eq 0 ]
+  ! grep -q "# local change" "$plugin_path/bin/list-all"
+}
+
+@test "asdf plugin-update --stash reapplies local changes after updating" {
+  local plugin_path
+  plugin_path="$(get_plugin_path dummy)"
+  echo "# local change" >>"$plugin_path/bin/list-all"
+  git -C "${BASE_DIR}/repo-dummy" commit -q --allow-empty -m "new commit"
+
+  run asdf plugin-update dummy --stash
+  [ "$status" -eq 0 ]
+  [ "$(git -C "$plugin_path" rev-parse HEAD)" = "$(git -C "${BASE_DIR}/repo-dummy" rev-parse HEAD)" ]
+  grep -q "# local change" "$plugin_path/bin/list-all"
+  [ -z "$(git -C "$plugin_path" stash list)" ]
+}
+
+@test "asdf plugin-update --stash keeps local changes in the stash when they conflict" {
+  local plugin_path
+  plugin_path="$(get_plugin_path dummy)"
+  echo "# local change" >>"$plugin_path/bin/list-all"
+  echo "# remote change" >>"${BASE_DIR}/repo-dummy/bin/list-all"
+  git -C "${BASE_DIR}/repo-dummy" commit -q -am "conflicting commit"
+
+  run asdf plugin-update dummy --stash
+  [ "$status" -eq 1 ]
+  [[ "$output" = *"Could not reapply local changes to dummy, they are kept in git stash"* ]]
+  [ -z "$(git -C "$plugin_path" status --porcelain --untracked-files=no)" ]
+  [ -n "$(git -C "$plugin_path" stash list)" ]
+}
+
+@test "asdf plugin-update updates the plugins matching a pattern" {
+  install_mock_plugin_repo "dummy2"
+  run asdf plugin add "dummy2" "${BASE_DIR}/repo-dummy2"
+  install_mock_plugin_repo "other"
+  run asdf plugin add "other" "${BASE_DIR}/repo-other"
+
+  run asdf plugin-update 'dummy*'
+  [ "$status" -eq 0 ]
+  [[ "$output" = *"dummy               current   "* ]]
+  [[ "$output" = *"dummy2              current   "* ]]
+  [[ "$output" != *"other"* ]]
+}
+
+@test "asdf plugin-update --dry-run prints the update without changing the plugin" {
+  git -C "${BASE_DIR}/repo-dummy" commit -q --allow-empty -m "new commit"
+
+  local old_ref new_ref
+  old_ref="$(git -C "$ASDF_DIR/plugins/dummy" rev-parse --short HEAD)"
+  new_ref="$(git -C "${BASE_DIR}/repo-dummy" rev-parse --short HEAD)"
+
+  run asdf plugin-update dummy --dry-run
+  [ "$status" -eq 0 ]
+  [[ "$output" = *"Would fetch master from origin into $ASDF_DIR/plugins/dummy and check it out ($old_ref -> $new_ref)"* ]]
+  [ "$(git -C "$ASDF_DIR/plugins/dummy" rev-parse --short HEAD)" = "$old_ref" ]
+}
+
+@test "asdf plugin-update keeps a pinned plugin on its tag" {
+  rm -rf "$ASDF_DIR/plugins/dummy"
+  git -C "${BASE_DIR}/repo-dummy" tag v1.0.0
+  run asdf plugin add --ref v1.0.0 dummy "${BASE_DIR}/repo-dummy"
+  git -C "${BASE_DIR}/repo-dummy" commit -q --allow-empty -m "new commit"
+
+  run asdf plugin-update dummy
+  [ "$status" -eq 0 ]
+  [[ "$output" = *"Updating dummy to v1.0.0"* ]]
+  [ "$(git -C "$ASDF_DIR/plugins/dummy" rev-parse HEAD)" = "$(git -C "${BASE_DIR}/repo-dummy" rev-parse v1.0.0)" ]
+}
+
+@test "asdf plugin-update updates a plugin pinned to a branch to the latest commit on that branch" {
+  rm -rf "$ASDF_DIR/plugins/dummy"
+  git -C "${BASE_DIR}/repo-dummy" branch stable
+  run asdf plugin add --ref stable dummy "${BASE_DIR}/repo-dummy"
+  git -C "${BASE_DIR}/repo-dummy" checkout -q stable
+  git -C "${BASE_DIR}/repo-dummy" commit -q --allow-empty -m "stable commit"
+  git -C "${BASE_DIR}/repo-dummy" checkout -q master
+  git -C "${BASE_DIR}/repo-dummy" commit -q --allow-empty -m "master commit"
+
+  run asdf plugin-update --all
+  [ "$status" -eq 0 ]
+  [ "$(git -C "$ASDF_DIR/plugins/dummy" rev-parse HEAD)" = "$(git -C "${BASE_DIR}/repo-dummy" rev-parse stable)" ]
+}
+
+@test "asdf plugin-update with a git-ref pins a pinned plugin to the new ref" {
+  rm -rf "$ASDF_DIR/plugins/dummy"
+  git -C "${BASE_DIR}/repo-dummy" tag v1.0.0
+  run asdf plugin add --ref v1.0.0 dummy "${BASE_DIR}/repo-dummy"
+  git -C "${BASE_DIR}/repo-dummy" commit -q --allow-empty -m "new commit"
+  git -C "${BASE_DIR}/repo-dummy" tag v1.1.0
+
+  run asdf plugin-update dummy v1.1.0
+  [ "$status" -eq 0 ]
+  [[ "$output" = *"Pinning dummy to v1.1.0"* ]]
+  [ "$(git -C "$ASDF_DIR/plugins/dummy" config asdf.ref)" = "v1.1.0" ]
+}
+
+@test "asdf plugin-update --unpin updates a pinned plugin to the default branch" {
+  rm -rf "$ASDF_DIR/plugins/dummy"
+  git -C "${BASE_DIR}/repo-dummy" tag v1.0.0
+  run asdf plugin add --ref v1.0.0 dummy "${BASE_DIR}/repo-dummy"
+  git -C "${BASE_DIR}/repo-dummy" commit -q --allow-empty -m "new commit"
+
+  run asdf plugin-update dummy --unpin
+  [ "$status" -eq 0 ]
+  [[ "$output" = *"Unpinning dummy"* ]]
+  [ "$(git -C "$ASDF_DIR/plugins/dummy" rev-parse HEAD)" = "$(git -C "${BASE_DIR}/repo-dummy" rev-parse master)" ]
+  run git -C "$ASDF_DIR/plugins/dummy" config asdf.ref
+  [ "$status" -eq 1 ]
+}
+
+@test "asdf plugin list --outdated shows the plugins behind their origin without updating them" {
+  local old_ref new_ref
+  old_ref="$(git -C "$ASDF_DIR/plugins/dummy" rev-parse --short HEAD)"
+
+  run asdf plugin list --outdated
+  [ "$status" -eq 0 ]
+  [ "$output" = "$(printf "%-20s%-10s%s\n%-20s%-10s%s" PLUGIN STATUS DETAIL dummy current "$old_ref (master)")" ]
+
+  git -C "${BASE_DIR}/repo-dummy" commit -q --allow-empty -m "new commit"
+  new_ref="$(git -C "${BASE_DIR}/repo-dummy" rev-parse --short HEAD)"
+
+  run asdf plugin list --outdated
+  [ "$status" -eq 0 ]
+  [[ "$output" = *"dummy               outdated  $old_ref -> $new_ref (master)"* ]]
+  [ "$(git -C "$ASDF_DIR/plugins/dummy" rev-parse --short HEAD)" = "$old_ref" ]
+}
+
+@test "asdf plugin list --outdated compares a pinned plugin with its pinned ref" {
+  rm -rf "$ASDF_DIR/plugins/dummy"
+  git -C "${BASE_DIR}/repo-dummy" tag v1.0.0
+  run asdf plugin add --ref v1.0.0 dummy "${BASE_DIR}/repo-dummy"
+  git -C "${BASE_DIR}/repo-dummy" commit -q --allow-empty -m "new commit"
+
+  run asdf plugin list --outdated
+  [ "$status" -eq 0 ]
+  [[ "$output" = *"dummy               current   $(git -C "${BASE_DIR}/repo-dummy" rev-parse --short v1.0.0) (v1.0.0)"* ]]
+}
+
+@test "asdf plugin-update fails straight away when offline" {
+  git -C "${BASE_DIR}/repo-dummy" commit -q --allow-empty -m "new commit"
+  local old_ref
+  old_ref="$(git -C "$ASDF_DIR/plugins/dummy" rev-parse --short HEAD)"
+
+  run asdf --offline plugin-update dummy
+  [ "$status" -eq 1 ]
+  [ "$output" = "Cannot update plugins while offline (--offline or ASDF_OFFLINE is set)" ]
+  [ "$(git -C "$ASDF_DIR/plugins/dummy" rev-parse --short HEAD)" = "$old_ref" ]
+}
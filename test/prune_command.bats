@@ -0,0 +1,105 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  setup_asdf_dir
+  install_dummy_plugin
+  install_dummy_version "1.0.0"
+  install_dummy_version "1.1.0"
+  install_dummy_version "2.0.0"
+
+  PROJECT_DIR=$HOME/project
+  mkdir -p $PROJECT_DIR/app $PROJECT_DIR/node_modules/dep
+  cd $HOME
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "prune_command --dry-run lists the unreferenced versions without removing them" {
+  echo 'dummy 1.0.0' >$HOME/.tool-versions
+  echo 'dummy 1.1.0' >$PROJECT_DIR/app/.tool-versions
+
+  run asdf prune $PROJECT_DIR --dry-run
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "Installed versions not referenced by any of the 2 version files found:" ]
+  [ "${lines[1]}" = "  dummy 2.0.0" ]
+  [ -d $ASDF_DIR/installs/dummy/2.0.0 ]
+}
+
+@test "prune_command --yes removes only the unreferenced versions" {
+  echo 'dummy 1.0.0' >$HOME/.tool-versions
+  echo 'dummy 1.1.0' >$PROJECT_DIR/app/.tool-versions
+
+  run asdf prune $PROJECT_DIR --yes
+  [ "$status" -eq 0 ]
+  [ -d $ASDF_DIR/installs/dummy/1.0.0 ]
+  [ -d $ASDF_DIR/installs/dummy/1.1.0 ]
+  [ ! -d $ASDF_DIR/installs/dummy/2.0.0 ]
+}
+
+@test "prune_command does not read version files under node_modules" {
+  echo 'dummy 1.0.0' >$HOME/.tool-versions
+  echo 'dummy 2.0.0' >$PROJECT_DIR/node_modules/dep/.tool-versions
+
+  run asdf prune $PROJECT_DIR --yes
+  [ "$status" -eq 0 ]
+  [ ! -d $ASDF_DIR/installs/dummy/2.0.0 ]
+}
+
+@test "prune_command keeps the newest installed version satisfying a constraint" {
+  echo 'dummy ^1' >$HOME/.tool-versions
+
+  run asdf prune --yes
+  [ "$status" -eq 0 ]
+  [ ! -d $ASDF_DIR/installs/dummy/1.0.0 ]
+  [ -d $ASDF_DIR/installs/dummy/1.1.0 ]
+  [ ! -d $ASDF_DIR/installs/dummy/2.0.0 ]
+}
+
+@test "prune_command keeps the versions in legacy version files when legacy_version_file is yes" {
+  echo 'legacy_version_file = yes' >$HOME/.asdfrc
+  echo 'dummy 1.0.0' >$HOME/.tool-versions
+  echo 'dummy-2.0.0' >$PROJECT_DIR/app/.dummy-version
+
+  run asdf prune $PROJECT_DIR --yes
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "Installed versions not referenced by any of the 2 version files found:" ]
+  [ -d $ASDF_DIR/installs/dummy/1.0.0 ]
+  [ ! -d $ASDF_DIR/installs/dummy/1.1.0 ]
+  [ -d $ASDF_DIR/installs/dummy/2.0.0 ]
+}
+
+@test "prune_command does not read legacy version files when legacy_version_file is no" {
+  echo 'dummy 1.0.0' >$HOME/.tool-versions
+  echo 'dummy-2.0.0' >$PROJECT_DIR/app/.dummy-version
+
+  run asdf prune $PROJECT_DIR --yes
+  [ "$status" -eq 0 ]
+  [ ! -d $ASDF_DIR/installs/dummy/2.0.0 ]
+}
+
+@test "prune_command reports when every version is referenced" {
+  echo 'dummy 1.0.0 1.1.0 2.0.0' >$HOME/.tool-versions
+
+  run asdf prune
+  [ "$status" -eq 0 ]
+  [ "$output" = "Every installed version is referenced by the 1 version files found" ]
+}
+
+@test "prune_command is cancelled when the prompt is declined" {
+  echo 'dummy 1.0.0' >$HOME/.tool-versions
+
+  run asdf prune <<<"n"
+  [ "$status" -eq 1 ]
+  [[ "$output" = *"Uninstall these 2 versions? [y/N] Prune cancelled" ]]
+  [ -d $ASDF_DIR/installs/dummy/2.0.0 ]
+}
+
+@test "prune_command fails for a project root that does not exist" {
+  run asdf prune $HOME/missing
+  [ "$status" -eq 1 ]
+  [ "$output" = "No such directory: $HOME/missing" ]
+}
@@ -140,3 +140,50 @@ EOM
   run asdf reshim dummy 1.0
   [ "$output" == "RESHIM" ]
 }
+
+@test "reshim command writes shim index" {
+  run asdf install dummy 1.0
+  run asdf install dummy 1.1
+
+  run asdf reshim dummy
+  [ "$status" -eq 0 ]
+  grep -x "dummy dummy 1.0" "$ASDF_DIR/shim-index"
+  grep -x "dummy dummy 1.1" "$ASDF_DIR/shim-index"
+}
+
+@test "shim lookups see shims written after the shim index" {
+  run asdf install dummy 1.0
+  [ -f "$ASDF_DIR/shim-index" ]
+
+  sleep 1
+  cat >"$ASDF_DIR/shims/handwritten" <<EOM
+#!/usr/bin/env bash
+# asdf-plugin: dummy 1.0
+exec "$ASDF_DIR/bin/asdf" exec "handwritten" "\$@"
+EOM
+  chmod +x "$ASDF_DIR/shims/handwritten"
+
+  run asdf shim-versions handwritten
+  [ "$status" -eq 0 ]
+  [ "$output" = "dummy 1.0" ]
+  grep -x "handwritten dummy 1.0" "$ASDF_DIR/shim-index"
+}
+
+@test "shim lookups scan the shims when there is no shim index" {
+  run asdf install dummy 1.0
+  rm -f "$ASDF_DIR/shim-index"
+
+  run asdf shim-versions dummy
+  [ "$status" -eq 0 ]
+  [ "$output" = "dummy 1.0" ]
+}
+
+@test "reshim command --dry-run prints the shims it would write without writing them" {
+  run asdf install dummy 1.0
+  rm "$ASDF_DIR/shims/dummy"
+
+  run asdf reshim dummy --dry-run
+  [ "$status" -eq 0 ]
+  [ "$output" = "Would write $ASDF_DIR/shims/dummy" ]
+  [ ! -f "$ASDF_DIR/shims/dummy" ]
+}
@@ -0,0 +1,67 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  setup_asdf_dir
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "shellenv prints the POSIX shell setup" {
+  run asdf shellenv bash
+  [ "$status" -eq 0 ]
+  [[ "${lines[0]}" = "export ASDF_DIR='"* ]]
+  [ "${lines[1]}" = "export ASDF_DATA_DIR='$ASDF_DIR'" ]
+  [[ "${lines[2]}" = "case \":\$PATH:\" in "* ]]
+  [ "${lines[4]}" = ". \"\$ASDF_DIR/lib/asdf.sh\"" ]
+}
+
+@test "shellenv adds the asdf directories to the PATH of the shell running it" {
+  data_dir=$ASDF_DIR
+  shellenv=$(asdf shellenv sh)
+
+  PATH="/usr/local/bin:/usr/bin:/bin"
+  eval "$shellenv"
+  [ "$PATH" = "$data_dir/shims:$ASDF_BIN:/usr/local/bin:/usr/bin:/bin" ]
+}
+
+@test "shellenv quotes the asdf directories" {
+  data_dir="$ASDF_DIR/it's \$HOME"
+  ASDF_DATA_DIR=$data_dir asdf shellenv sh >"$BATS_TMPDIR/shellenv"
+
+  . "$BATS_TMPDIR/shellenv"
+  [ "$ASDF_DATA_DIR" = "$data_dir" ]
+  [[ "$PATH" = "$data_dir/shims:"* ]]
+}
+
+@test "shellenv does not add the asdf directories to PATH twice" {
+  eval "$(asdf shellenv sh)"
+  first_path=$PATH
+
+  eval "$(asdf shellenv sh)"
+  [ "$PATH" = "$first_path" ]
+  [ "$(type -t asdf)" = "function" ]
+}
+
+@test "shellenv prints the fish setup" {
+  run asdf shellenv fish
+  [ "$status" -eq 0 ]
+  [ "${lines[1]}" = "set -gx ASDF_DATA_DIR '$ASDF_DIR'" ]
+  [ "${lines[2]}" = "fish_add_path --global --move --path \"\$ASDF_DATA_DIR/shims\" \"\$ASDF_DIR/bin\"" ]
+  [ "${lines[3]}" = "source \"\$ASDF_DIR/lib/asdf.fish\"" ]
+}
+
+@test "shellenv uses the shell in SHELL by default" {
+  run env SHELL=/usr/bin/fish asdf shellenv
+  [ "$status" -eq 0 ]
+  [[ "${lines[0]}" = "set -gx ASDF_DIR "* ]]
+}
+
+@test "shellenv fails for an unsupported shell" {
+  run asdf shellenv tcsh
+  [ "$status" -eq 1 ]
+  [ "$output" = "usage: asdf shellenv [bash|zsh|sh|ksh|fish|nu|pwsh]" ]
+}
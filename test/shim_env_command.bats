@@ -33,6 +33,17 @@ teardown() {
   [ "$output" == "$ASDF_DIR/installs/dummy/1.0/bin/dummy" ]
 }
 
+@test "asdf env sets the plugin, version and install path the shim uses" {
+  echo "dummy 1.0" >$PROJECT_DIR/.tool-versions
+  run asdf install
+
+  run asdf env dummy
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"ASDF_PLUGIN=dummy"* ]]
+  [[ "$output" == *"ASDF_VERSION=1.0"* ]]
+  [[ "$output" == *"ASDF_INSTALL_PATH=$ASDF_DIR/installs/dummy/1.0"* ]]
+}
+
 @test "asdf env should execute under plugin custom environment used for a shim" {
   echo "dummy 1.0" >$PROJECT_DIR/.tool-versions
   run asdf install
@@ -81,3 +92,36 @@ teardown() {
   run grep '::' <(echo "$path_line")
   [ "$duplicate_colon" == "" ]
 }
+
+@test "asdf env --direnv should print the PATH and plugin environment of the current versions" {
+  echo "dummy 1.0" >$PROJECT_DIR/.tool-versions
+  run asdf install
+
+  echo "export FOO=bar" >$ASDF_DIR/plugins/dummy/bin/exec-env
+
+  run asdf env --direnv
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "watch_file $PROJECT_DIR/.tool-versions" ]
+  [ "${lines[1]}" = "export FOO=bar" ]
+  [[ "${lines[2]}" == "export PATH=$ASDF_DIR/installs/dummy/1.0/bin:"* ]]
+  [ "${#lines[@]}" -eq 3 ]
+}
+
+@test "asdf env --direnv should use the first installed fallback version" {
+  run asdf install dummy 1.0
+  echo "dummy 2.0 1.0" >$PROJECT_DIR/.tool-versions
+
+  run asdf env --direnv
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"export PATH=$ASDF_DIR/installs/dummy/1.0/bin:"* ]]
+}
+
+@test "asdf env --direnv should warn about versions that are not installed" {
+  echo "dummy 2.0" >$PROJECT_DIR/.tool-versions
+
+  run asdf env --direnv
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "watch_file $PROJECT_DIR/.tool-versions" ]
+  [ "${lines[1]}" = "warning: dummy 2.0 is not installed" ]
+  [[ "$output" != *"export PATH="* ]]
+}
@@ -33,6 +33,28 @@ teardown() {
   [ "$status" -eq 0 ]
 }
 
+@test "asdf exec --tool-versions uses the version in the given file" {
+  echo "dummy 1.0" >$PROJECT_DIR/.tool-versions
+  echo "dummy 1.1" >$HOME/release.tool-versions
+  run asdf install dummy 1.0
+  run asdf install dummy 1.1
+
+  run asdf exec --tool-versions $HOME/release.tool-versions dummy world hello
+  [ "$output" == "This is Dummy 1.1! hello world" ]
+  [ "$status" -eq 0 ]
+}
+
+@test "shim exec uses the newest installed version satisfying a constraint" {
+  run asdf install dummy 1.0
+  run asdf install dummy 1.1
+  run asdf install dummy 2.0
+  echo "dummy ^1" >$PROJECT_DIR/.tool-versions
+
+  run $ASDF_DIR/shims/dummy world hello
+  [ "$output" == "This is Dummy 1.1! hello world" ]
+  [ "$status" -eq 0 ]
+}
+
 @test "asdf exec should pass all arguments to executable even if shim is not in PATH" {
   echo "dummy 1.0" >$PROJECT_DIR/.tool-versions
   run asdf install
@@ -80,6 +102,14 @@ teardown() {
   echo "$output" | grep -q "No version is set for command dummy" 2>/dev/null
 }
 
+@test "shim exec should suggest the global config file when there is no .tool-versions file" {
+  run asdf install dummy 1.0
+
+  run $ASDF_DIR/shims/dummy world hello
+  [ "$status" -eq 126 ]
+  echo "$output" | grep -q "Consider adding one of the following versions in your config file at $HOME/.tool-versions" 2>/dev/null
+}
+
 @test "shim exec should suggest which plugin to use when no version is selected" {
   run asdf install dummy 1.0
   run asdf install dummy 2.0.0
@@ -192,6 +222,125 @@ teardown() {
   [ "$status" -eq 0 ]
 }
 
+@test "shim exec uses the plugin whose version is set in the environment when several plugins provide the tool" {
+  cp -rf $ASDF_DIR/plugins/dummy $ASDF_DIR/plugins/mummy
+  sed -i -e 's/Dummy/Mummy/' $ASDF_DIR/plugins/mummy/bin/install
+
+  run asdf install dummy 1.0
+  run asdf install mummy 3.0
+
+  echo "dummy 1.0" >$PROJECT_DIR/.tool-versions
+  run env ASDF_MUMMY_VERSION=3.0 $ASDF_DIR/shims/dummy world hello
+  [ "$status" -eq 0 ]
+  [ "$output" == "This is Mummy 3.0! hello world" ]
+
+  echo "mummy 3.0" >$PROJECT_DIR/.tool-versions
+  run env ASDF_DUMMY_VERSION=1.0 $ASDF_DIR/shims/dummy world hello
+  [ "$status" -eq 0 ]
+  [ "$output" == "This is Dummy 1.0! hello world" ]
+}
+
+@test "shim exec fails for a version set in the environment that is not installed even if another plugin provides the tool" {
+  cp -rf $ASDF_DIR/plugins/dummy $ASDF_DIR/plugins/mummy
+  sed -i -e 's/Dummy/Mummy/' $ASDF_DIR/plugins/mummy/bin/install
+
+  run asdf install dummy 1.0
+  run asdf install mummy 3.0
+
+  echo "dummy 1.0" >$PROJECT_DIR/.tool-versions
+  run env ASDF_MUMMY_VERSION=9.9 $ASDF_DIR/shims/dummy world hello
+  [ "$status" -eq 126 ]
+  [[ "$output" == *"asdf install mummy 9.9"* ]]
+  [[ "$output" != *"asdf install dummy 1.0"* ]]
+}
+
+@test "shim exec runs the newest installed version when missing_version_behavior is latest_installed" {
+  run asdf install dummy 1.0
+  run asdf install dummy 1.1.0
+  echo "missing_version_behavior = latest_installed" >$HOME/.asdfrc
+
+  touch $PROJECT_DIR/.tool-versions
+
+  run $ASDF_DIR/shims/dummy world hello
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"warning: No version is set for dummy, using 1.1.0 (missing_version_behavior = latest_installed)"* ]]
+  [[ "$output" == *"This is Dummy 1.1.0! hello world"* ]]
+}
+
+@test "shim exec runs the system executable when missing_version_behavior is system" {
+  run asdf install dummy 1.0
+  echo "missing_version_behavior = system" >$HOME/.asdfrc
+
+  touch $PROJECT_DIR/.tool-versions
+
+  mkdir $PROJECT_DIR/foo/
+  echo "echo System" >$PROJECT_DIR/foo/dummy
+  chmod +x $PROJECT_DIR/foo/dummy
+
+  run env PATH=$PATH:$PROJECT_DIR/foo $ASDF_DIR/shims/dummy hello
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"warning: No version is set for dummy, using system (missing_version_behavior = system)"* ]]
+  [[ "$output" == *"System" ]]
+}
+
+@test "shim exec reads missing_version_behavior for the plugin providing the command" {
+  run asdf install dummy 1.0
+  printf "missing_version_behavior = error\nmissing_version_behavior_dummy = system\n" >$HOME/.asdfrc
+
+  touch $PROJECT_DIR/.tool-versions
+
+  mkdir $PROJECT_DIR/foo/
+  echo "echo System" >$PROJECT_DIR/foo/dummy
+  chmod +x $PROJECT_DIR/foo/dummy
+
+  run env PATH=$PATH:$PROJECT_DIR/foo $ASDF_DIR/shims/dummy hello
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"warning: No version is set for dummy, using system (missing_version_behavior = system)"* ]]
+  [[ "$output" == *"System" ]]
+}
+
+@test "shim exec still fails for a set version that is not installed whatever missing_version_behavior is" {
+  run asdf install dummy 1.0
+  echo "missing_version_behavior = latest_installed" >$HOME/.asdfrc
+
+  echo "dummy 2.0.0" >$PROJECT_DIR/.tool-versions
+
+  run $ASDF_DIR/shims/dummy world hello
+  [ "$status" -eq 126 ]
+  [[ "$output" == *"No preset version installed for command dummy"* ]]
+}
+
+@test "shim exec runs the system executable for a version that is not installed when fallback_to_system is yes" {
+  run asdf install dummy 1.0
+  echo "fallback_to_system = yes" >$HOME/.asdfrc
+
+  echo "dummy 2.0.0" >$PROJECT_DIR/.tool-versions
+
+  mkdir $PROJECT_DIR/foo/
+  echo "echo System" >$PROJECT_DIR/foo/dummy
+  chmod +x $PROJECT_DIR/foo/dummy
+
+  run env PATH=$PATH:$PROJECT_DIR/foo $ASDF_DIR/shims/dummy hello
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"warning: dummy 2.0.0 is not installed, using system (fallback_to_system = yes)"* ]]
+  [[ "$output" == *"System" ]]
+}
+
+@test "shim exec honors fallback_to_system for a single plugin" {
+  run asdf install dummy 1.0
+  printf "fallback_to_system = yes\nfallback_to_system_dummy = no\n" >$HOME/.asdfrc
+
+  echo "dummy 2.0.0" >$PROJECT_DIR/.tool-versions
+
+  mkdir $PROJECT_DIR/foo/
+  echo "echo System" >$PROJECT_DIR/foo/dummy
+  chmod +x $PROJECT_DIR/foo/dummy
+
+  run env PATH=$PATH:$PROJECT_DIR/foo $ASDF_DIR/shims/dummy hello
+  [ "$status" -eq 126 ]
+  [[ "$output" == *"No preset version installed for command dummy"* ]]
+}
+
 @test "shim exec should fallback to system executable when specified version is system" {
   run asdf install dummy 1.0
 
@@ -247,6 +396,32 @@ teardown() {
   [ "$output" == "sourced custom" ]
 }
 
+@test "shim exec tells the command which plugin, version and install it runs from" {
+  run asdf install dummy 2.0.0
+  mkdir $ASDF_DIR/plugins/dummy/shims
+  echo 'echo "$ASDF_PLUGIN $ASDF_VERSION $ASDF_INSTALL_PATH"' >$ASDF_DIR/plugins/dummy/shims/foo
+  chmod +x $ASDF_DIR/plugins/dummy/shims/foo
+  run asdf reshim dummy 2.0.0
+
+  echo "dummy 2.0.0" >$PROJECT_DIR/.tool-versions
+  run $ASDF_DIR/shims/foo
+  [ "$status" -eq 0 ]
+  [ "$output" == "dummy 2.0.0 $ASDF_DIR/installs/dummy/2.0.0" ]
+}
+
+@test "shim exec runs exec-env of a plugin without bin/download" {
+  install_fixture_plugin "dummy" "no-download" "exec-env"
+  run asdf install dummy 2.0.0
+  mkdir $ASDF_DIR/plugins/dummy/shims
+  echo 'echo $DUMMY_EXEC_ENV custom' >$ASDF_DIR/plugins/dummy/shims/foo
+  chmod +x $ASDF_DIR/plugins/dummy/shims/foo
+  run asdf reshim dummy 2.0.0
+
+  echo "dummy 2.0.0" >$PROJECT_DIR/.tool-versions
+  run $ASDF_DIR/shims/foo
+  [ "$output" == "dummy custom" ]
+}
+
 @test "shim exec with custom exec-env using ASDF_INSTALL_PATH" {
   run asdf install dummy 2.0.0
   echo 'export FOO=$ASDF_INSTALL_PATH/foo' >$ASDF_DIR/plugins/dummy/bin/exec-env
@@ -350,6 +525,25 @@ teardown() {
   [ "$output" == "This is Dummy 2.0.0! hello world" ]
 }
 
+@test "shim exec does not fall back to later versions with ASDF_STRICT" {
+  run asdf install dummy 1.0
+  echo "dummy 2.0.0 1.0" >$PROJECT_DIR/.tool-versions
+
+  run env ASDF_STRICT=1 $ASDF_DIR/shims/dummy world hello
+  [ "$status" -eq 126 ]
+  [[ "$output" == *"No preset version installed for command dummy"* ]]
+}
+
+@test "shim exec ignores missing_version_behavior with ASDF_STRICT" {
+  run asdf install dummy 1.0
+  echo "missing_version_behavior = latest_installed" >$HOME/.asdfrc
+  touch $PROJECT_DIR/.tool-versions
+
+  run env ASDF_STRICT=1 $ASDF_DIR/shims/dummy world hello
+  [ "$status" -eq 126 ]
+  [[ "$output" == *"No version is set for command dummy"* ]]
+}
+
 @test "shim exec uses plugin list-bin-paths" {
   exec_path="$ASDF_DIR/plugins/dummy/bin/list-bin-paths"
   custom_path="$ASDF_DIR/installs/dummy/1.0/custom"
@@ -447,3 +641,51 @@ EOM
   [ "$output" == "This is Dummy 1.0! hello world" ]
   [ "$status" -eq 0 ]
 }
+
+@test "shim exec reshims a version whose bin directory changed when auto_reshim is enabled" {
+  run asdf install dummy 1.0
+  echo dummy 1.0 >$PROJECT_DIR/.tool-versions
+  echo 'auto_reshim = yes' >$HOME/.asdfrc
+
+  touch $ASDF_DIR/installs/dummy/1.0/bin/added
+  chmod +x $ASDF_DIR/installs/dummy/1.0/bin/added
+
+  run $ASDF_DIR/shims/dummy
+  [ "$status" -eq 0 ]
+  [ -f $ASDF_DIR/shims/added ]
+}
+
+@test "shim exec does not reshim when auto_reshim is disabled" {
+  run asdf install dummy 1.0
+  echo dummy 1.0 >$PROJECT_DIR/.tool-versions
+
+  touch $ASDF_DIR/installs/dummy/1.0/bin/added
+  chmod +x $ASDF_DIR/installs/dummy/1.0/bin/added
+
+  run $ASDF_DIR/shims/dummy
+  [ "$status" -eq 0 ]
+  [ ! -f $ASDF_DIR/shims/added ]
+}
+
+@test "shim exec reshims after a command in auto_reshim_commands exits" {
+  run asdf install dummy 1.0
+  echo dummy 1.0 >$PROJECT_DIR/.tool-versions
+
+  cat >$ASDF_DIR/installs/dummy/1.0/bin/installer <<'EOM'
+#!/usr/bin/env bash
+touch "$(dirname "$0")/added"
+chmod +x "$(dirname "$0")/added"
+exit 3
+EOM
+  chmod +x $ASDF_DIR/installs/dummy/1.0/bin/installer
+  run asdf reshim dummy 1.0
+
+  cat >$HOME/.asdfrc <<'EOM'
+auto_reshim = yes
+auto_reshim_commands = installer
+EOM
+
+  run $ASDF_DIR/shims/installer
+  [ "$status" -eq 3 ]
+  [ -f $ASDF_DIR/shims/added ]
+}
@@ -0,0 +1,71 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  setup_asdf_dir
+  install_dummy_plugin
+
+  PROJECT_DIR=$HOME/project
+  mkdir -p $PROJECT_DIR
+  cd $PROJECT_DIR
+
+  # asdf lib needed to run generated shims
+  cp -rf $BATS_TEST_DIRNAME/../{bin,lib} $ASDF_DIR/
+
+  echo "dummy 1.0" >$PROJECT_DIR/.tool-versions
+  run asdf install
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "shimexec without a command should display usage" {
+  run asdf shimexec
+  [ "$status" -eq 1 ]
+  [ "$output" = "usage: asdf shimexec [--dry-run] <shim> [-- <args>...]" ]
+}
+
+@test "shimexec runs the command like its shim" {
+  run asdf shimexec dummy -- world hello
+  [ "$status" -eq 0 ]
+  [ "$output" = "This is Dummy 1.0! hello world" ]
+}
+
+@test "shimexec --dry-run prints the resolved command without running it" {
+  run asdf shimexec --dry-run dummy -- "big world" hello
+  [ "$status" -eq 0 ]
+  [[ "$output" = *"plugin      dummy"* ]]
+  [[ "$output" = *"version     1.0"* ]]
+  [[ "$output" = *"set by      $PROJECT_DIR/.tool-versions"* ]]
+  [[ "$output" = *"command     $ASDF_DIR/installs/dummy/1.0/bin/dummy big\\ world hello"* ]]
+  [[ "$output" != *"This is Dummy"* ]]
+}
+
+@test "shimexec --dry-run prints the environment added by exec-env" {
+  echo 'export FOO="$ASDF_INSTALL_VERSION-bar"' >$ASDF_DIR/plugins/dummy/bin/exec-env
+
+  run asdf shimexec --dry-run dummy
+  [ "$status" -eq 0 ]
+  [[ "$output" = *"  FOO=1.0-bar"* ]]
+}
+
+@test "shimexec --dry-run does not run the pre hook" {
+  cat >$HOME/.asdfrc <<-'EOM'
+pre_dummy_dummy = echo PRE $@
+EOM
+
+  run asdf shimexec --dry-run dummy -- hello
+  [ "$status" -eq 0 ]
+  [[ "$output" = *"pre hook    pre_dummy_dummy"* ]]
+  [[ "$output" != *"PRE hello"* ]]
+}
+
+@test "shimexec --dry-run reports a missing version like the shim" {
+  rm $PROJECT_DIR/.tool-versions
+
+  run asdf shimexec --dry-run dummy
+  [ "$status" -eq 126 ]
+  [[ "$output" = *"No version is set for command dummy"* ]]
+}
@@ -0,0 +1,73 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  ASDF_BATS_SPACE_IN_PATH=true setup_asdf_dir
+  install_dummy_plugin
+
+  # Run asdf itself from the data dir so that its own path also contains spaces
+  cp -rf "$BATS_TEST_DIRNAME"/../{bin,lib,defaults} "$ASDF_DIR/"
+  export ASDF_DIR
+  PATH="$ASDF_DIR/bin:$PATH"
+
+  PROJECT_DIR="$HOME/project dir ü"
+  mkdir -p "$PROJECT_DIR"
+  cd "$PROJECT_DIR"
+  echo "dummy 1.0" >"$PROJECT_DIR/.tool-versions"
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "shims run the selected version when the data dir contains spaces and UTF-8" {
+  run asdf install
+  [ "$status" -eq 0 ]
+
+  run "$ASDF_DIR/shims/dummy" world hello
+  [ "$status" -eq 0 ]
+  [ "$output" = "This is Dummy 1.0! hello world" ]
+}
+
+@test "exec-env receives install paths containing spaces and UTF-8" {
+  cat <<'EOF_ENV' >"$ASDF_DIR/plugins/dummy/bin/exec-env"
+export DUMMY_HOME="$ASDF_INSTALL_PATH/home"
+EOF_ENV
+  run asdf install
+  [ "$status" -eq 0 ]
+
+  run asdf env dummy
+  [ "$status" -eq 0 ]
+  [[ "$output" = *"DUMMY_HOME=$ASDF_DIR/installs/dummy/1.0/home"* ]]
+}
+
+@test "hooks run with their arguments when the data dir contains spaces and UTF-8" {
+  cat >"$HOME/.asdfrc" <<-'EOM'
+post_asdf_install_dummy = printf "installed %s\n" "$1" >"$HOME/hook output"
+EOM
+  run asdf install
+  [ "$status" -eq 0 ]
+  [ "$(cat "$HOME/hook output")" = "installed 1.0" ]
+}
+
+@test "reshim quotes the asdf path in shims written by older versions" {
+  run asdf install
+  [ "$status" -eq 0 ]
+  sed -i.bak -e 's|^exec "\(.*\)/bin/asdf" exec |exec \1/bin/asdf exec |' "$ASDF_DIR/shims/dummy"
+
+  run asdf reshim dummy
+  [ "$status" -eq 0 ]
+  run "$ASDF_DIR/shims/dummy" world hello
+  [ "$status" -eq 0 ]
+  [ "$output" = "This is Dummy 1.0! hello world" ]
+}
+
+@test "uninstall removes shims when the data dir contains spaces and UTF-8" {
+  run asdf install
+  [ "$status" -eq 0 ]
+
+  run asdf uninstall dummy 1.0
+  [ "$status" -eq 0 ]
+  [ ! -f "$ASDF_DIR/shims/dummy" ]
+}
@@ -0,0 +1,66 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  setup_asdf_dir
+  install_dummy_plugin
+  run asdf install dummy 1.0
+  run asdf install dummy 1.1
+
+  PROJECT_DIR="$HOME/project"
+  mkdir -p "$PROJECT_DIR"
+  printf "dummy 1.0 1.1\n" >"$PROJECT_DIR/.tool-versions"
+  echo "shim_metrics = yes" >"$HOME/.asdfrc"
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "stats commands summarizes the commands run in the current project" {
+  cd "$PROJECT_DIR"
+  run dummy
+  run dummy
+
+  run asdf stats commands
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "$PROJECT_DIR" ]
+  [ "${lines[1]}" = "  PLUGIN          VERSION       COMMAND               RUNS  LAST RUN" ]
+  [ "${lines[2]}" = "  dummy           1.0           dummy                    2  today" ]
+}
+
+@test "stats commands lists pinned versions that were never run" {
+  cd "$PROJECT_DIR"
+  run dummy
+
+  run asdf stats commands
+  [ "$status" -eq 0 ]
+  [ "${lines[3]}" = "Pinned in $PROJECT_DIR/.tool-versions but never run:" ]
+  [ "${lines[4]}" = "  dummy           1.1" ]
+}
+
+@test "stats commands with --all summarizes every project" {
+  cd "$PROJECT_DIR"
+  run dummy
+  mkdir "$HOME/other"
+  echo "dummy 1.1" >"$HOME/other/.tool-versions"
+  cd "$HOME/other"
+  run dummy
+
+  run asdf stats commands --all
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"$PROJECT_DIR"* ]]
+  [[ "$output" == *"$HOME/other"* ]]
+}
+
+@test "stats commands fails when shim metrics are off" {
+  echo "shim_metrics = no" >"$HOME/.asdfrc"
+  cd "$PROJECT_DIR"
+  run dummy
+
+  run asdf stats commands
+  [ "$status" -eq 1 ]
+  [[ "$output" == "No commands recorded, set shim_metrics = yes"* ]]
+  [ ! -f "$ASDF_DIR/metrics" ]
+}
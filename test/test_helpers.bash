@@ -9,7 +9,7 @@ unset ASDF_DIR
 
 setup_asdf_dir() {
   if [ -n "${ASDF_BATS_SPACE_IN_PATH:-}" ]; then
-    BASE_DIR="$(mktemp -dt "asdf with spaces.XXXX")"
+    BASE_DIR="$(mktemp -dt "asdf with spaces ünïcödé.XXXX")"
   else
     BASE_DIR="$(mktemp -dt asdf.XXXX)"
   fi
@@ -54,6 +54,51 @@ install_mock_plugin_repo() {
   git -C "${location}" commit -q -m "asdf ${plugin_name} plugin"
 }
 
+# Installs a copy of the dummy plugin with some of its scripts changed, so the
+# installer, exec and latest code paths can be tested with plugins that lack
+# or break a callback. Each capability is one of:
+#   no-<script>       remove bin/<script>, e.g. no-download or no-latest-stable
+#   exec-env          add a bin/exec-env that exports DUMMY_EXEC_ENV=<name>
+#   failing:<script>  make bin/<script> print "<script> failed" and exit 1
+#   slow:<script>     make bin/<script> touch $HOME/<script>-started and then
+#                     sleep for ASDF_FIXTURE_DELAY seconds (1 by default)
+install_fixture_plugin() {
+  local plugin_name=$1
+  shift
+  local plugin_path="$ASDF_DIR/plugins/$plugin_name"
+
+  rm -rf "$plugin_path"
+  install_mock_plugin "$plugin_name"
+
+  local capability script
+  for capability in "$@"; do
+    script=${capability#*:}
+    case $capability in
+    no-*)
+      rm "$plugin_path/bin/${capability#no-}"
+      ;;
+    exec-env)
+      printf "export DUMMY_EXEC_ENV=%s\\n" "$plugin_name" >"$plugin_path/bin/exec-env"
+      chmod +x "$plugin_path/bin/exec-env"
+      ;;
+    failing:*)
+      printf '#!/usr/bin/env bash\necho "%s failed" >&2\nexit 1\n' "$script" >"$plugin_path/bin/$script"
+      chmod +x "$plugin_path/bin/$script"
+      ;;
+    slow:*)
+      sed -i.bak "1a\\
+touch \"\$HOME/$script-started\"; sleep \"\${ASDF_FIXTURE_DELAY:-1}\"
+" "$plugin_path/bin/$script"
+      rm "$plugin_path/bin/$script.bak"
+      ;;
+    *)
+      printf "unknown fixture plugin capability: %s\\n" "$capability" >&2
+      return 1
+      ;;
+    esac
+  done
+}
+
 install_mock_plugin_version() {
   local plugin_name=$1
   local plugin_version=$2
@@ -103,3 +103,187 @@ EOM
   echo $output
   [ "$output" == "removed dummy 1.0.0" ]
 }
+
+@test "uninstall command removes a version of the plugins matching a pattern after confirmation" {
+  install_mock_plugin "dummy2"
+  run asdf install 'dummy*' 1.0.0
+  run asdf install dummy2 1.1.0
+
+  run asdf uninstall 'dummy*' 1.0.0 <<<"y"
+  [ "$status" -eq 0 ]
+  [[ "$output" = "Uninstall 1.0.0 of dummy dummy2? [y/N] "* ]]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+  [ ! -d "$ASDF_DIR/installs/dummy2/1.0.0" ]
+  [ -d "$ASDF_DIR/installs/dummy2/1.1.0" ]
+}
+
+@test "uninstall command with a pattern uninstalls the other plugins when one fails" {
+  install_fixture_plugin "dummy1" "failing:uninstall"
+  install_mock_plugin "dummy2"
+  run asdf install 'dummy*' 1.0.0
+
+  run asdf uninstall 'dummy*' 1.0.0 --yes
+  [ "$status" -eq 1 ]
+  [[ "$output" = *"Uninstall failed for 1 of 3:
+  dummy1 1.0.0" ]]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+  [ ! -d "$ASDF_DIR/installs/dummy2/1.0.0" ]
+}
+
+@test "uninstall command with a pattern and --fail-fast stops at the first failure" {
+  install_fixture_plugin "dummy1" "failing:uninstall"
+  install_mock_plugin "dummy2"
+  run asdf install 'dummy*' 1.0.0
+
+  run asdf uninstall 'dummy*' 1.0.0 --yes --fail-fast
+  [ "$status" -eq 1 ]
+  [[ "$output" = *"Stopped after the first failure" ]]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+  [ -d "$ASDF_DIR/installs/dummy2/1.0.0" ]
+}
+
+@test "uninstall command keeps the versions when a pattern uninstall is declined" {
+  install_mock_plugin "dummy2"
+  run asdf install 'dummy*' 1.0.0
+
+  run asdf uninstall 'dummy*' 1.0.0 <<<"n"
+  [ "$status" -eq 1 ]
+  [[ "$output" = *"Uninstall cancelled" ]]
+  [ -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+  [ -d "$ASDF_DIR/installs/dummy2/1.0.0" ]
+}
+
+@test "uninstall command skips the confirmation with --yes" {
+  install_mock_plugin "dummy2"
+  run asdf install 'dummy*' 1.0.0
+
+  run asdf uninstall 'dummy*' 1.0.0 --yes </dev/null
+  [ "$status" -eq 0 ]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+  [ ! -d "$ASDF_DIR/installs/dummy2/1.0.0" ]
+}
+
+@test "uninstall command --dry-run prints what it would remove" {
+  run asdf install dummy 1.0.0
+
+  run asdf uninstall dummy 1.0.0 --dry-run
+  [ "$status" -eq 0 ]
+  [ "$output" = "$(printf "Would remove %s\n" "$ASDF_DIR/shims/dummy" "$ASDF_DIR/installs/dummy/1.0.0" "$ASDF_DIR/receipts/dummy/1.0.0")" ]
+  [ -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+  [ -f "$ASDF_DIR/shims/dummy" ]
+}
+
+@test "uninstall command --all removes every version and their shims" {
+  run asdf install dummy 1.0.0
+  run asdf install dummy 1.1.0
+  run asdf install dummy ref:master
+  [ -f "$ASDF_DIR/shims/dummy" ]
+
+  run asdf uninstall dummy --all --yes
+  [ "$status" -eq 0 ]
+  [ ! -d "$ASDF_DIR/installs/dummy" ]
+  [ ! -f "$ASDF_DIR/shims/dummy" ]
+  [ -z "$(plugin_shims dummy 1.0.0)" ]
+}
+
+@test "uninstall command --all keeps the shims other plugins provide" {
+  install_mock_plugin "other"
+  run asdf install dummy 1.0.0
+  run asdf install other 1.0.0
+
+  run asdf uninstall dummy --all --yes
+  [ "$status" -eq 0 ]
+  [ -f "$ASDF_DIR/shims/dummy" ]
+  [ "$(grep "# asdf-plugin:" "$ASDF_DIR/shims/dummy")" = "# asdf-plugin: other 1.0.0" ]
+}
+
+@test "uninstall command --all asks for confirmation" {
+  run asdf install dummy 1.0.0
+  run asdf install dummy 1.1.0
+
+  run asdf uninstall dummy --all <<<"n"
+  [ "$status" -eq 1 ]
+  [ "$output" = "Uninstall every version of dummy: 1.0.0 1.1.0? [y/N] Uninstall cancelled" ]
+  [ -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+}
+
+@test "uninstall command --all-but-current keeps the project and home versions" {
+  run asdf install dummy 1.0.0
+  run asdf install dummy 1.1.0
+  run asdf install dummy 2.0.0
+  echo "dummy 1.1.0" >$PROJECT_DIR/.tool-versions
+  echo "dummy 2.0.0" >$HOME/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf uninstall dummy --all-but-current --yes
+  [ "$status" -eq 0 ]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+  [ -d "$ASDF_DIR/installs/dummy/1.1.0" ]
+  [ -d "$ASDF_DIR/installs/dummy/2.0.0" ]
+}
+
+@test "uninstall command --all-but-current keeps the newest version matching latest" {
+  run asdf install dummy 1.0.0
+  run asdf install dummy 1.1.0
+  run asdf install dummy 2.0.0
+  echo "dummy latest:1" >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf uninstall dummy --all-but-current --yes
+  [ "$status" -eq 0 ]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+  [ -d "$ASDF_DIR/installs/dummy/1.1.0" ]
+  [ ! -d "$ASDF_DIR/installs/dummy/2.0.0" ]
+}
+
+@test "uninstall command --all-but-current keeps the newest version satisfying a constraint" {
+  run asdf install dummy 1.0.0
+  run asdf install dummy 1.1.0
+  run asdf install dummy 2.0.0
+  echo "dummy ^1" >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf uninstall dummy --all-but-current --yes
+  [ "$status" -eq 0 ]
+  [ ! -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+  [ -d "$ASDF_DIR/installs/dummy/1.1.0" ]
+  [ ! -d "$ASDF_DIR/installs/dummy/2.0.0" ]
+}
+
+@test "uninstall command --all-but-current asks for confirmation" {
+  run asdf install dummy 1.0.0
+  run asdf install dummy 1.1.0
+  echo "dummy 1.1.0" >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf uninstall dummy --all-but-current <<<"n"
+  [ "$status" -eq 1 ]
+  [ "$output" = "Uninstall dummy 1.0.0 and keep 1.1.0? [y/N] Uninstall cancelled" ]
+  [ -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+}
+
+@test "uninstall command --all-but-current reports when only current versions are installed" {
+  run asdf install dummy 1.1.0
+  echo "dummy 1.1.0" >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf uninstall dummy --all-but-current
+  [ "$status" -eq 0 ]
+  [ "$output" = "Only the current versions of dummy are installed: 1.1.0" ]
+}
+
+@test "uninstall command --all-but-current refuses to run without a current version" {
+  run asdf install dummy 1.0.0
+  cd $PROJECT_DIR
+
+  run asdf uninstall dummy --all-but-current --yes
+  [ "$status" -eq 1 ]
+  [ "$output" = "No installed version of dummy is set in this directory or $HOME/.tool-versions, not uninstalling every version" ]
+  [ -d "$ASDF_DIR/installs/dummy/1.0.0" ]
+}
+
+@test "uninstall command --all-but-current does not take a version" {
+  run asdf uninstall dummy 1.0.0 --all-but-current
+  [ "$status" -eq 1 ]
+  [ "$output" = "usage: asdf uninstall <name> --all-but-current [--yes] [--fail-fast]" ]
+}
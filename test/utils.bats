@@ -172,6 +172,78 @@ teardown() {
   [ "$output" = "0.1.0|$HOME/.tool-versions" ]
 }
 
+@test "find_versions should not search more parent directories than ASDF_SEARCH_MAX_DEPTH" {
+  mkdir -p $PROJECT_DIR/child
+  echo "dummy 0.1.0" >$PROJECT_DIR/.tool-versions
+  echo "dummy 0.2.0" >$HOME/.tool-versions
+
+  ASDF_SEARCH_MAX_DEPTH=0 run find_versions "dummy" $PROJECT_DIR/child
+  [ "$status" -eq 0 ]
+  [ "$output" = "0.2.0|$HOME/.tool-versions" ]
+
+  ASDF_SEARCH_MAX_DEPTH=1 run find_versions "dummy" $PROJECT_DIR/child
+  [ "$status" -eq 0 ]
+  [ "$output" = "0.1.0|$PROJECT_DIR/.tool-versions" ]
+}
+
+@test "find_versions should warn about and ignore an ASDF_SEARCH_MAX_DEPTH that is not a whole number" {
+  mkdir -p $PROJECT_DIR/child
+  echo "dummy 0.1.0" >$PROJECT_DIR/.tool-versions
+  echo "dummy 0.2.0" >$HOME/.tool-versions
+
+  ASDF_SEARCH_MAX_DEPTH=one run find_versions "dummy" $PROJECT_DIR/child
+  [ "$status" -eq 0 ]
+  [[ "$output" == "warning: search_max_depth must be a whole number, ignoring 'one'"* ]]
+  [ "${lines[-1]}" = "0.1.0|$PROJECT_DIR/.tool-versions" ]
+}
+
+@test "find_versions should stop searching at ASDF_SEARCH_SKIP_PATHS" {
+  mkdir -p $PROJECT_DIR/child
+  echo "dummy 0.1.0" >$PROJECT_DIR/.tool-versions
+  echo "dummy 0.2.0" >$HOME/.tool-versions
+
+  ASDF_SEARCH_SKIP_PATHS="/nonexistent:$PROJECT_DIR" run find_versions "dummy" $PROJECT_DIR/child
+  [ "$status" -eq 0 ]
+  [ "$output" = "0.2.0|$HOME/.tool-versions" ]
+}
+
+@test "find_file_upwards should honor search_max_depth from the config file" {
+  mkdir -p $PROJECT_DIR/child
+  touch $PROJECT_DIR/.asdfrc
+  echo "search_max_depth = 0" >$HOME/.asdfrc
+  cd $PROJECT_DIR/child
+
+  run find_file_upwards ".asdfrc"
+  [ "$status" -eq 0 ]
+  [ "$output" = "" ]
+}
+
+@test "find_versions should search the tool_versions_filenames in order" {
+  mkdir -p $PROJECT_DIR/child
+  echo "tool_versions_filenames = .tools .tool-versions" >$HOME/.asdfrc
+  echo "dummy 0.1.0" >$PROJECT_DIR/.tool-versions
+  echo "dummy 0.2.0" >$PROJECT_DIR/.tools
+
+  run find_versions "dummy" $PROJECT_DIR/child
+  [ "$status" -eq 0 ]
+  [ "$output" = "0.2.0|$PROJECT_DIR/.tools" ]
+
+  rm $PROJECT_DIR/.tools
+  run find_versions "dummy" $PROJECT_DIR/child
+  [ "$status" -eq 0 ]
+  [ "$output" = "0.1.0|$PROJECT_DIR/.tool-versions" ]
+}
+
+@test "find_tool_versions should find any of ASDF_TOOL_VERSIONS_FILENAMES" {
+  mkdir -p $PROJECT_DIR/child
+  touch $PROJECT_DIR/asdf.lock
+  cd $PROJECT_DIR/child
+
+  ASDF_TOOL_VERSIONS_FILENAMES=".tool-versions asdf.lock" run find_tool_versions
+  [ "$status" -eq 0 ]
+  [ "$output" = "$PROJECT_DIR/asdf.lock" ]
+}
+
 @test "find_versions should return the version set by environment variable" {
   export ASDF_DUMMY_VERSION=0.2.0
 
@@ -231,6 +303,100 @@ teardown() {
   [[ "$output" =~ "0.1.0|$HOME/.dummy-version" ]]
 }
 
+@test "find_versions asks the version resolvers when no version file sets a version" {
+  mkdir -p "$ASDF_DIR/resolvers"
+  printf '#!/usr/bin/env bash\necho "0.2.0 from $1 in $2"\n' >"$ASDF_DIR/resolvers/company"
+  chmod +x "$ASDF_DIR/resolvers/company"
+  echo "version_resolvers = company" >$HOME/.asdfrc
+
+  run find_versions "dummy" $PROJECT_DIR
+  [ "$status" -eq 0 ]
+  [ "$output" = "0.2.0 from dummy in $PROJECT_DIR|$ASDF_DIR/resolvers/company" ]
+}
+
+@test "find_versions prefers the project version file to the version resolvers" {
+  mkdir -p "$ASDF_DIR/resolvers"
+  printf '#!/usr/bin/env bash\necho 0.2.0\n' >"$ASDF_DIR/resolvers/company"
+  chmod +x "$ASDF_DIR/resolvers/company"
+  echo "version_resolvers = company" >$HOME/.asdfrc
+  echo "dummy 0.1.0" >$PROJECT_DIR/.tool-versions
+
+  run find_versions "dummy" $PROJECT_DIR
+  [ "$status" -eq 0 ]
+  [ "$output" = "0.1.0|$PROJECT_DIR/.tool-versions" ]
+}
+
+@test "find_versions prefers \$HOME/.tool-versions to the version resolvers outside of \$HOME too" {
+  mkdir -p "$ASDF_DIR/resolvers" "$BASE_DIR/outside"
+  printf '#!/usr/bin/env bash\necho 0.2.0\n' >"$ASDF_DIR/resolvers/company"
+  chmod +x "$ASDF_DIR/resolvers/company"
+  echo "version_resolvers = company" >$HOME/.asdfrc
+  echo "dummy 0.1.0" >$HOME/.tool-versions
+
+  run find_versions "dummy" $PROJECT_DIR
+  [ "$status" -eq 0 ]
+  [ "$output" = "0.1.0|$HOME/.tool-versions" ]
+
+  run find_versions "dummy" "$BASE_DIR/outside"
+  [ "$status" -eq 0 ]
+  [ "$output" = "0.1.0|$HOME/.tool-versions" ]
+}
+
+@test "find_versions asks the version resolvers in the configured order" {
+  mkdir -p "$ASDF_DIR/resolvers"
+  printf '#!/usr/bin/env bash\n' >"$ASDF_DIR/plugins/dummy/bin/resolve-version"
+  printf '#!/usr/bin/env bash\necho 0.2.0\n' >"$ASDF_DIR/resolvers/company"
+  chmod +x "$ASDF_DIR/plugins/dummy/bin/resolve-version" "$ASDF_DIR/resolvers/company"
+  echo "version_resolvers = plugin company" >$HOME/.asdfrc
+
+  run find_versions "dummy" $PROJECT_DIR
+  [ "$status" -eq 0 ]
+  [ "$output" = "0.2.0|$ASDF_DIR/resolvers/company" ]
+
+  printf '#!/usr/bin/env bash\necho 0.1.0\n' >"$ASDF_DIR/plugins/dummy/bin/resolve-version"
+  run find_versions "dummy" $PROJECT_DIR
+  [ "$status" -eq 0 ]
+  [ "$output" = "0.1.0|$ASDF_DIR/plugins/dummy/bin/resolve-version" ]
+}
+
+@test "find_versions does not ask the version resolvers when ASDF_STRICT is set" {
+  mkdir -p "$ASDF_DIR/resolvers"
+  printf '#!/usr/bin/env bash\necho 0.2.0\n' >"$ASDF_DIR/resolvers/company"
+  chmod +x "$ASDF_DIR/resolvers/company"
+  echo "version_resolvers = company" >$HOME/.asdfrc
+
+  ASDF_STRICT=1 run find_versions "dummy" $PROJECT_DIR
+  [ "$status" -eq 0 ]
+  [ "$output" = "" ]
+}
+
+@test "with_proxy sets the proxy variables from the proxy settings" {
+  printf "proxy = http://proxy.example.com:3128\nno_proxy = localhost,.example.org\n" >$HOME/.asdfrc
+
+  run with_proxy env
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"https_proxy=http://proxy.example.com:3128"* ]]
+  [[ "$output" == *"ALL_PROXY=http://proxy.example.com:3128"* ]]
+  [[ "$output" == *"no_proxy=localhost,.example.org"* ]]
+}
+
+@test "with_proxy prefers ASDF_PROXY and ASDF_NO_PROXY to the settings" {
+  echo "proxy = http://proxy.example.com:3128" >$HOME/.asdfrc
+
+  ASDF_PROXY=socks5://socks.example.com:1080 ASDF_NO_PROXY=localhost run with_proxy env
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"all_proxy=socks5://socks.example.com:1080"* ]]
+  [[ "$output" == *"NO_PROXY=localhost"* ]]
+  [[ "$output" != *"proxy.example.com"* ]]
+}
+
+@test "with_proxy leaves the proxy environment alone without a proxy setting" {
+  https_proxy=http://env.example.com:8080 run with_proxy env
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"https_proxy=http://env.example.com:8080"* ]]
+  [[ "$output" != *"all_proxy="* ]]
+}
+
 @test "get_preset_version_for returns the current version" {
   cd $PROJECT_DIR
   echo "dummy 0.2.0" >.tool-versions
@@ -272,6 +438,43 @@ teardown() {
   [ "$output" = "path:/some/place with spaces" ]
 }
 
+@test "get_preset_version_for returns the version set in the given directory" {
+  echo "dummy 0.1.0" >$HOME/.tool-versions
+  echo "dummy 0.2.0" >$PROJECT_DIR/.tool-versions
+  cd $HOME
+  run get_preset_version_for "dummy" "$PROJECT_DIR"
+  [ "$status" -eq 0 ]
+  [ "$output" = "0.2.0" ]
+}
+
+@test "filter_versions_by_constraint keeps the release versions that satisfy every clause" {
+  run filter_versions_by_constraint ">=1.20,<1.22" <<<"$(printf "%s\n" 1.19.5 1.20.0 1.21.7 1.22.0 1.21.0-rc1)"
+  [ "$status" -eq 0 ]
+  [ "$output" = "$(printf "%s\n" 1.20.0 1.21.7)" ]
+}
+
+@test "filter_versions_by_constraint supports caret and tilde constraints" {
+  versions=$(printf "%s\n" 0.2.3 0.2.9 0.3.0 3.10.2 3.11.4 3.12.0 18.0.0 v18.12.1 19.0.0)
+
+  run filter_versions_by_constraint "^18" <<<"$versions"
+  [ "$output" = "$(printf "%s\n" 18.0.0 v18.12.1)" ]
+
+  run filter_versions_by_constraint "^0.2.3" <<<"$versions"
+  [ "$output" = "$(printf "%s\n" 0.2.3 0.2.9)" ]
+
+  run filter_versions_by_constraint "~3.11" <<<"$versions"
+  [ "$output" = "3.11.4" ]
+}
+
+@test "find_install_path for a constraint should return the newest installed version satisfying it" {
+  run find_install_path "dummy" "^0.1"
+  [ "$status" -eq 0 ]
+  [ "$output" = "$ASDF_DIR/installs/dummy/0.1.0" ]
+
+  run find_install_path "dummy" ">=0.1"
+  [ "$output" = "$ASDF_DIR/installs/dummy/0.2.0" ]
+}
+
 @test "get_executable_path for system version should return system path" {
   mkdir -p $ASDF_DIR/plugins/foo
   run get_executable_path "foo" "system" "ls"
@@ -446,3 +649,73 @@ EOF
   [ "$status" -eq 0 ]
   [ "$output" = "$message" ]
 }
+
+@test "print_warnings prints each collected warning once to stderr" {
+  ASDF_WARNINGS_FILE="$ASDF_DIR/tmp/warnings-test"
+  asdf_warn "first"
+  asdf_warn "second"
+  asdf_warn "first"
+
+  run print_warnings
+  [ "$status" -eq 0 ]
+  [ "$output" = "$(printf "warning: first\nwarning: second")" ]
+  [ ! -f "$ASDF_WARNINGS_FILE" ]
+}
+
+@test "print_warnings prints warnings as JSON when ASDF_WARNINGS_FORMAT is json" {
+  ASDF_WARNINGS_FILE="$ASDF_DIR/tmp/warnings-test"
+  ASDF_WARNINGS_FORMAT=json
+  asdf_warn 'a "quoted" warning'
+
+  run print_warnings
+  [ "$status" -eq 0 ]
+  [ "$output" = '{"warning":"a \"quoted\" warning"}' ]
+}
+
+@test "sort_versions sorts numeric parts as numbers" {
+  run sort_versions <<<"$(printf "%s\n" 10.0.0 0.10.0 0.9.0 9.1.0 v2.0.0)"
+  [ "$status" -eq 0 ]
+  [ "$output" = "$(printf "%s\n" 0.9.0 0.10.0 v2.0.0 9.1.0 10.0.0)" ]
+}
+
+@test "sort_versions sorts pre-releases before and patch levels after a release" {
+  run sort_versions <<<"$(printf "%s\n" 2.7.0-p0 1.0.0 1.0.0-rc1 2.7.0 1.0.0-beta.11 1.0.0-beta.2)"
+  [ "$status" -eq 0 ]
+  [ "$output" = "$(printf "%s\n" 1.0.0-beta.2 1.0.0-beta.11 1.0.0-rc1 1.0.0 2.7.0 2.7.0-p0)" ]
+}
+
+@test "shorten_path leaves paths that fit or without a width as they are" {
+  run shorten_path "$HOME/project/.tool-versions" ""
+  [ "$output" = "$HOME/project/.tool-versions" ]
+
+  run shorten_path "/a/.tool-versions" 20
+  [ "$output" = "/a/.tool-versions" ]
+}
+
+@test "shorten_path writes HOME as ~ and then keeps the end of the path" {
+  run shorten_path "$HOME/project/.tool-versions" 24
+  [ "$output" = "~/project/.tool-versions" ]
+
+  run shorten_path "$HOME/project/.tool-versions" 20
+  [ "$output" = "...ct/.tool-versions" ]
+}
+
+@test "terminal_width prints COLUMNS when it is a number" {
+  COLUMNS=72 run terminal_width
+  [ "$output" = "72" ]
+}
+
+@test "print_table shortens only the path column to fit the width" {
+  . "$(dirname "$BATS_TEST_DIRNAME")/lib/functions/versions.bash"
+
+  run print_table $'PLUGIN\tSOURCE\tSTATUS\nlong-plugin-name\t/a/very/long/source/path\tinstalled' 40 2
+  [ "${lines[0]}" = "PLUGIN            SOURCE       STATUS" ]
+  [ "${lines[1]}" = "long-plugin-name  ...rce/path  installed" ]
+}
+
+@test "print_table does not shorten a table without a path column" {
+  . "$(dirname "$BATS_TEST_DIRNAME")/lib/functions/versions.bash"
+
+  run print_table $'PLUGIN\tCURRENT\tLATEST\tSTATUS\nlong-plugin-name\t1.0.0-nightly-2000-01-01\t2.0.0\tmissing' 20
+  [ "${lines[1]}" = "long-plugin-name  1.0.0-nightly-2000-01-01  2.0.0   missing" ]
+}
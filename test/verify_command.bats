@@ -0,0 +1,69 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  setup_asdf_dir
+  install_dummy_plugin
+  run asdf install dummy 1.0.0
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "install command records an install receipt" {
+  [ -f "$ASDF_DIR/receipts/dummy/1.0.0" ]
+  grep "  ./bin/dummy$" "$ASDF_DIR/receipts/dummy/1.0.0"
+}
+
+@test "verify command reports an intact install" {
+  run asdf verify dummy 1.0.0
+  [ "$status" -eq 0 ]
+  [ "$output" = "dummy 1.0.0 is intact" ]
+}
+
+@test "verify command reports modified and missing files" {
+  echo "changed" >>"$ASDF_DIR/installs/dummy/1.0.0/bin/dummy"
+  rm "$ASDF_DIR/installs/dummy/1.0.0/version"
+  touch "$ASDF_DIR/installs/dummy/1.0.0/added"
+
+  run asdf verify dummy 1.0.0
+  [ "$status" -eq 1 ]
+  [ "${lines[0]}" = "modified  bin/dummy" ]
+  [ "${lines[1]}" = "missing   version" ]
+  [ "${lines[2]}" = "dummy 1.0.0 differs from its install receipt in 2 files" ]
+  [ "${lines[3]}" = "Run \`asdf verify dummy 1.0.0 --repair\` to reinstall it" ]
+}
+
+@test "verify command with --repair reinstalls a modified install" {
+  rm "$ASDF_DIR/installs/dummy/1.0.0/version"
+
+  run asdf verify dummy 1.0.0 --repair
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"Reinstalling dummy 1.0.0"* ]]
+  [ -f "$ASDF_DIR/installs/dummy/1.0.0/version" ]
+
+  run asdf verify dummy 1.0.0
+  [ "$status" -eq 0 ]
+}
+
+@test "verify command fails for installs without a receipt" {
+  rm "$ASDF_DIR/receipts/dummy/1.0.0"
+
+  run asdf verify dummy 1.0.0
+  [ "$status" -eq 1 ]
+  [ "${lines[0]}" = "dummy 1.0.0 has no install receipt, it was installed before asdf recorded them" ]
+}
+
+@test "verify command fails for versions that are not installed" {
+  run asdf verify dummy 2.0.0
+  [ "$status" -eq 1 ]
+  [ "$output" = "Version 2.0.0 of dummy is not installed" ]
+}
+
+@test "uninstall command removes the install receipt" {
+  run asdf uninstall dummy 1.0.0
+  [ "$status" -eq 0 ]
+  [ ! -f "$ASDF_DIR/receipts/dummy/1.0.0" ]
+}
@@ -0,0 +1,31 @@
+#!/usr/bin/env bats
+
+load test_helpers
+
+setup() {
+  setup_asdf_dir
+}
+
+teardown() {
+  clean_asdf_dir
+}
+
+@test "version should print the asdf version" {
+  run asdf version
+  [ "$status" -eq 0 ]
+  [[ "$output" == v* ]]
+}
+
+@test "version --verbose should print the version, commit and build date" {
+  run asdf version --verbose
+  [ "$status" -eq 0 ]
+  [[ "${lines[0]}" == "version: v"* ]]
+  [[ "${lines[1]}" == "commit: "* ]]
+  [[ "${lines[2]}" == "date: "* ]]
+}
+
+@test "--version accepts --verbose" {
+  run asdf --version --verbose
+  [ "$status" -eq 0 ]
+  [[ "${lines[0]}" == "version: v"* ]]
+}
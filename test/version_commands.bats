@@ -74,6 +74,41 @@ teardown() {
   [ "$output" = "$(echo "No compatible versions available (dummy 99)")" ]
 }
 
+@test "[local - dummy_plugin] --pin replaces latest versions in the local file" {
+  echo "dummy latest:1 # keep me" >$PROJECT_DIR/.tool-versions
+  echo "legacy-dummy latest" >>$PROJECT_DIR/.tool-versions
+
+  run asdf local --pin
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "Pinned dummy latest:1 to 1.1.0 in $PROJECT_DIR/.tool-versions" ]
+  [ "$(cat $PROJECT_DIR/.tool-versions)" = "$(printf "dummy 1.1.0 # keep me\nlegacy-dummy 5.1.0")" ]
+}
+
+@test "[local - dummy_plugin] --pin keeps the spacing of the pinned line" {
+  printf '  dummy\tlatest:1   2.0.0 # keep me\n\n' >$PROJECT_DIR/.tool-versions
+
+  run asdf local --pin
+  [ "$status" -eq 0 ]
+  [ "$(cat $PROJECT_DIR/.tool-versions)" = "$(printf '  dummy\t1.1.0   2.0.0 # keep me\n\n')" ]
+}
+
+@test "[local - dummy_plugin] --pin with a name only pins that plugin" {
+  echo "dummy latest" >$PROJECT_DIR/.tool-versions
+  echo "legacy-dummy latest" >>$PROJECT_DIR/.tool-versions
+
+  run asdf local dummy --pin
+  [ "$status" -eq 0 ]
+  [ "$(cat $PROJECT_DIR/.tool-versions)" = "$(printf "dummy 2.0.0\nlegacy-dummy latest")" ]
+}
+
+@test "[local - dummy_plugin] --pin reports when there is nothing to pin" {
+  echo "dummy 1.0.0" >$PROJECT_DIR/.tool-versions
+
+  run asdf local --pin
+  [ "$status" -eq 0 ]
+  [ "$output" = "No latest versions to pin in $PROJECT_DIR/.tool-versions" ]
+}
+
 @test "[local - dummy_legacy_plugin] with latest should use the latest installed version" {
   run asdf local "legacy-dummy" "latest"
   [ "$status" -eq 0 ]
@@ -126,6 +161,39 @@ teardown() {
   [ "$(cat $PROJECT_DIR/.tool-versions)" = "dummy 1.1.0" ]
 }
 
+@test "local should keep comments, blank lines and order when overwriting a version" {
+  printf '# tools\n\nfoobar 1.0.0\n  dummy   1.0.0  # pinned for ci\n\nbaz 2.0.0\n' >$PROJECT_DIR/.tool-versions
+
+  run asdf local "dummy" "1.1.0"
+  [ "$status" -eq 0 ]
+  [ "$(cat $PROJECT_DIR/.tool-versions)" = $'# tools\n\nfoobar 1.0.0\n  dummy 1.1.0  # pinned for ci\n\nbaz 2.0.0' ]
+}
+
+@test "local should only overwrite the first line of a plugin" {
+  printf 'dummy 1.0.0\ndummy 2.0.0\n' >$PROJECT_DIR/.tool-versions
+
+  run asdf local "dummy" "1.1.0"
+  [ "$status" -eq 0 ]
+  [ "$(cat $PROJECT_DIR/.tool-versions)" = $'dummy 1.1.0\ndummy 2.0.0' ]
+}
+
+@test "local should warn about malformed lines in the file it writes to" {
+  printf 'dummy 1.0.0\nruby=3.1.2\n' >$PROJECT_DIR/.tool-versions
+
+  run asdf local "dummy" "1.1.0"
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"warning: $PROJECT_DIR/.tool-versions:2:5: expected a space between ruby and its version"* ]]
+  [ "$(cat $PROJECT_DIR/.tool-versions)" = $'dummy 1.1.0\nruby=3.1.2' ]
+}
+
+@test "local should not match a plugin name in a comment" {
+  printf '# dummy 1.0.0\n' >$PROJECT_DIR/.tool-versions
+
+  run asdf local "dummy" "1.1.0"
+  [ "$status" -eq 0 ]
+  [ "$(cat $PROJECT_DIR/.tool-versions)" = $'# dummy 1.0.0\ndummy 1.1.0' ]
+}
+
 @test "local should append trailing newline before appending new version when missing" {
   echo -n 'foobar 1.0.0' >>$PROJECT_DIR/.tool-versions
 
@@ -379,6 +447,52 @@ teardown() {
   unset ASDF_DUMMY_VERSION
 }
 
+@test "shell wrapper function with only --unset restores the variables set in the session" {
+  . $(dirname "$BATS_TEST_DIRNAME")/asdf.sh
+  export ASDF_DUMMY_VERSION=1.0.0
+  asdf shell "dummy" "1.1.0"
+  asdf shell "legacy-dummy" "2.0.0"
+  [ "$ASDF_DUMMY_VERSION" = "1.1.0" ]
+
+  asdf shell --unset
+  [ "$ASDF_DUMMY_VERSION" = "1.0.0" ]
+  [ -z "${ASDF_LEGACY_DUMMY_VERSION+set}" ]
+  unset ASDF_DUMMY_VERSION
+}
+
+@test "shell wrapper function with only --unset restores values with quotes and dollar signs as they were" {
+  . $(dirname "$BATS_TEST_DIRNAME")/asdf.sh
+  export ASDF_DUMMY_VERSION='1.0.0 "$(touch $HOME/ran)" `x`'
+  asdf shell "dummy" "1.1.0"
+
+  asdf shell --unset
+  [ "$ASDF_DUMMY_VERSION" = '1.0.0 "$(touch $HOME/ran)" `x`' ]
+  [ ! -f "$HOME/ran" ]
+  unset ASDF_DUMMY_VERSION
+}
+
+@test "export-shell-version refuses to record a value with a newline" {
+  ASDF_DUMMY_VERSION=$'1.0.0\n2.0.0' run asdf export-shell-version sh "dummy" "1.1.0"
+  [ "$status" -eq 1 ]
+  [ "$output" = "ASDF_DUMMY_VERSION contains a newline, so asdf shell could not restore it
+false" ]
+}
+
+@test "env wrapper function with --restore unsets the session variables and removes the shims from PATH" {
+  . $(dirname "$BATS_TEST_DIRNAME")/asdf.sh
+  asdf shell "dummy" "1.1.0"
+
+  asdf env --restore
+  [ -z "${ASDF_DUMMY_VERSION+set}" ]
+  [[ ":$PATH:" != *":$ASDF_DIR/shims:"* ]]
+}
+
+@test "env --restore should emit an error when wrapper function is not loaded" {
+  run asdf env --restore
+  [ "$status" -eq 1 ]
+  [ "$output" = "Shell integration is not enabled. Please ensure you source asdf in your shell setup." ]
+}
+
 @test "shell wrapper function should return an error for missing plugins" {
   . $(dirname "$BATS_TEST_DIRNAME")/asdf.sh
   expected="No such plugin: nonexistent
@@ -450,6 +564,23 @@ false"
   [ "$output" = $'unset-env\nASDF_DUMMY_VERSION' ]
 }
 
+@test "export-shell-version --unset restores the earlier values of the session variables" {
+  ASDF_SHELL_PID=1234 ASDF_DUMMY_VERSION=1.0.0 asdf export-shell-version sh "dummy" "1.1.0"
+  ASDF_SHELL_PID=1234 asdf export-shell-version sh "legacy-dummy" "2.0.0"
+  [ -f "$ASDF_DIR/sessions/1234" ]
+
+  run env ASDF_SHELL_PID=1234 asdf export-shell-version fish --unset
+  [ "$status" -eq 0 ]
+  [ "$output" = $'set -gx ASDF_DUMMY_VERSION \'1.0.0\'\nset -e ASDF_LEGACY_DUMMY_VERSION' ]
+  [ ! -f "$ASDF_DIR/sessions/1234" ]
+}
+
+@test "export-shell-version --unset prints nothing without a session" {
+  run env ASDF_SHELL_PID=1234 asdf export-shell-version sh --unset
+  [ "$status" -eq 0 ]
+  [ "$output" = "" ]
+}
+
 @test "[shell - dummy_plugin] wrapper function should support latest" {
   . $(dirname "$BATS_TEST_DIRNAME")/asdf.sh
   asdf shell "dummy" "latest"
@@ -491,3 +622,40 @@ false"
   [ "$status" -eq 0 ]
   [ "$(cat $PROJECT_DIR/.tool-versions)" = "legacy-dummy 1.0.0 5.1.0" ]
 }
+
+@test "local --legacy should also write the legacy version file" {
+  echo 'legacy_version_file = yes' >$HOME/.asdfrc
+
+  run asdf local "dummy" "1.1.0" "1.0.0" --legacy
+  [ "$status" -eq 0 ]
+  [ "$(cat $PROJECT_DIR/.tool-versions)" = "dummy 1.1.0 1.0.0" ]
+  [ "$(cat $PROJECT_DIR/.dummy-version)" = "1.1.0" ]
+}
+
+@test "local --legacy should write the legacy version file with the plugin's write-legacy-file" {
+  echo 'legacy_version_file = yes' >$HOME/.asdfrc
+  cat >$ASDF_DIR/plugins/dummy/bin/write-legacy-file <<'EOM'
+#!/usr/bin/env bash
+printf "dummy-%s\n" "$2" >"$1"
+EOM
+  chmod +x $ASDF_DIR/plugins/dummy/bin/write-legacy-file
+
+  run asdf local "dummy" "1.1.0" --legacy
+  [ "$status" -eq 0 ]
+  [ "$(cat $PROJECT_DIR/.dummy-version)" = "dummy-1.1.0" ]
+}
+
+@test "global --legacy should write the legacy version file in HOME" {
+  echo 'legacy_version_file = yes' >$HOME/.asdfrc
+
+  run asdf global "dummy" "1.1.0" --legacy
+  [ "$status" -eq 0 ]
+  [ "$(cat $HOME/.dummy-version)" = "1.1.0" ]
+}
+
+@test "local --legacy should fail when legacy version files are off" {
+  run asdf local "dummy" "1.1.0" --legacy
+  [ "$status" -eq 1 ]
+  [ "$output" = "--legacy needs legacy_version_file = yes in your .asdfrc" ]
+  [ ! -f $PROJECT_DIR/.tool-versions ]
+}
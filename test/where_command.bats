@@ -69,3 +69,9 @@ function teardown() {
   [ "$status" -eq 1 ]
   [ "$output" = "$expected" ]
 }
+
+@test "where --json shows install location of selected version as JSON" {
+  run asdf where 'dummy' '1.0' --json
+  [ "$status" -eq 0 ]
+  [ "$output" = "{\"plugin\":\"dummy\",\"version\":\"1.0\",\"path\":\"$ASDF_DIR/installs/dummy/1.0\"}" ]
+}
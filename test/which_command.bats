@@ -106,3 +106,56 @@ teardown() {
   [ "$status" -eq 1 ]
   [ "$output" == "No dummy executable found for dummy 1.0" ]
 }
+
+@test "which --json should show the plugin, version and path as JSON" {
+  cd $PROJECT_DIR
+
+  run asdf which "dummy" --json
+  [ "$status" -eq 0 ]
+  [ "$output" = "{\"plugin\":\"dummy\",\"version\":\"1.0\",\"path\":\"$ASDF_DIR/installs/dummy/1.0/bin/dummy\"}" ]
+}
+
+@test "which should warn when another executable shadows the shim" {
+  cd $PROJECT_DIR
+  mkdir -p $HOME/bin
+  touch $HOME/bin/dummy
+  chmod +x $HOME/bin/dummy
+
+  run env PATH=$HOME/bin:$PATH asdf which dummy
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "$ASDF_DIR/installs/dummy/1.0/bin/dummy" ]
+  [ "${lines[1]}" = "warning: $HOME/bin/dummy is earlier in PATH and shadows the asdf shim for dummy" ]
+}
+
+@test "which --explain should show the version file and the rule that picked the version" {
+  cd $PROJECT_DIR
+
+  run asdf which "dummy" --explain
+  [ "$status" -eq 0 ]
+  [ "${lines[0]}" = "shim        $ASDF_DIR/shims/dummy" ]
+  [ "${lines[1]}" = "provided by dummy 1.0 1.1" ]
+  [ "${lines[2]}" = "version     dummy 1.0 set by $PROJECT_DIR/.tool-versions" ]
+  [ "${lines[3]}" = "selected    dummy 1.0 (first set version that provides dummy)" ]
+  [ "${lines[4]}" = "install     $ASDF_DIR/installs/dummy/1.0" ]
+  [ "${lines[5]}" = "bin path    $ASDF_DIR/installs/dummy/1.0/bin" ]
+  [ "${lines[6]}" = "executable  $ASDF_DIR/installs/dummy/1.0/bin/dummy" ]
+}
+
+@test "which --explain should show the environment variable that set the version" {
+  cd $PROJECT_DIR
+
+  run env ASDF_DUMMY_VERSION=1.1 asdf which --explain "dummy"
+  [ "$status" -eq 0 ]
+  [ "${lines[2]}" = "version     dummy 1.1 set by ASDF_DUMMY_VERSION environment variable" ]
+  [ "${lines[6]}" = "executable  $ASDF_DIR/installs/dummy/1.1/bin/dummy" ]
+}
+
+@test "which --explain should show when no set version provides the command" {
+  echo 'dummy 2.0' >$PROJECT_DIR/.tool-versions
+  cd $PROJECT_DIR
+
+  run asdf which "dummy" --explain
+  [ "$status" -eq 1 ]
+  [ "${lines[3]}" = "selected    none, no set version provides dummy" ]
+  [ "${lines[4]}" = "No preset version installed for command dummy" ]
+}